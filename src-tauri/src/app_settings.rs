@@ -1,7 +1,11 @@
 use std::fs::{File, OpenOptions};
 
 use crate::{
-    errors::AppError, model::settings::SettingsValues,
+    errors::AppError,
+    model::settings::{
+        default_check_updates_on_startup, default_disco_catalog_enabled,
+        default_scan_excludes, default_scan_max_depth, SettingsValues,
+    },
     util::paths::settings_json_path,
 };
 
@@ -69,6 +73,25 @@ impl AppSettings {
         SettingsValues {
             theme: AppSettings::default_theme(),
             skip_dir_selection_hint: false,
+            hide_jres: false,
+            reject_jres: false,
+            scan_max_depth: default_scan_max_depth(),
+            scan_excludes: default_scan_excludes(),
+            scan_follow_links: false,
+            scan_roots: vec![],
+            rescan_on_startup: false,
+            verify_on_startup: false,
+            check_updates_on_startup: default_check_updates_on_startup(),
+            discovery_offered: false,
+            sync_maven_toolchains: false,
+            sync_gradle_paths: false,
+            disco_catalog_enabled: default_disco_catalog_enabled(),
+            catalog_sources: vec![],
+            trusted_minisign_keys: vec![],
+            trusted_gpg_keys: vec![],
+            strict_signatures: false,
+            known_projects: vec![],
+            lifecycle_source: String::new(),
         }
     }
 
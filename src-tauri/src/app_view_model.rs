@@ -1,25 +1,130 @@
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
 use async_channel::{Receiver, Sender};
+use glob::Pattern;
 use tokio::runtime::Runtime;
 
 use crate::{
     app_settings::AppSettings,
+    build_tools::{gradle, maven},
+    discovery::{
+        self,
+        system::{find_system_java, resolve_java_links},
+    },
+    distribution_classifier::classify_distribution,
     errors::AppError,
-    jdk_finder::{find_jdk_from_exe_path, find_jdks_from_dir},
+    ide_config::{self, ExportPreview, IdeKind, ImportReport},
+    installer::{
+        self,
+        catalog::{
+            CatalogDistribution, CatalogMajorVersion, CatalogPackage,
+            JdkCatalog, PackageQuery,
+        },
+        disco::http_agent,
+        index::IndexCatalog,
+        signature::{SignaturePolicy, SignatureStatus},
+        uninstall, upgrade, InstallProgress, InstalledPackage,
+    },
+    jar_inspector::{self, JarInspection},
+    jdk_capabilities::detect_capabilities,
+    jdk_finder::{
+        find_jdk_from_exe_path, find_jdks_from_dir, java_executable_filename,
+        WalkOptions,
+    },
+    jdk_integrity, jdk_lifecycle,
+    jdk_scanner::{self, ScanProgress},
+    jdk_selector::{self, JdkRequirement},
     jdk_switcher,
-    model::{jdk::Jdk, settings::SettingsValues},
+    model::{
+        arch::Arch,
+        capabilities::JvmImpl,
+        distribution::Distribution,
+        integrity::{IntegrityStatus, JdkFingerprint},
+        jdk::Jdk,
+        jdk_source::JdkSource,
+        settings::SettingsValues,
+    },
+    project_analyzer::{
+        self,
+        workspace::{self, ReportFormat},
+        ProjectAnalysis,
+    },
     repo::jdk_repository::JdkRepository,
-    util::{self, paths::find_command_exe_path},
+    util::{
+        cancellation_token::CancellationToken,
+        exe_header::read_exe_arch,
+        paths::{self, find_command_exe_path},
+        release_file::read_release_file,
+    },
 };
 
 #[derive(Clone, serde::Serialize)]
 pub struct AppUiState {
     pub settings: SettingsValues,
     pub jdks: Vec<Jdk>,
+    /// Jdks found from other tools, waiting to be added.
+    pub discovered_jdks: Vec<Jdk>,
+}
+
+#[derive(serde::Serialize)]
+pub struct InstallResult {
+    pub jdks: Vec<Jdk>,
+    pub signature: SignatureStatus,
+}
+
+#[derive(serde::Serialize)]
+pub struct UpdateReport {
+    pub checked: usize,
+    /// Jdks which have a newer patch release, e.g. `Temurin 17.0.9 ->
+    /// 17.0.10+7`.
+    pub outdated: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct UpgradeResult {
+    pub old: Jdk,
+    pub new: Jdk,
+    pub signature: SignatureStatus,
+    /// Pin files rewritten to the new jdk.
+    pub migrated_pins: Vec<String>,
+    /// The old jdk was current, the new one is switched to.
+    pub switched: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct UninstallReport {
+    /// Names of the deleted jdks.
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct IntegrityReport {
+    pub checked: usize,
+    /// Names of jdks whose files were changed.
+    pub modified: Vec<String>,
+    /// Names of jdks whose files went missing.
+    pub corrupted: Vec<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct JarExit {
+    pub path: String,
+    /// `None` if the process is killed by a signal or cannot be waited.
+    pub code: Option<i32>,
+}
+
+#[derive(serde::Serialize)]
+pub struct RescanSummary {
+    /// Jdks which were not saved before.
+    pub found: usize,
+    pub removed: usize,
+    /// Roots which could not be scanned, their jdks are kept.
+    pub skipped: Vec<String>,
 }
 
 pub struct AppViewModel {
@@ -28,33 +133,187 @@ pub struct AppViewModel {
     ui_state: Mutex<AppUiState>,
     state_sender: Arc<Sender<AppUiState>>,
     state_receiver: Receiver<AppUiState>,
+    scan_token: Mutex<Option<CancellationToken>>,
+    scan_progress_sender: Arc<Sender<ScanProgress>>,
+    scan_progress_receiver: Receiver<ScanProgress>,
+    install_token: Mutex<Option<CancellationToken>>,
+    install_progress_sender: Arc<Sender<InstallProgress>>,
+    install_progress_receiver: Receiver<InstallProgress>,
 }
 
 #[allow(dead_code)]
 impl AppViewModel {
     pub fn new() -> Self {
         let (sender, receiver) = async_channel::unbounded::<AppUiState>();
+        let (scan_progress_sender, scan_progress_receiver) =
+            async_channel::unbounded::<ScanProgress>();
+        let (install_progress_sender, install_progress_receiver) =
+            async_channel::unbounded::<InstallProgress>();
         Self {
             jdk_repo: JdkRepository::new(),
             tokio_runtime: Runtime::new().unwrap(),
             ui_state: Mutex::new(AppUiState {
                 settings: AppSettings::load(),
                 jdks: vec![],
+                discovered_jdks: vec![],
             }),
             state_sender: Arc::new(sender),
             state_receiver: receiver,
+            scan_token: Mutex::new(None),
+            scan_progress_sender: Arc::new(scan_progress_sender),
+            scan_progress_receiver,
+            install_token: Mutex::new(None),
+            install_progress_sender: Arc::new(install_progress_sender),
+            install_progress_receiver,
         }
     }
 
     /// Load all saved jdks.
     pub fn load_jdks(&self) {
         let jdks = self.jdk_repo.get_all().unwrap_or_default();
+        let mut jdks = self.process_saved_jdks(jdks);
+        jdk_lifecycle::annotate(&mut jdks, &jdk_lifecycle::load_table());
+        jdk_selector::sort_jdks(&mut jdks);
+        self.sync_build_tools(&jdks);
+        self.update_ui_state(|state| {
+            state.jdks = jdks;
+        });
+    }
+
+    /// Find the newest jdk which matches the requirement, e.g. `21+` or
+    /// `temurin@17`.
+    pub fn select_jdk(
+        &self,
+        requirement: &str,
+    ) -> Result<Option<Jdk>, AppError> {
+        let requirement = requirement.parse::<JdkRequirement>()?;
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        Ok(jdk_selector::select_best(&jdks, &requirement).cloned())
+    }
+
+    /// Find the jdk pinned by the `.java-version` file of a dir.
+    pub fn resolve_pinned_jdk(
+        &self,
+        dir: &str,
+    ) -> Result<Option<Jdk>, AppError> {
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        Ok(jdk_selector::resolve_pin(Path::new(dir), &jdks)?.cloned())
+    }
+
+    /// Find the java requirements of a project dir from its build files and
+    /// the stored jdk which matches them best.
+    pub fn analyze_project(
+        &self,
+        dir: &str,
+    ) -> Result<ProjectAnalysis, AppError> {
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        project_analyzer::analyze_project(Path::new(dir), &jdks)
+    }
+
+    /// Register a project dir, the jdk it pins is kept from being
+    /// uninstalled and its pin is migrated on upgrades.
+    pub fn add_known_project(&self, dir: &str) -> Result<(), AppError> {
+        if !Path::new(dir).is_dir() {
+            return Err(AppError::new(format!("'{}' is not a dir", dir)));
+        }
+        self.update_ui_state(|state| {
+            let projects = &mut state.settings.known_projects;
+            if projects.iter().any(|item| item == dir) {
+                return Ok(());
+            }
+            projects.push(dir.to_string());
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    pub fn remove_known_project(&self, dir: &str) -> Result<(), AppError> {
         self.update_ui_state(|state| {
-            state.jdks = self.process_saved_jdks(jdks);
+            state.settings.known_projects.retain(|item| item != dir);
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Find the projects under a root and write their jdk requirements to a
+    /// report, the format is chosen by the file extension. Returns the
+    /// number of projects.
+    pub async fn export_workspace_report(
+        &self,
+        root: &str,
+        path: &str,
+    ) -> Result<usize, AppError> {
+        let format = ReportFormat::from_path(Path::new(path))?;
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        let root = PathBuf::from(root);
+        let report = self
+            .tokio_runtime
+            .spawn_blocking(move || workspace::scan_workspace(&root, &jdks))
+            .await
+            .map_err(|e| AppError::new(format!("Scan failed: {}", e)))?;
+        let content = workspace::render_report(&report, format)?;
+        std::fs::write(path, content).map_err(|e| {
+            AppError::new(format!("Cannot write '{}': {}", path, e))
+        })?;
+        Ok(report.projects.len())
+    }
+
+    /// Find the java version a jar or class file needs and the jdk to run it.
+    pub fn inspect_jar(&self, path: &str) -> Result<JarInspection, AppError> {
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        jar_inspector::inspect(Path::new(path), &jdks)
+    }
+
+    /// Run a jar with the newest jdk which can load it, returns the jdk. The
+    /// process is waited in the background, its exit is sent to `on_exit`.
+    pub fn run_jar<F>(&self, path: &str, on_exit: F) -> Result<Jdk, AppError>
+    where
+        F: FnOnce(JarExit) + Send + 'static,
+    {
+        let JarInspection { info, jdk } = self.inspect_jar(path)?;
+        let Some(jdk) = jdk else {
+            return Err(AppError::new(format!(
+                "No saved jdk can run '{}', it needs java {}+",
+                path,
+                info.min_java.unwrap_or(1)
+            )));
+        };
+        let mut child = jar_inspector::run_jar(Path::new(path), &jdk, &[])?;
+        let path = path.to_string();
+        self.tokio_runtime.spawn_blocking(move || {
+            let code = match child.wait() {
+                Ok(status) => status.code(),
+                Err(e) => {
+                    eprintln!("Cannot wait for '{}': {}", path, e);
+                    None
+                }
+            };
+            on_exit(JarExit { path, code });
         });
+        Ok(jdk)
     }
 
-    pub fn remove_jdk_by_path(&self, path: &str) -> Result<(), AppError> {
+    /// Remove a jdk from the list, jdks managed by other tools are only
+    /// removed if `force` is set, the user should be warned first.
+    pub fn remove_jdk_by_path(
+        &self,
+        path: &str,
+        force: bool,
+    ) -> Result<(), AppError> {
+        let source = self
+            .ui_state
+            .lock()
+            .unwrap()
+            .jdks
+            .iter()
+            .find(|item| item.path == path)
+            .map(|item| item.source);
+        if let Some(source) = source {
+            if source.is_tool_managed() && !force {
+                return Err(AppError::new(format!(
+                    "Jdk '{}' is managed by {}.",
+                    path, source
+                )));
+            }
+        }
         let ret = self.jdk_repo.remove_by_path(path);
         if ret.is_ok() {
             self.load_jdks();
@@ -62,27 +321,1003 @@ impl AppViewModel {
         ret
     }
 
-    /// Scan jdk recursively from a path.
-    pub fn try_add_jdks_from_dir(&self, path: &str) -> Result<usize, AppError> {
-        let path = PathBuf::from(path);
-        if !path.exists() {
-            return Err(AppError::new("Dir does not exist."));
-        }
-        let jdks = find_jdks_from_dir(&path)?;
-        let ret = self.jdk_repo.add_all(&jdks).map(|_| jdks.len());
+    /// Scan jdk recursively from a path, the scan runs in the background
+    /// and reports progress to the scan progress stream. The dir is saved
+    /// to the scan roots.
+    pub async fn try_add_jdks_from_dir(
+        &self,
+        path: &str,
+    ) -> Result<usize, AppError> {
+        let jdks = self
+            .scan_dir(PathBuf::from(path), CancellationToken::new())
+            .await?;
+        self.update_ui_state(|state| {
+            let roots = &mut state.settings.scan_roots;
+            if !roots.iter().any(|item| item == path) {
+                roots.push(path.to_string());
+                return AppSettings::update(&state.settings);
+            }
+            Ok(())
+        })?;
+        let ret = self.save_jdks(&jdks).await.map(|_| jdks.len());
         if ret.is_ok() {
             self.load_jdks();
         }
         ret
     }
 
-    pub fn add_jdk(&self, jdk: &Jdk) {
-        self.jdk_repo
-            .add(jdk)
+    /// Rescan all scan roots, new jdks are added and saved jdks which were
+    /// deleted from the roots are removed. Roots which cannot be reached,
+    /// e.g. unmounted drives, are skipped and their jdks are kept.
+    pub async fn rescan_scan_roots(&self) -> Result<RescanSummary, AppError> {
+        let roots = self.ui_state.lock().unwrap().settings.scan_roots.clone();
+        let saved = self.jdk_repo.get_all().unwrap_or_default();
+        let (found, skipped) = self.scan_roots(&roots).await?;
+        let removed: Vec<String> = saved
+            .iter()
+            .filter(|jdk| {
+                let path = Path::new(&jdk.path);
+                roots.iter().any(|root| {
+                    !skipped.contains(root) && path.starts_with(root)
+                }) && !path.exists()
+            })
+            .map(|jdk| jdk.path.clone())
+            .collect();
+        let new_count = found
+            .iter()
+            .filter(|item| !saved.iter().any(|jdk| jdk.path == item.path))
+            .count();
+        self.save_jdks(&found).await?;
+        self.jdk_repo.remove_all_by_paths(&removed)?;
+        self.load_jdks();
+        Ok(RescanSummary {
+            found: new_count,
+            removed: removed.len(),
+            skipped,
+        })
+    }
+
+    /// Scan the roots one by one, returns the jdks found and the roots
+    /// which were skipped.
+    async fn scan_roots(
+        &self,
+        roots: &[String],
+    ) -> Result<(Vec<Jdk>, Vec<String>), AppError> {
+        let mut found = vec![];
+        let mut skipped = vec![];
+        // Cancelling the scan of a root cancels the whole rescan
+        let token = CancellationToken::new();
+        for root in roots {
+            let root_path = PathBuf::from(root);
+            if !root_path.is_dir() {
+                eprintln!("Skipped unreachable scan root '{}'", root);
+                skipped.push(root.clone());
+                continue;
+            }
+            match self.scan_dir(root_path, token.clone()).await {
+                Ok(jdks) => found.extend(jdks),
+                Err(e) => {
+                    if token.is_cancelled() {
+                        return Err(e);
+                    }
+                    eprintln!("Cannot rescan '{}': {}", root, e);
+                    skipped.push(root.clone());
+                }
+            }
+        }
+        Ok((found, skipped))
+    }
+
+    /// Forget a scan root, saved jdks are kept.
+    pub fn remove_scan_root(&self, root: &str) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.scan_roots.retain(|item| item != root);
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Update how dirs are scanned, invalid exclude patterns are rejected.
+    pub fn update_scan_options(
+        &self,
+        max_depth: usize,
+        excludes: Vec<String>,
+        follow_links: bool,
+    ) -> Result<(), AppError> {
+        let excludes: Vec<String> = excludes
+            .into_iter()
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect();
+        for item in excludes.iter() {
+            Pattern::new(item).map_err(|e| {
+                AppError::new(format!(
+                    "Invalid exclude pattern '{}': {}",
+                    item, e
+                ))
+            })?;
+        }
+        self.update_ui_state(|state| {
+            state.settings.scan_max_depth = max_depth;
+            state.settings.scan_excludes = excludes;
+            state.settings.scan_follow_links = follow_links;
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    pub fn update_rescan_on_startup(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.rescan_on_startup = value;
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    async fn scan_dir(
+        &self,
+        path: PathBuf,
+        token: CancellationToken,
+    ) -> Result<Vec<Jdk>, AppError> {
+        if !path.exists() {
+            return Err(AppError::new("Dir does not exist."));
+        }
+        let settings = self.ui_state.lock().unwrap().settings.clone();
+        let excludes = settings
+            .scan_excludes
+            .iter()
+            .filter_map(|item| match Pattern::new(item) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    eprintln!("Invalid exclude pattern '{}': {}", item, e);
+                    None
+                }
+            })
+            .collect();
+        let options = WalkOptions {
+            max_depth: settings.scan_max_depth,
+            excludes,
+            follow_links: settings.scan_follow_links,
+        };
+
+        if let Some(prev) =
+            self.scan_token.lock().unwrap().replace(token.clone())
+        {
+            // Only one scan at a time, the roots of a rescan share a token
+            if !prev.is_same(&token) {
+                prev.cancel();
+            }
+        }
+        let progress_sender = self.scan_progress_sender.clone();
+        let scan = self.tokio_runtime.spawn(jdk_scanner::scan_jdks(
+            path,
+            options,
+            token,
+            move |progress| {
+                let _ = progress_sender.try_send(progress);
+            },
+        ));
+        let mut jdks = scan
+            .await
+            .map_err(|e| AppError::new(format!("Scan failed: {}", e)))??;
+
+        if settings.reject_jres {
+            jdks.retain(|item| !item.capabilities.is_jre());
+        }
+        Ok(jdks)
+    }
+
+    /// Find jdks installed by other tools which are not saved yet, they
+    /// are offered in the ui state until added or dismissed.
+    pub async fn discover_jdks(&self) -> Result<usize, AppError> {
+        let jdks = self
+            .tokio_runtime
+            .spawn_blocking(|| {
+                discovery::discover_jdks(&discovery::default_discoverers())
+            })
+            .await
+            .map_err(|e| AppError::new(format!("Discovery failed: {}", e)))?;
+        let saved = self.jdk_repo.get_all().unwrap_or_default();
+        let jdks: Vec<Jdk> = jdks
+            .into_iter()
+            .filter(|jdk| !saved.iter().any(|item| item.path == jdk.path))
+            .collect();
+        let count = jdks.len();
+        self.update_ui_state(|state| {
+            state.discovered_jdks = jdks;
+        });
+        Ok(count)
+    }
+
+    /// Add the discovered jdks of the paths, the rest are dismissed.
+    pub async fn add_discovered_jdks(
+        &self,
+        paths: &[String],
+    ) -> Result<usize, AppError> {
+        let jdks: Vec<Jdk> = self
+            .ui_state
+            .lock()
+            .unwrap()
+            .discovered_jdks
+            .iter()
+            .filter(|item| paths.contains(&item.path))
+            .cloned()
+            .collect();
+        self.save_jdks(&jdks).await?;
+        self.dismiss_discovered_jdks()?;
+        self.load_jdks();
+        Ok(jdks.len())
+    }
+
+    pub fn dismiss_discovered_jdks(&self) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.discovered_jdks.clear();
+            state.settings.discovery_offered = true;
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Import jdks defined in the config files of an IDE, config files on
+    /// this machine are used if `path` is not set.
+    pub async fn import_ide_jdks(
+        &self,
+        kind: IdeKind,
+        path: Option<String>,
+    ) -> Result<ImportReport, AppError> {
+        let files = match path {
+            Some(path) => vec![PathBuf::from(path)],
+            None => ide_config::default_config_files(kind),
+        };
+        if files.is_empty() {
+            return Err(AppError::new("No IDE config file found."));
+        }
+        let mut entries = vec![];
+        for file in files {
+            entries.extend(ide_config::read_entries(kind, &file)?);
+        }
+        let saved = self.jdk_repo.get_all().unwrap_or_default();
+        let (jdks, report) = self
+            .tokio_runtime
+            .spawn_blocking(move || {
+                ide_config::import_entries(&entries, &saved)
+            })
+            .await
+            .map_err(|e| AppError::new(format!("Import failed: {}", e)))?;
+        self.save_jdks(&jdks).await?;
+        self.load_jdks();
+        Ok(report)
+    }
+
+    /// Write the jdks to `~/.m2/toolchains.xml`, returns the file path.
+    pub fn export_maven_toolchains(&self) -> Result<String, AppError> {
+        let Some(path) = maven::toolchains_xml_path() else {
+            return Err(AppError::new("Cannot find the user home dir."));
+        };
+        let jdks = AppViewModel::exportable_jdks(&self.ui_state_jdks());
+        maven::sync_toolchains(&path, &jdks)?;
+        Ok(path.display().to_string())
+    }
+
+    /// Write the jdks to the installation paths of Gradle, returns the file
+    /// path.
+    pub fn export_gradle_paths(&self) -> Result<String, AppError> {
+        let Some(path) = gradle::gradle_properties_path() else {
+            return Err(AppError::new("Cannot find the Gradle user home."));
+        };
+        let jdks = AppViewModel::exportable_jdks(&self.ui_state_jdks());
+        gradle::sync_installation_paths(&path, &jdks)?;
+        Ok(path.display().to_string())
+    }
+
+    /// Managed entries are removed from the file when the sync is disabled.
+    pub fn update_sync_maven_toolchains(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.sync_maven_toolchains = value;
+            AppSettings::update(&state.settings)
+        })?;
+        if value {
+            self.export_maven_toolchains()?;
+        } else if let Some(path) = maven::toolchains_xml_path() {
+            if path.exists() {
+                maven::sync_toolchains(&path, &[])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Managed paths are removed from the file when the sync is disabled.
+    pub fn update_sync_gradle_paths(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.sync_gradle_paths = value;
+            AppSettings::update(&state.settings)
+        })?;
+        if value {
+            self.export_gradle_paths()?;
+        } else if let Some(path) = gradle::gradle_properties_path() {
+            if path.exists() {
+                gradle::sync_installation_paths(&path, &[])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Preview the changes of exporting jdks to the config files of an IDE.
+    pub fn preview_ide_export(
+        &self,
+        kind: IdeKind,
+    ) -> Result<Vec<ExportPreview>, AppError> {
+        let jdks = AppViewModel::exportable_jdks(&self.ui_state_jdks());
+        ide_config::preview_export(kind, &jdks)
+    }
+
+    /// Export jdks to the config files of an IDE, existing entries are kept.
+    pub fn export_jdks_to_ide(
+        &self,
+        kind: IdeKind,
+    ) -> Result<Vec<ExportPreview>, AppError> {
+        let previews = self.preview_ide_export(kind)?;
+        ide_config::write_previews(&previews)?;
+        Ok(previews)
+    }
+
+    fn ui_state_jdks(&self) -> Vec<Jdk> {
+        self.ui_state.lock().unwrap().jdks.clone()
+    }
+
+    /// Build tools need valid jdks with a compiler and a known version.
+    fn exportable_jdks(jdks: &[Jdk]) -> Vec<Jdk> {
+        jdks.iter()
+            .filter(|item| {
+                item.is_valid
+                    && !item.capabilities.is_jre()
+                    && !item.version.is_unknown()
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn sync_build_tools(&self, jdks: &[Jdk]) {
+        let settings = self.settings();
+        let jdks = AppViewModel::exportable_jdks(jdks);
+        if settings.sync_maven_toolchains {
+            if let Some(path) = maven::toolchains_xml_path() {
+                if let Err(e) = maven::sync_toolchains(&path, &jdks) {
+                    eprintln!("Failed to sync maven toolchains: {}", e);
+                }
+            }
+        }
+        if settings.sync_gradle_paths {
+            if let Some(path) = gradle::gradle_properties_path() {
+                if let Err(e) = gradle::sync_installation_paths(&path, &jdks) {
+                    eprintln!("Failed to sync gradle paths: {}", e);
+                }
+            }
+        }
+    }
+
+    pub async fn catalog_distributions(
+        &self,
+    ) -> Result<Vec<CatalogDistribution>, AppError> {
+        let mut distributions = self
+            .query_catalogs(|catalog| catalog.distributions())
+            .await?;
+        let mut seen = std::collections::HashSet::new();
+        distributions.retain(|item| seen.insert(item.api_parameter.clone()));
+        Ok(distributions)
+    }
+
+    pub async fn catalog_major_versions(
+        &self,
+    ) -> Result<Vec<CatalogMajorVersion>, AppError> {
+        let mut versions = self
+            .query_catalogs(|catalog| catalog.major_versions())
+            .await?;
+        // Keep the first one, indexes do not know the term of support
+        let mut seen = std::collections::HashSet::new();
+        versions.retain(|item| seen.insert(item.major_version));
+        versions.sort_by_key(|item| std::cmp::Reverse(item.major_version));
+        Ok(versions)
+    }
+
+    pub async fn catalog_packages(
+        &self,
+        query: PackageQuery,
+    ) -> Result<Vec<CatalogPackage>, AppError> {
+        self.query_catalogs(move |catalog| catalog.packages(&query))
+            .await
+    }
+
+    /// Query every enabled catalog in the background, see
+    /// [installer::query_catalogs].
+    async fn query_catalogs<T, F>(&self, query: F) -> Result<Vec<T>, AppError>
+    where
+        T: Send + 'static,
+        F: Fn(&dyn JdkCatalog) -> Result<Vec<T>, AppError> + Send + 'static,
+    {
+        let settings = self.settings();
+        self.tokio_runtime
+            .spawn_blocking(move || installer::query_catalogs(&settings, query))
+            .await
+            .map_err(|e| AppError::new(format!("Request failed: {}", e)))?
+    }
+
+    pub fn update_disco_catalog_enabled(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.disco_catalog_enabled = value;
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Add a catalog index, it is loaded first to check the source.
+    pub async fn add_catalog_source(
+        &self,
+        source: &str,
+    ) -> Result<(), AppError> {
+        let source = source.trim().to_string();
+        if self.settings().catalog_sources.contains(&source) {
+            return Ok(());
+        }
+        let loaded = source.clone();
+        self.tokio_runtime
+            .spawn_blocking(move || IndexCatalog::load(&loaded, &http_agent()))
+            .await
+            .map_err(|e| AppError::new(format!("Request failed: {}", e)))??;
+        self.update_ui_state(|state| {
+            state.settings.catalog_sources.push(source);
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    pub fn remove_catalog_source(&self, source: &str) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.catalog_sources.retain(|item| item != source);
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Download and install a package from the catalog into the managed
+    /// jdks dir, the progress is reported to the install progress stream.
+    pub async fn install_jdk(
+        &self,
+        package: CatalogPackage,
+    ) -> Result<InstallResult, AppError> {
+        let token = CancellationToken::new();
+        if let Some(prev) =
+            self.install_token.lock().unwrap().replace(token.clone())
+        {
+            // Only one install at a time
+            prev.cancel();
+        }
+        let progress_sender = self.install_progress_sender.clone();
+        let settings = self.settings();
+        let installed = self
+            .tokio_runtime
+            .spawn_blocking(move || {
+                // Packages are installed from the catalog they are found in
+                let catalog = installer::enabled_catalogs(&settings)
+                    .into_iter()
+                    .filter_map(|catalog| catalog.ok())
+                    .find(|catalog| catalog.id() == package.catalog)
+                    .ok_or_else(|| {
+                        AppError::new(format!(
+                            "Catalog '{}' is not enabled",
+                            package.catalog
+                        ))
+                    })?;
+                installer::install_package(
+                    catalog.as_ref(),
+                    &package,
+                    &paths::managed_jdks_dir(),
+                    &paths::downloads_dir(),
+                    &AppViewModel::signature_policy(&settings),
+                    &token,
+                    |progress| {
+                        let _ = progress_sender.try_send(progress);
+                    },
+                )
+            })
+            .await
+            .map_err(|e| AppError::new(format!("Install failed: {}", e)))??;
+        self.register_installed_package(installed).await
+    }
+
+    /// Extract a jdk archive into the managed jdks dir and add the jdks
+    /// found in it.
+    pub async fn import_jdk_archive(
+        &self,
+        archive: PathBuf,
+    ) -> Result<InstallResult, AppError> {
+        let settings = self.settings();
+        let installed = self
+            .tokio_runtime
+            .spawn_blocking(move || {
+                installer::import_archive(
+                    &archive,
+                    &paths::managed_jdks_dir(),
+                    &AppViewModel::signature_policy(&settings),
+                )
+            })
+            .await
+            .map_err(|e| AppError::new(format!("Import failed: {}", e)))??;
+        self.register_installed_package(installed).await
+    }
+
+    fn signature_policy(settings: &SettingsValues) -> SignaturePolicy {
+        SignaturePolicy {
+            minisign_keys: settings.trusted_minisign_keys.clone(),
+            gpg_key_files: settings
+                .trusted_gpg_keys
+                .iter()
+                .map(PathBuf::from)
+                .collect(),
+            strict: settings.strict_signatures,
+        }
+    }
+
+    /// Save the jdks of an installed package, the package is removed if
+    /// there is none.
+    async fn register_installed_package(
+        &self,
+        installed: InstalledPackage,
+    ) -> Result<InstallResult, AppError> {
+        let jdks = find_jdks_from_dir(&installed.home).unwrap_or_default();
+        if jdks.is_empty() {
+            let _ = std::fs::remove_dir_all(&installed.home);
+            return Err(AppError::new(format!(
+                "No jdk found in '{}'",
+                installed.home.display()
+            )));
+        }
+        let jdks: Vec<Jdk> = jdks
+            .into_iter()
+            .map(|jdk| Jdk {
+                source: JdkSource::Installed,
+                verified_digest: installed.verified_digest.clone(),
+                ..jdk
+            })
+            .collect();
+        self.save_jdks(&jdks).await?;
+        self.load_jdks();
+        Ok(InstallResult {
+            jdks,
+            signature: installed.signature,
+        })
+    }
+
+    pub fn update_strict_signatures(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.strict_signatures = value;
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    pub fn update_trusted_keys(
+        &self,
+        minisign_keys: Vec<String>,
+        gpg_keys: Vec<String>,
+    ) -> Result<(), AppError> {
+        let trimmed = |keys: Vec<String>| -> Vec<String> {
+            keys.into_iter()
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .collect()
+        };
+        self.update_ui_state(|state| {
+            state.settings.trusted_minisign_keys = trimmed(minisign_keys);
+            state.settings.trusted_gpg_keys = trimmed(gpg_keys);
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Find the newest patch release of a saved jdk in the catalogs, i.e.
+    /// the same distribution and feature version.
+    pub async fn find_jdk_upgrade(
+        &self,
+        path: &str,
+    ) -> Result<Option<CatalogPackage>, AppError> {
+        let jdk = self.saved_jdk(path)?;
+        let query_jdk = jdk.clone();
+        let packages = self
+            .query_catalogs(move |catalog| {
+                upgrade::query_upgrades(
+                    catalog,
+                    std::slice::from_ref(&query_jdk),
+                )
+            })
+            .await?;
+        Ok(upgrade::newest_patch(&jdk, &packages).cloned())
+    }
+
+    /// Look for newer patch releases of the saved jdks in the catalogs, the
+    /// newest one is recorded to each jdk.
+    pub async fn check_jdk_updates(&self) -> Result<UpdateReport, AppError> {
+        let query_jdks = self.jdk_repo.get_all().unwrap_or_default();
+        let checked: Vec<String> =
+            query_jdks.iter().map(|item| item.path.clone()).collect();
+        let packages = self
+            .query_catalogs(move |catalog| {
+                upgrade::query_upgrades(catalog, &query_jdks)
+            })
+            .await?;
+        // The jdks may have changed during the query
+        let mut jdks = vec![];
+        self.jdk_repo.update_all(|all| {
+            let checked_jdks =
+                all.iter_mut().filter(|item| checked.contains(&item.path));
+            upgrade::record_updates(checked_jdks, &packages);
+            jdks = all
+                .iter()
+                .filter(|item| checked.contains(&item.path))
+                .cloned()
+                .collect();
+            Ok(())
+        })?;
+        self.load_jdks();
+        Ok(UpdateReport {
+            checked: jdks.len(),
+            outdated: jdks
+                .iter()
+                .filter_map(|jdk| {
+                    let latest = jdk.latest_available.as_ref()?;
+                    Some(format!("{} {} -> {}", jdk.name, jdk.version, latest))
+                })
+                .collect(),
+        })
+    }
+
+    /// Download the jdk lifecycle table from a url or path, the source is
+    /// saved to update it again later. Returns the date of the table.
+    pub async fn update_lifecycle_table(
+        &self,
+        source: String,
+    ) -> Result<String, AppError> {
+        let source = source.trim().to_string();
+        let loaded = source.clone();
+        let table = self
+            .tokio_runtime
+            .spawn_blocking(move || jdk_lifecycle::update_table(&loaded))
+            .await
+            .map_err(|e| AppError::new(format!("Update failed: {}", e)))??;
+        self.update_ui_state(|state| {
+            state.settings.lifecycle_source = source;
+            AppSettings::update(&state.settings)
+        })?;
+        self.load_jdks();
+        Ok(table.updated)
+    }
+
+    pub fn update_check_updates_on_startup(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.check_updates_on_startup = value;
+            AppSettings::update(&state.settings)
+        })
+    }
+
+    /// Install the newest patch release of a jdk next to it. Pins of known
+    /// projects are moved to the new jdk, and it is switched to if the old
+    /// one is current. The old jdk is kept.
+    pub async fn upgrade_jdk(
+        &self,
+        path: &str,
+    ) -> Result<UpgradeResult, AppError> {
+        let old = self.saved_jdk(path)?;
+        let Some(package) = self.find_jdk_upgrade(path).await? else {
+            return Err(AppError::new(format!(
+                "Jdk '{} {}' is up to date",
+                old.name, old.version
+            )));
+        };
+        let installed = self.install_jdk(package).await?;
+        let Some(new) = installed
+            .jdks
+            .into_iter()
+            .find(|item| item.version.feature == old.version.feature)
+        else {
+            return Err(AppError::new("No jdk found in the installed package"));
+        };
+
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        let mut migrated_pins: Vec<String> = vec![];
+        let pin_files = self
+            .settings()
+            .known_projects
+            .iter()
+            .filter_map(|project| {
+                jdk_selector::find_pin_file(Path::new(project))
+            })
+            .collect::<Vec<PathBuf>>();
+        for pin_file in pin_files {
+            let name = pin_file.to_string_lossy().to_string();
+            // Pins of other jdks are left alone
+            let is_pinned = jdk_selector::read_pin_file(&pin_file)
+                .ok()
+                .and_then(|requirement| {
+                    jdk_selector::select_best(&jdks, &requirement).cloned()
+                })
+                .is_some_and(|item| item.path == old.path);
+            if !is_pinned || migrated_pins.contains(&name) {
+                continue;
+            }
+            if jdk_selector::migrate_pin(&pin_file, &old, &new)? {
+                migrated_pins.push(name);
+            }
+        }
+
+        let switched = old.is_current;
+        if switched {
+            self.switch_to_jdk(&new)?;
+        }
+        Ok(UpgradeResult {
+            old,
+            new,
+            signature: installed.signature,
+            migrated_pins,
+            switched,
+        })
+    }
+
+    fn saved_jdk(&self, path: &str) -> Result<Jdk, AppError> {
+        self.ui_state
+            .lock()
+            .unwrap()
+            .jdks
+            .iter()
+            .find(|item| item.path == path)
+            .cloned()
+            .ok_or_else(|| {
+                AppError::new(format!("Jdk '{}' is not saved", path))
+            })
+    }
+
+    /// Delete a jdk installed by the app from disk, the current jdk and jdks
+    /// pinned by known projects are refused. The install dir is moved to
+    /// the trash first, it is moved back if the jdk cannot be removed from
+    /// the list, and it is deleted from the trash then.
+    pub async fn uninstall_jdk(
+        &self,
+        path: &str,
+    ) -> Result<UninstallReport, AppError> {
+        let jdk = self.saved_jdk(path)?;
+        let jdks = self.ui_state.lock().unwrap().jdks.clone();
+        let install_dir = match jdk.source {
+            JdkSource::Installed => uninstall::install_dir_of(
+                &jdk.home_dir(),
+                &paths::managed_jdks_dir(),
+            ),
+            _ => None,
+        };
+        let Some(install_dir) = install_dir else {
+            return Err(AppError::new(format!(
+                "Jdk '{}' is not installed by the app, it can only be \
+                removed from the list",
+                jdk.name
+            )));
+        };
+        // An install dir may contain more than one jdk
+        let affected: Vec<&Jdk> = jdks
+            .iter()
+            .filter(|item| item.home_dir().starts_with(&install_dir))
+            .collect();
+        if let Some(current) = affected.iter().find(|item| item.is_current) {
+            return Err(AppError::new(format!(
+                "Jdk '{}' is the current jdk, switch to another one first",
+                current.name
+            )));
+        }
+        for project in self.settings().known_projects {
+            let pinned = jdk_selector::resolve_pin(Path::new(&project), &jdks)
+                .ok()
+                .flatten()
+                .filter(|pinned| {
+                    affected.iter().any(|item| item.path == pinned.path)
+                });
+            if let Some(pinned) = pinned {
+                return Err(AppError::new(format!(
+                    "Jdk '{}' is pinned by project '{}'",
+                    pinned.name, project
+                )));
+            }
+        }
+
+        let removed: Vec<String> =
+            affected.iter().map(|item| item.path.clone()).collect();
+        let trash_dir = paths::trash_dir();
+        let trashed = uninstall::move_to_trash(&install_dir, &trash_dir)?;
+        if let Err(e) = self.jdk_repo.remove_all_by_paths(&removed) {
+            let _ = std::fs::rename(&trashed, &install_dir);
+            return Err(e);
+        }
+        self.load_jdks();
+        let freed_bytes = self
+            .tokio_runtime
+            .spawn_blocking(move || uninstall::delete_trashed(&trashed))
+            .await
+            .map_err(|e| AppError::new(format!("Uninstall failed: {}", e)))??;
+        Ok(UninstallReport {
+            removed: affected.iter().map(|item| item.name.clone()).collect(),
+            freed_bytes,
+        })
+    }
+
+    pub fn cancel_install(&self) {
+        if let Some(token) = self.install_token.lock().unwrap().take() {
+            token.cancel();
+        }
+    }
+
+    /// Cancel the running scan.
+    pub fn cancel_scan(&self) {
+        if let Some(token) = self.scan_token.lock().unwrap().take() {
+            token.cancel();
+        }
+    }
+
+    pub async fn add_jdk(&self, jdk: &Jdk) {
+        self.save_jdks(std::slice::from_ref(jdk))
+            .await
             .expect(format!("Cannot add jdk '{}'", jdk.name).as_str());
         self.load_jdks();
     }
 
+    /// Save jdks, new jdks are fingerprinted to detect changes of their
+    /// files later. Saved jdks keep their fingerprint, a rescan must not
+    /// accept changed files.
+    async fn save_jdks(&self, jdks: &[Jdk]) -> Result<(), AppError> {
+        let saved = self.jdk_repo.get_all().unwrap_or_default();
+        // A jdk updated in place is fingerprinted again
+        let is_fingerprinted = |prev: &Jdk, jdk: &Jdk| {
+            prev.fingerprint.is_some() && prev.version == jdk.version
+        };
+        let homes: Vec<PathBuf> = jdks
+            .iter()
+            .filter(|jdk| {
+                !saved.iter().any(|item| {
+                    item.path == jdk.path && is_fingerprinted(item, jdk)
+                })
+            })
+            .map(|jdk| jdk.home_dir())
+            .collect();
+        // Hashing the files of the jdks takes a while
+        let mut fingerprints: HashMap<PathBuf, JdkFingerprint> = self
+            .tokio_runtime
+            .spawn_blocking(move || {
+                homes
+                    .into_iter()
+                    .filter_map(|home| {
+                        let fingerprint =
+                            jdk_integrity::fingerprint(&home).ok()?;
+                        Some((home, fingerprint))
+                    })
+                    .collect()
+            })
+            .await
+            .map_err(|e| AppError::new(format!("Save failed: {}", e)))?;
+        self.jdk_repo.update_all(|all| {
+            for jdk in jdks {
+                let mut jdk = jdk.clone();
+                let index = all.iter().position(|item| item.path == jdk.path);
+                let prev = index.map(|index| &all[index]);
+                if let Some(prev) = prev {
+                    jdk.latest_available.clone_from(&prev.latest_available);
+                    // Probed jdks are always manual, the source comes from
+                    // how they were first found
+                    if jdk.source == JdkSource::Manual {
+                        jdk.source = prev.source;
+                    }
+                }
+                match prev {
+                    Some(prev) if is_fingerprinted(prev, &jdk) => {
+                        jdk.fingerprint = prev.fingerprint.clone();
+                        jdk.integrity = prev.integrity;
+                        if jdk.verified_digest.is_none() {
+                            jdk.verified_digest = prev.verified_digest.clone();
+                        }
+                    }
+                    _ => {
+                        let fingerprint = fingerprints.remove(&jdk.home_dir());
+                        jdk.integrity = match fingerprint {
+                            Some(_) => IntegrityStatus::Intact,
+                            None => IntegrityStatus::Unknown,
+                        };
+                        jdk.fingerprint = fingerprint;
+                    }
+                }
+                match index {
+                    Some(index) => all[index] = jdk,
+                    None => all.push(jdk),
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Check the files of the saved jdks against their fingerprints, jdks
+    /// saved by an older version are fingerprinted now.
+    pub async fn verify_jdks(&self) -> Result<IntegrityReport, AppError> {
+        let saved = self.jdk_repo.get_all().unwrap_or_default();
+        let checked = self
+            .tokio_runtime
+            .spawn_blocking(move || {
+                saved
+                    .into_iter()
+                    .map(|mut jdk| {
+                        let home = jdk.home_dir();
+                        match &jdk.fingerprint {
+                            Some(fingerprint) => {
+                                jdk.integrity = jdk_integrity::check_integrity(
+                                    &home,
+                                    fingerprint,
+                                );
+                            }
+                            None => {
+                                let fingerprint =
+                                    jdk_integrity::fingerprint(&home).ok();
+                                if fingerprint.is_some() {
+                                    jdk.integrity = IntegrityStatus::Intact;
+                                }
+                                jdk.fingerprint = fingerprint;
+                            }
+                        }
+                        jdk
+                    })
+                    .collect::<Vec<Jdk>>()
+            })
+            .await
+            .map_err(|e| AppError::new(format!("Verify failed: {}", e)))?;
+        // Only the results are saved, the jdks may have changed meanwhile
+        self.jdk_repo.update_all(|all| {
+            for jdk in all.iter_mut() {
+                let Some(result) =
+                    checked.iter().find(|item| item.path == jdk.path)
+                else {
+                    continue;
+                };
+                jdk.fingerprint.clone_from(&result.fingerprint);
+                jdk.integrity = result.integrity;
+            }
+            Ok(())
+        })?;
+        self.load_jdks();
+
+        let names = |status: IntegrityStatus| -> Vec<String> {
+            checked
+                .iter()
+                .filter(|jdk| jdk.integrity == status)
+                .map(|jdk| format!("{} {}", jdk.name, jdk.version))
+                .collect()
+        };
+        Ok(IntegrityReport {
+            checked: checked.len(),
+            modified: names(IntegrityStatus::Modified),
+            corrupted: names(IntegrityStatus::Corrupted),
+        })
+    }
+
+    pub fn update_verify_on_startup(
+        &self,
+        value: bool,
+    ) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.verify_on_startup = value;
+            AppSettings::update(&state.settings)
+        })
+    }
+
     pub fn switch_to_jdk(&self, jdk: &Jdk) -> Result<(), AppError> {
         let ret = jdk_switcher::switch_to_jdk(jdk);
         if ret.is_ok() {
@@ -110,12 +1345,43 @@ impl AppViewModel {
         Ok(())
     }
 
+    pub fn update_hide_jres(&self, value: bool) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.hide_jres = value;
+            AppSettings::update(&state.settings)
+        })?;
+        Ok(())
+    }
+
+    pub fn update_reject_jres(&self, value: bool) -> Result<(), AppError> {
+        self.update_ui_state(|state| {
+            state.settings.reject_jres = value;
+            AppSettings::update(&state.settings)
+        })?;
+        Ok(())
+    }
+
+    /// Get the current settings.
+    pub fn settings(&self) -> SettingsValues {
+        self.ui_state.lock().unwrap().settings.clone()
+    }
+
     // Get the ui state stream to receive incoming updates.
     pub fn ui_state_stream(&self) -> &Receiver<AppUiState> {
         self.notify_ui_state();
         &self.state_receiver
     }
 
+    // Get the install progress stream to receive progress of installs.
+    pub fn install_progress_stream(&self) -> &Receiver<InstallProgress> {
+        &self.install_progress_receiver
+    }
+
+    // Get the scan progress stream to receive progress of running scans.
+    pub fn scan_progress_stream(&self) -> &Receiver<ScanProgress> {
+        &self.scan_progress_receiver
+    }
+
     // Update the current ui state.
     fn update_ui_state<F, R>(&self, closure: F) -> R
     where
@@ -141,16 +1407,21 @@ impl AppViewModel {
     /// Function to validate jdk path, check for the current jdk, etc.
     ///
     /// This function will always add/update the current jdk to the list if a jdk has added
-    /// to the PATH.
+    /// to the PATH, or selected by the alternatives system on Linux.
     fn process_saved_jdks(&self, jdks: Vec<Jdk>) -> Vec<Jdk> {
-        if let Err(e) = util::env::use_sys_env_path_var() {
+        #[cfg(windows)]
+        if let Err(e) = crate::util::env::use_sys_env_path_var() {
             eprintln!(
                 "Failed to refresh env before getting the java exe path: {}",
                 e
             );
         }
 
-        let Some(java_path) = find_command_exe_path("java") else {
+        let java_path = find_command_exe_path("java")
+            .map(|path| resolve_java_links(&path))
+            // Linux distros select the jdk through alternatives
+            .or_else(find_system_java);
+        let Some(java_path) = java_path else {
             // Jdk not added to PATH
             return AppViewModel::validate_jdks(jdks);
         };
@@ -184,7 +1455,75 @@ impl AppViewModel {
         let mut list = list;
         for item in list.iter_mut() {
             item.is_valid = Path::new(&item.path).exists();
+            if item.is_valid && item.arch == Arch::Unknown {
+                // Older versions saved the bitness instead of the arch
+                let java =
+                    Path::new(&item.path).join(java_executable_filename());
+                item.arch = read_exe_arch(&java).unwrap_or(Arch::Unknown);
+            }
+            item.is_foreign_arch = item.arch.is_foreign();
+            if !item.is_valid {
+                continue;
+            }
+            let home = item.home_dir();
+            let release = read_release_file(&home).unwrap_or_default();
+            if item.distribution == Distribution::Unknown {
+                // Saved by an older version
+                item.distribution =
+                    classify_distribution(&home, &release, &HashMap::new());
+            }
+            // Tools may be added or removed after the jdk is saved
+            let jvm = item.capabilities.jvm;
+            item.capabilities =
+                detect_capabilities(&home, &release, &HashMap::new());
+            if item.capabilities.jvm == JvmImpl::Unknown {
+                item.capabilities.jvm = jvm;
+            }
         }
         list
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::AppViewModel;
+    use crate::{jdk_finder::java_executable_filename, test_util::temp_dir};
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_roots_with_one_token() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("scan-roots");
+        let mut roots = vec![];
+        for (root, version) in [("a", "17.0.9"), ("b", "21.0.1")] {
+            let bin = dir.join(root).join("jdk").join("bin");
+            std::fs::create_dir_all(&bin).unwrap();
+            let java = bin.join(java_executable_filename());
+            let script = format!(
+                "#!/bin/sh\necho 'openjdk version \"{}\"' >&2\n\
+                echo 'OpenJDK Runtime Environment' >&2\n\
+                echo 'OpenJDK 64-Bit Server VM' >&2\n",
+                version
+            );
+            std::fs::write(&java, script).unwrap();
+            std::fs::set_permissions(&java, PermissionsExt::from_mode(0o755))
+                .unwrap();
+            roots.push(dir.join(root).to_string_lossy().to_string());
+        }
+        roots.push(dir.join("missing").to_string_lossy().to_string());
+
+        let view_model = AppViewModel::new();
+        let ret = view_model
+            .tokio_runtime
+            .block_on(view_model.scan_roots(&roots));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let (jdks, skipped) = ret.unwrap();
+        let mut versions: Vec<String> =
+            jdks.iter().map(|item| item.version.to_string()).collect();
+        versions.sort();
+        assert_eq!(vec!["17.0.9", "21.0.1"], versions);
+        assert_eq!(roots[2..], skipped[..]);
+    }
+}
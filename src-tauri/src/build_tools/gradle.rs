@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use crate::{errors::AppError, model::jdk::Jdk, util::paths::gradle_user_home};
+
+use super::write_if_changed;
+
+const PATHS_KEY: &str = "org.gradle.java.installations.paths";
+
+/// The comment before the property, lists the paths added by this app so
+/// paths added by the user are kept when syncing.
+const MANAGED_COMMENT: &str = "# Managed by JDK Switcher:";
+
+/// `~/.gradle/gradle.properties`
+pub fn gradle_properties_path() -> Option<PathBuf> {
+    gradle_user_home().map(|home| home.join("gradle.properties"))
+}
+
+/// Write the jdk homes to the installation paths of Gradle, returns `true`
+/// if the file is changed.
+pub fn sync_installation_paths(
+    path: &PathBuf,
+    jdks: &[Jdk],
+) -> Result<bool, AppError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let merged = merge_installation_paths(&content, jdks);
+    write_if_changed(path, &merged)
+}
+
+/// Merge the jdk homes into `org.gradle.java.installations.paths`, other
+/// properties and paths added by the user are kept.
+pub fn merge_installation_paths(content: &str, jdks: &[Jdk]) -> String {
+    let mut lines: Vec<String> =
+        content.lines().map(|line| line.to_string()).collect();
+
+    let property_index = lines.iter().position(|line| {
+        line.split_once('=')
+            .is_some_and(|(key, _)| key.trim() == PATHS_KEY)
+    });
+    let current: Vec<String> = property_index
+        .map(|index| split_paths(lines[index].split_once('=').unwrap().1))
+        .unwrap_or_default();
+
+    let comment_index = property_index
+        .and_then(|index| index.checked_sub(1))
+        .filter(|index| lines[*index].starts_with(MANAGED_COMMENT));
+    let managed: Vec<String> = comment_index
+        .map(|index| split_paths(&lines[index][MANAGED_COMMENT.len()..]))
+        .unwrap_or_default();
+
+    let ours: Vec<String> = jdks
+        .iter()
+        .map(|jdk| to_property_path(&jdk.home_dir().display().to_string()))
+        .collect();
+    let mut paths: Vec<String> = current
+        .into_iter()
+        .filter(|path| !managed.contains(path))
+        .collect();
+    let user_paths = paths.clone();
+    for path in &ours {
+        if !paths.contains(path) {
+            paths.push(path.clone());
+        }
+    }
+    let added: Vec<String> = ours
+        .into_iter()
+        .filter(|path| !user_paths.contains(path))
+        .collect();
+
+    let mut new_lines = vec![];
+    if !added.is_empty() {
+        new_lines.push(format!("{} {}", MANAGED_COMMENT, added.join(",")));
+    }
+    if !paths.is_empty() {
+        new_lines.push(format!("{}={}", PATHS_KEY, paths.join(",")));
+    }
+
+    match (property_index, comment_index) {
+        (Some(property_index), comment_index) => {
+            let start = comment_index.unwrap_or(property_index);
+            lines.splice(start..=property_index, new_lines);
+        }
+        (None, _) => lines.extend(new_lines),
+    }
+    let mut merged = lines.join("\n");
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged
+}
+
+fn split_paths(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
+}
+
+/// Backslashes are escape characters in properties files, Gradle accepts
+/// forward slashes on Windows as well.
+fn to_property_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+#[cfg(test)]
+mod test {
+    use super::merge_installation_paths;
+    use crate::test_util::jdk;
+
+    #[test]
+    fn test_merge_installation_paths() {
+        let content = "org.gradle.daemon=true\n\
+            org.gradle.java.installations.paths=/opt/custom-jdk\n";
+        let jdks = [
+            jdk("/opt/jdk-17/bin", "17"),
+            jdk("/opt/custom-jdk/bin", "17"),
+        ];
+        let merged = merge_installation_paths(content, &jdks);
+        assert_eq!(
+            "org.gradle.daemon=true\n\
+             # Managed by JDK Switcher: /opt/jdk-17\n\
+             org.gradle.java.installations.paths=/opt/custom-jdk,/opt/jdk-17\n",
+            merged
+        );
+
+        // Removed jdks are removed, paths of the user are kept
+        let merged = merge_installation_paths(&merged, &jdks[1..]);
+        assert_eq!(
+            "org.gradle.daemon=true\n\
+             org.gradle.java.installations.paths=/opt/custom-jdk\n",
+            merged
+        );
+
+        assert_eq!(
+            "# Managed by JDK Switcher: /opt/jdk-17\n\
+             org.gradle.java.installations.paths=/opt/jdk-17\n",
+            merge_installation_paths("", &jdks[..1])
+        );
+        assert_eq!("", merge_installation_paths("", &[]));
+    }
+}
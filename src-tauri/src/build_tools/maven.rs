@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use crate::{
+    errors::AppError,
+    model::{distribution::Distribution, jdk::Jdk},
+    util::paths::user_home_dir,
+};
+
+use super::write_if_changed;
+
+const BEGIN_MARKER: &str = "<!-- BEGIN JDK Switcher managed toolchains -->";
+
+const END_MARKER: &str = "<!-- END JDK Switcher managed toolchains -->";
+
+const CLOSING_TAG: &str = "</toolchains>";
+
+/// `~/.m2/toolchains.xml`
+pub fn toolchains_xml_path() -> Option<PathBuf> {
+    user_home_dir().map(|home| home.join(".m2").join("toolchains.xml"))
+}
+
+/// Write the jdks to the toolchains file, returns `true` if the file is
+/// changed.
+pub fn sync_toolchains(path: &PathBuf, jdks: &[Jdk]) -> Result<bool, AppError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let merged = merge_toolchains(&content, jdks)?;
+    write_if_changed(path, &merged)
+}
+
+/// Replace the managed block of the toolchains file with the jdks, content
+/// outside the block is kept as is. Jdks already declared outside the block
+/// are skipped. Without jdks the block is removed, a file without a block
+/// is not changed.
+pub fn merge_toolchains(
+    content: &str,
+    jdks: &[Jdk],
+) -> Result<String, AppError> {
+    // Remove the previous block, including its indent and line break
+    let (before, after) =
+        match (content.find(BEGIN_MARKER), content.find(END_MARKER)) {
+            (Some(begin), Some(end)) if begin < end => {
+                let line_start =
+                    content[..begin].rfind('\n').map_or(0, |i| i + 1);
+                let block_end = end + END_MARKER.len();
+                let line_end = content[block_end..]
+                    .find('\n')
+                    .map_or(content.len(), |i| block_end + i + 1);
+                (&content[..line_start], &content[line_end..])
+            }
+            _ => (content, ""),
+        };
+    let unmanaged = format!("{}{}", before, after);
+    if jdks.is_empty() {
+        return Ok(unmanaged);
+    }
+    if unmanaged.trim().is_empty() {
+        let block = managed_block(jdks, &[], "  ");
+        return Ok(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <toolchains>\n{}\n{}\n",
+            block, CLOSING_TAG
+        ));
+    }
+
+    let doc = roxmltree::Document::parse(&unmanaged)
+        .map_err(|e| AppError::new(format!("Invalid toolchains.xml: {}", e)))?;
+    let declared: Vec<&str> = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("jdkHome"))
+        .filter_map(|node| node.text())
+        .map(|text| text.trim())
+        .collect();
+
+    let Some(close_index) = unmanaged.rfind(CLOSING_TAG) else {
+        return Err(AppError::new("Invalid toolchains.xml: no </toolchains>"));
+    };
+    let line_start = unmanaged[..close_index].rfind('\n').map_or(0, |i| i + 1);
+    let closing_indent = &unmanaged[line_start..close_index];
+    let indent = if closing_indent.trim().is_empty() {
+        format!("{}  ", closing_indent)
+    } else {
+        "  ".to_string()
+    };
+    let block = managed_block(jdks, &declared, &indent);
+    Ok(format!(
+        "{}{}\n{}",
+        &unmanaged[..line_start],
+        block,
+        &unmanaged[line_start..]
+    ))
+}
+
+fn managed_block(jdks: &[Jdk], declared: &[&str], indent: &str) -> String {
+    let mut lines = vec![format!("{}{}", indent, BEGIN_MARKER)];
+    for jdk in jdks {
+        let home = jdk.home_dir().display().to_string();
+        if declared.iter().any(|item| *item == home) {
+            continue;
+        }
+        lines.push(format!("{}<toolchain>", indent));
+        lines.push(format!("{}  <type>jdk</type>", indent));
+        lines.push(format!("{}  <provides>", indent));
+        lines.push(format!(
+            "{}    <version>{}</version>",
+            indent,
+            escape_xml(&jdk.version.to_string())
+        ));
+        if jdk.distribution != Distribution::Unknown {
+            lines.push(format!(
+                "{}    <vendor>{}</vendor>",
+                indent,
+                jdk.distribution.id()
+            ));
+        }
+        lines.push(format!("{}  </provides>", indent));
+        lines.push(format!("{}  <configuration>", indent));
+        lines.push(format!(
+            "{}    <jdkHome>{}</jdkHome>",
+            indent,
+            escape_xml(&home)
+        ));
+        lines.push(format!("{}  </configuration>", indent));
+        lines.push(format!("{}</toolchain>", indent));
+    }
+    lines.push(format!("{}{}", indent, END_MARKER));
+    lines.join("\n")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::merge_toolchains;
+    use crate::{
+        model::{distribution::Distribution, jdk::Jdk},
+        test_util,
+    };
+
+    fn jdk(path: &str, version: &str) -> Jdk {
+        Jdk {
+            distribution: Distribution::Temurin,
+            ..test_util::jdk(path, version)
+        }
+    }
+
+    #[test]
+    fn test_merge_toolchains() {
+        let content = "<?xml version=\"1.0\"?>\n\
+            <toolchains>\n  \
+              <!-- netbeans -->\n  \
+              <toolchain>\n    \
+                <type>netbeans</type>\n  \
+              </toolchain>\n  \
+              <toolchain>\n    \
+                <type>jdk</type>\n    \
+                <configuration><jdkHome>/opt/jdk-11</jdkHome></configuration>\n  \
+              </toolchain>\n\
+            </toolchains>\n";
+        let jdks = [
+            jdk("/opt/jdk-11/bin", "11.0.21"),
+            jdk("/opt/jdk-17/bin", "17.0.9"),
+        ];
+        let merged = merge_toolchains(content, &jdks).unwrap();
+        assert!(merged.contains("<!-- netbeans -->"));
+        assert!(merged.contains("<jdkHome>/opt/jdk-17</jdkHome>"));
+        assert!(merged.contains("<vendor>temurin</vendor>"));
+        assert_eq!(1, merged.matches("/opt/jdk-11").count());
+        assert!(merged.ends_with(
+            "  <!-- END JDK Switcher managed toolchains -->\n</toolchains>\n"
+        ));
+
+        // Syncing again replaces the block
+        let merged_again = merge_toolchains(&merged, &jdks[..1]).unwrap();
+        assert!(!merged_again.contains("/opt/jdk-17"));
+        assert_eq!(merge_toolchains(&merged, &jdks).unwrap(), merged);
+
+        let created = merge_toolchains("", &jdks).unwrap();
+        assert!(created.starts_with("<?xml"));
+        assert_eq!(2, created.matches("<toolchain>").count());
+    }
+
+    #[test]
+    fn test_merge_toolchains_without_jdks() {
+        let content = "<?xml version=\"1.0\"?>\n\
+            <toolchains>\n  \
+              <toolchain>\n    \
+                <type>netbeans</type>\n  \
+              </toolchain>\n\
+            </toolchains>\n";
+        // A file without a managed block is kept as is
+        assert_eq!(content, merge_toolchains(content, &[]).unwrap());
+        assert_eq!("", merge_toolchains("", &[]).unwrap());
+
+        // The managed block is removed with its markers
+        let jdks = [jdk("/opt/jdk-17/bin", "17.0.9")];
+        let merged = merge_toolchains(content, &jdks).unwrap();
+        let removed = merge_toolchains(&merged, &[]).unwrap();
+        assert!(!removed.contains("JDK Switcher"));
+        assert_eq!(content, removed);
+    }
+}
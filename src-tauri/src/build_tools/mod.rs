@@ -0,0 +1,21 @@
+pub mod gradle;
+pub mod maven;
+
+use std::path::Path;
+
+use crate::errors::AppError;
+
+/// Write the content if it is changed, parent dirs are created if needed.
+/// Returns `true` if the file is written.
+fn write_if_changed(path: &Path, content: &str) -> Result<bool, AppError> {
+    if std::fs::read_to_string(path).is_ok_and(|prev| prev == content) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content).map_err(|e| {
+        AppError::new(format!("Cannot write '{}': {}", path.display(), e))
+    })?;
+    Ok(true)
+}
@@ -0,0 +1,182 @@
+use std::path::Path;
+
+use crate::{
+    app_settings::AppSettings,
+    errors::AppError,
+    installer::{self, upgrade},
+    jar_inspector::{self, JarInspection},
+    model::jdk::Jdk,
+    repo::jdk_repository::JdkRepository,
+};
+
+const CODE_BAD_ARGS: i32 = -10;
+const CODE_FAILED: i32 = -20;
+
+const HELP_MESSAGE: &str = r#"
+Usage:
+  jdk-switcher inspect-jar <path>         Print the java version a jar or
+                                          class file needs.
+  jdk-switcher run-jar <path> [args...]   Run a jar with the best matching
+                                          saved jdk.
+  jdk-switcher check-updates              Look for newer patch releases of
+                                          the saved jdks.
+  jdk-switcher help                       Print help message.
+
+The app window is opened if no command is given.
+"#;
+
+enum CliCommand {
+    Help,
+    InspectJar(String),
+    RunJar(String, Vec<String>),
+    CheckUpdates,
+}
+
+/// Run the command line command if the args have one, returns the exit
+/// code, or `None` to open the app window.
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = match parse_command(args)? {
+        Ok(command) => command,
+        Err(e) => {
+            attach_parent_console();
+            eprintln!("{}\n{}", e, HELP_MESSAGE);
+            return Some(CODE_BAD_ARGS);
+        }
+    };
+    attach_parent_console();
+    let ret = match command {
+        CliCommand::Help => {
+            println!("{}", HELP_MESSAGE);
+            Ok(0)
+        }
+        CliCommand::InspectJar(path) => inspect_jar(&path),
+        CliCommand::RunJar(path, args) => run_jar(&path, &args),
+        CliCommand::CheckUpdates => check_updates(),
+    };
+    match ret {
+        Ok(code) => Some(code),
+        Err(e) => {
+            eprintln!("{}", e);
+            Some(CODE_FAILED)
+        }
+    }
+}
+
+fn parse_command(args: &[String]) -> Option<Result<CliCommand, String>> {
+    let (name, rest) = args.split_first()?;
+    let path = || {
+        rest.first()
+            .cloned()
+            .ok_or_else(|| format!("Missing the path of '{}'", name))
+    };
+    let command = match name.as_str() {
+        "help" | "-h" | "--help" => Ok(CliCommand::Help),
+        "inspect-jar" => path().map(CliCommand::InspectJar),
+        "run-jar" => path().map(|path| {
+            CliCommand::RunJar(path, rest.iter().skip(1).cloned().collect())
+        }),
+        "check-updates" => Ok(CliCommand::CheckUpdates),
+        // Unknown args, e.g. passed by the OS, open the window
+        _ => return None,
+    };
+    Some(command)
+}
+
+fn inspect(path: &str) -> Result<JarInspection, AppError> {
+    let jdks = JdkRepository::new().get_all().unwrap_or_default();
+    jar_inspector::inspect(Path::new(path), &jdks)
+}
+
+fn inspect_jar(path: &str) -> Result<i32, AppError> {
+    let JarInspection { info, jdk } = inspect(path)?;
+    let or_none = |value: Option<String>| value.unwrap_or("-".to_string());
+    println!(
+        "Minimum java:   {}",
+        or_none(info.min_java.map(|v| v.to_string()))
+    );
+    println!(
+        "Class version:  {}",
+        or_none(info.class_major.map(|v| v.to_string()))
+    );
+    if info.multi_release {
+        let versions: Vec<String> = info
+            .release_versions
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        println!("Multi-release:  {}", versions.join(", "));
+    }
+    println!("Created-By:     {}", or_none(info.created_by));
+    println!("Build-Jdk-Spec: {}", or_none(info.build_jdk_spec));
+    println!("Main-Class:     {}", or_none(info.main_class));
+    match jdk {
+        Some(jdk) => println!("Matching jdk:   {} {}", jdk.name, jdk.version),
+        None => println!("Matching jdk:   missing"),
+    }
+    Ok(0)
+}
+
+fn run_jar(path: &str, args: &[String]) -> Result<i32, AppError> {
+    let JarInspection { info, jdk } = inspect(path)?;
+    let Some(jdk) = jdk else {
+        return Err(AppError::new(format!(
+            "No saved jdk can run '{}', it needs java {}+",
+            path,
+            info.min_java.unwrap_or(1)
+        )));
+    };
+    eprintln!("Running with {} {}", jdk.name, jdk.version);
+    let status = jar_inspector::run_jar(Path::new(path), &jdk, args)?.wait()?;
+    Ok(status.code().unwrap_or(CODE_FAILED))
+}
+
+/// Record the newest patch release of each saved jdk like the app does,
+/// and print them.
+fn check_updates() -> Result<i32, AppError> {
+    let repo = JdkRepository::new();
+    let query_jdks = repo.get_all().unwrap_or_default();
+    let packages =
+        installer::query_catalogs(&AppSettings::load(), |catalog| {
+            upgrade::query_upgrades(catalog, &query_jdks)
+        })?;
+    // The app may have changed the jdks during the query
+    let is_checked =
+        |jdk: &Jdk| query_jdks.iter().any(|item| item.path == jdk.path);
+    let mut jdks = vec![];
+    repo.update_all(|all| {
+        upgrade::record_updates(
+            all.iter_mut().filter(|item| is_checked(item)),
+            &packages,
+        );
+        jdks = all
+            .iter()
+            .filter(|item| is_checked(item))
+            .cloned()
+            .collect();
+        Ok(())
+    })?;
+    for jdk in &jdks {
+        let name = format!("{} {}", jdk.name, jdk.version);
+        match &jdk.latest_available {
+            Some(latest) => println!("{:<40}{} available", name, latest),
+            None => println!("{:<40}up to date", name),
+        }
+    }
+    Ok(0)
+}
+
+/// The app uses the windows subsystem in release builds, attach to the
+/// console of the shell to print the output.
+#[cfg(windows)]
+fn attach_parent_console() {
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
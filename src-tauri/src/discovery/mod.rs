@@ -0,0 +1,91 @@
+pub mod registry;
+pub mod system;
+pub mod tools;
+pub mod version_managers;
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    jdk_finder::{find_java_exe, find_jdk_from_exe_path},
+    model::{jdk::Jdk, jdk_source::JdkSource},
+};
+
+use self::{
+    system::SystemJdks, tools::ToolJdks, version_managers::VersionManager,
+};
+
+/// Find jdks installed by version managers, IDEs and build tools from their
+/// well-known dirs.
+pub trait JdkDiscoverer: Send + Sync {
+    fn source(&self) -> JdkSource;
+
+    /// Home dirs of the installed jdks, they are not validated yet.
+    fn candidate_homes(&self) -> Vec<PathBuf>;
+}
+
+pub fn default_discoverers() -> Vec<Box<dyn JdkDiscoverer>> {
+    let mut discoverers: Vec<Box<dyn JdkDiscoverer>> = vec![
+        Box::new(VersionManager::sdkman()),
+        Box::new(VersionManager::jenv()),
+        Box::new(VersionManager::asdf()),
+        Box::new(VersionManager::mise()),
+        Box::new(ToolJdks::intellij_idea()),
+        Box::new(ToolJdks::gradle()),
+        // Before JetBrains IDEs since the Toolbox App installs both
+        Box::new(ToolJdks::android_studio()),
+        Box::new(ToolJdks::jetbrains_ide()),
+    ];
+    #[cfg(windows)]
+    discoverers.push(Box::new(registry::RegistryJdks::windows()));
+    if cfg!(target_os = "linux") {
+        discoverers.push(Box::new(SystemJdks::linux()));
+    }
+    discoverers
+}
+
+/// Probe the candidate homes of the discoverers, jdks are tagged with the
+/// source of the first discoverer that finds them.
+pub fn discover_jdks(discoverers: &[Box<dyn JdkDiscoverer>]) -> Vec<Jdk> {
+    let mut probed: HashSet<PathBuf> = HashSet::new();
+    let mut jdks = vec![];
+    for discoverer in discoverers {
+        for home in discoverer.candidate_homes() {
+            let Some(java) = find_java_exe(&home) else {
+                continue;
+            };
+            let canonical = java.canonicalize().unwrap_or(java.clone());
+            if !probed.insert(canonical) {
+                continue;
+            }
+            match find_jdk_from_exe_path(&java) {
+                Ok(mut jdk) => {
+                    jdk.source = discoverer.source();
+                    jdks.push(jdk);
+                }
+                Err(e) => eprintln!(
+                    "Skipped {} jdk '{}': {}",
+                    discoverer.source(),
+                    home.display(),
+                    e
+                ),
+            }
+        }
+    }
+    jdks
+}
+
+/// Check if a dir is a link to a sibling dir, like `current` of SDKMAN,
+/// `17` of mise or `default-java` of Debian.
+fn is_alias(dir: &Path, path: &Path) -> bool {
+    if !path.is_symlink() {
+        return false;
+    }
+    let (Ok(target), Ok(dir)) = (path.canonicalize(), dir.canonicalize())
+    else {
+        return false;
+    };
+    target.parent() == Some(dir.as_path())
+}
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+#[cfg(windows)]
+use winreg::{
+    enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY},
+    RegKey,
+};
+
+use crate::model::jdk_source::JdkSource;
+
+use super::JdkDiscoverer;
+
+/// Keys under `HKLM` where installers register jdks, versions are nested in
+/// different depths, e.g. `JavaSoft\JDK\17.0.9` and
+/// `Eclipse Adoptium\JDK\17.0.9.9\hotspot\MSI`.
+const ROOT_KEYS: [&str; 12] = [
+    "SOFTWARE\\JavaSoft\\JDK",
+    "SOFTWARE\\JavaSoft\\JRE",
+    "SOFTWARE\\JavaSoft\\Java Development Kit",
+    "SOFTWARE\\JavaSoft\\Java Runtime Environment",
+    "SOFTWARE\\Eclipse Adoptium",
+    "SOFTWARE\\Eclipse Foundation",
+    "SOFTWARE\\AdoptOpenJDK",
+    "SOFTWARE\\Semeru",
+    "SOFTWARE\\Azul Systems\\Zulu",
+    "SOFTWARE\\Amazon Corretto",
+    "SOFTWARE\\Microsoft\\JDK",
+    "SOFTWARE\\BellSoft\\Liberica",
+];
+
+/// Names of values which point to the jdk home, vendors do not agree.
+const HOME_VALUE_NAMES: [&str; 3] = ["JavaHome", "InstallationPath", "Path"];
+
+const MAX_KEY_DEPTH: usize = 4;
+
+/// The registry view, 32-bit installers write to `WOW6432Node` on 64-bit
+/// Windows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegistryView {
+    Bits64,
+    Bits32,
+}
+
+/// Read access to `HKEY_LOCAL_MACHINE`.
+pub trait RegistryAccess: Send + Sync {
+    /// Names of the sub keys, empty if the key does not exist.
+    fn sub_keys(&self, view: RegistryView, path: &str) -> Vec<String>;
+
+    /// A string value of the key, `None` if the key or value does not exist.
+    fn string_value(
+        &self,
+        view: RegistryView,
+        path: &str,
+        name: &str,
+    ) -> Option<String>;
+}
+
+#[cfg(windows)]
+pub struct WindowsRegistry;
+
+#[cfg(windows)]
+impl WindowsRegistry {
+    fn open(view: RegistryView, path: &str) -> Option<RegKey> {
+        let flags = match view {
+            RegistryView::Bits64 => KEY_READ | KEY_WOW64_64KEY,
+            RegistryView::Bits32 => KEY_READ | KEY_WOW64_32KEY,
+        };
+        RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey_with_flags(path, flags)
+            .ok()
+    }
+}
+
+#[cfg(windows)]
+impl RegistryAccess for WindowsRegistry {
+    fn sub_keys(&self, view: RegistryView, path: &str) -> Vec<String> {
+        let Some(key) = WindowsRegistry::open(view, path) else {
+            return vec![];
+        };
+        key.enum_keys().filter_map(|item| item.ok()).collect()
+    }
+
+    fn string_value(
+        &self,
+        view: RegistryView,
+        path: &str,
+        name: &str,
+    ) -> Option<String> {
+        WindowsRegistry::open(view, path)?.get_value(name).ok()
+    }
+}
+
+/// Jdks registered by installers in the Windows registry.
+pub struct RegistryJdks<R: RegistryAccess> {
+    registry: R,
+}
+
+impl<R: RegistryAccess> RegistryJdks<R> {
+    pub fn new(registry: R) -> Self {
+        Self { registry }
+    }
+
+    fn find_homes(
+        &self,
+        view: RegistryView,
+        path: &str,
+        depth: usize,
+        homes: &mut Vec<PathBuf>,
+    ) {
+        let home = HOME_VALUE_NAMES
+            .iter()
+            .find_map(|name| self.registry.string_value(view, path, name));
+        if let Some(home) = home {
+            let home = home.trim().trim_end_matches('\\');
+            // Paths are case-insensitive, '1.8' and '1.8.0_392' share
+            // the same home
+            let is_duplicate = homes
+                .iter()
+                .any(|item| item.to_string_lossy().eq_ignore_ascii_case(home));
+            if !home.is_empty() && !is_duplicate {
+                homes.push(PathBuf::from(home));
+            }
+            return;
+        }
+        if depth >= MAX_KEY_DEPTH {
+            return;
+        }
+        for sub_key in self.registry.sub_keys(view, path) {
+            let sub_path = format!("{}\\{}", path, sub_key);
+            self.find_homes(view, &sub_path, depth + 1, homes);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl RegistryJdks<WindowsRegistry> {
+    pub fn windows() -> Self {
+        Self::new(WindowsRegistry)
+    }
+}
+
+impl<R: RegistryAccess> JdkDiscoverer for RegistryJdks<R> {
+    fn source(&self) -> JdkSource {
+        JdkSource::Registry
+    }
+
+    fn candidate_homes(&self) -> Vec<PathBuf> {
+        let mut homes = vec![];
+        for view in [RegistryView::Bits64, RegistryView::Bits32] {
+            for root in ROOT_KEYS {
+                self.find_homes(view, root, 0, &mut homes);
+            }
+        }
+        homes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use super::{RegistryAccess, RegistryJdks, RegistryView};
+    use crate::discovery::JdkDiscoverer;
+
+    /// Keys are `(view, path)`, values are `(name, data)` pairs.
+    struct FakeRegistry {
+        keys: HashMap<(RegistryView, String), Vec<(String, String)>>,
+    }
+
+    impl FakeRegistry {
+        fn new(keys: &[(RegistryView, &str, &[(&str, &str)])]) -> Self {
+            let keys = keys
+                .iter()
+                .map(|(view, path, values)| {
+                    let values = values
+                        .iter()
+                        .map(|(name, data)| {
+                            (name.to_string(), data.to_string())
+                        })
+                        .collect();
+                    ((*view, path.to_string()), values)
+                })
+                .collect();
+            Self { keys }
+        }
+    }
+
+    impl RegistryAccess for FakeRegistry {
+        fn sub_keys(&self, view: RegistryView, path: &str) -> Vec<String> {
+            let prefix = format!("{}\\", path);
+            let mut sub_keys: Vec<String> = self
+                .keys
+                .keys()
+                .filter(|(item_view, _)| *item_view == view)
+                .filter_map(|(_, item)| item.strip_prefix(&prefix))
+                .filter_map(|rest| rest.split('\\').next())
+                .map(|name| name.to_string())
+                .collect();
+            sub_keys.sort();
+            sub_keys.dedup();
+            sub_keys
+        }
+
+        fn string_value(
+            &self,
+            view: RegistryView,
+            path: &str,
+            name: &str,
+        ) -> Option<String> {
+            self.keys
+                .get(&(view, path.to_string()))?
+                .iter()
+                .find(|(item, _)| item == name)
+                .map(|(_, data)| data.clone())
+        }
+    }
+
+    #[test]
+    fn test_registry_homes() {
+        let registry = FakeRegistry::new(&[
+            (
+                RegistryView::Bits64,
+                "SOFTWARE\\JavaSoft\\JDK\\17",
+                &[("JavaHome", "C:\\Program Files\\Java\\jdk-17")],
+            ),
+            (
+                RegistryView::Bits64,
+                "SOFTWARE\\JavaSoft\\JDK\\17.0.9",
+                &[("JavaHome", "C:\\Program Files\\Java\\JDK-17\\")],
+            ),
+            (
+                RegistryView::Bits64,
+                "SOFTWARE\\Eclipse Adoptium\\JDK\\21.0.1.12\\hotspot\\MSI",
+                &[("Path", "C:\\Program Files\\Eclipse Adoptium\\jdk-21")],
+            ),
+            (
+                RegistryView::Bits32,
+                "SOFTWARE\\Azul Systems\\Zulu\\zulu-8",
+                &[("InstallationPath", "C:\\Program Files (x86)\\Zulu\\8")],
+            ),
+            (RegistryView::Bits64, "SOFTWARE\\JavaSoft\\JRE\\1.8", &[]),
+        ]);
+        let homes = RegistryJdks::new(registry).candidate_homes();
+        assert_eq!(
+            vec![
+                PathBuf::from("C:\\Program Files\\Java\\jdk-17"),
+                PathBuf::from("C:\\Program Files\\Eclipse Adoptium\\jdk-21"),
+                PathBuf::from("C:\\Program Files (x86)\\Zulu\\8"),
+            ],
+            homes
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::model::jdk_source::JdkSource;
+
+use super::{is_alias, JdkDiscoverer};
+
+const JVM_DIR: &str = "/usr/lib/jvm";
+
+const ALTERNATIVES_LINK: &str = "/etc/alternatives/java";
+
+/// Jdks installed by the package manager of Linux distros, they live in
+/// `/usr/lib/jvm` and are selected through `update-alternatives`.
+pub struct SystemJdks {
+    jvm_dir: PathBuf,
+}
+
+impl SystemJdks {
+    pub fn new(jvm_dir: PathBuf) -> Self {
+        Self { jvm_dir }
+    }
+
+    pub fn linux() -> Self {
+        Self::new(PathBuf::from(JVM_DIR))
+    }
+}
+
+impl JdkDiscoverer for SystemJdks {
+    fn source(&self) -> JdkSource {
+        JdkSource::System
+    }
+
+    fn candidate_homes(&self) -> Vec<PathBuf> {
+        let mut homes: Vec<PathBuf> = std::fs::read_dir(&self.jvm_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|item| item.ok())
+                    .map(|item| item.path())
+                    .filter(|path| {
+                        path.is_dir() && !is_alias(&self.jvm_dir, path)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        homes.sort();
+        // Alternatives may be registered from other dirs
+        if let Some(mut query) = query_alternatives("java") {
+            query.alternatives.sort_by_key(|item| -item.priority);
+            for alternative in query.alternatives {
+                let Some(home) = java_home_of(&alternative.path) else {
+                    continue;
+                };
+                if !home.starts_with(&self.jvm_dir) && !homes.contains(&home) {
+                    homes.push(home);
+                }
+            }
+        }
+        homes
+    }
+}
+
+/// Parsed output of `update-alternatives --query <name>`.
+#[derive(Debug, Default)]
+pub struct AlternativesQuery {
+    /// The alternative with the highest priority.
+    pub best: Option<PathBuf>,
+    /// The selected alternative.
+    pub value: Option<PathBuf>,
+    pub alternatives: Vec<Alternative>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Alternative {
+    pub path: PathBuf,
+    pub priority: i64,
+}
+
+/// Run `update-alternatives --query <name>`, returns `None` if the command
+/// is not available, e.g. on Fedora which ships `alternatives` instead.
+pub fn query_alternatives(name: &str) -> Option<AlternativesQuery> {
+    let output = Command::new("update-alternatives")
+        .arg("--query")
+        .arg(name)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(parse_alternatives_query(&stdout))
+}
+
+/// Parse the output of `update-alternatives --query`, it is a list of
+/// blank line separated stanzas, the first one describes the link group.
+pub fn parse_alternatives_query(output: &str) -> AlternativesQuery {
+    let mut query = AlternativesQuery::default();
+    let mut alternative: Option<Alternative> = None;
+    for line in output.lines() {
+        // Slave lines are indented
+        if line.starts_with(' ') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key {
+            "Best" => query.best = Some(PathBuf::from(value)),
+            "Value" if value != "none" => {
+                query.value = Some(PathBuf::from(value))
+            }
+            "Alternative" => {
+                query.alternatives.extend(alternative.take());
+                alternative = Some(Alternative {
+                    path: PathBuf::from(value),
+                    priority: 0,
+                });
+            }
+            "Priority" => {
+                if let Some(alternative) = alternative.as_mut() {
+                    alternative.priority = value.parse().unwrap_or(0);
+                }
+            }
+            _ => {}
+        }
+    }
+    query.alternatives.extend(alternative);
+    query
+}
+
+/// Find the java executable selected by the system, from the alternatives
+/// database or by resolving `/etc/alternatives/java`.
+pub fn find_system_java() -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    query_alternatives("java")
+        .and_then(|query| query.value.or(query.best))
+        .or_else(|| Path::new(ALTERNATIVES_LINK).canonicalize().ok())
+        .filter(|path| path.is_file())
+}
+
+/// Resolve links of a java executable found on the PATH, e.g. from
+/// `/usr/bin/java` to `/usr/lib/jvm/java-17-openjdk-amd64/bin/java`.
+pub fn resolve_java_links(path: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") || !path.is_symlink() {
+        return path.to_path_buf();
+    }
+    path.canonicalize().unwrap_or(path.to_path_buf())
+}
+
+/// `<home>/bin/java` to `<home>`, older jdks keep java in `<home>/jre/bin`.
+fn java_home_of(java: &Path) -> Option<PathBuf> {
+    let home = java.parent()?.parent()?;
+    if home.file_name().is_some_and(|name| name == "jre") {
+        return home.parent().map(|path| path.to_path_buf());
+    }
+    Some(home.to_path_buf())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{parse_alternatives_query, Alternative};
+
+    #[test]
+    fn test_parse_alternatives_query() {
+        let output =
+            include_str!("fixtures/update_alternatives_query_java.txt");
+        let query = parse_alternatives_query(output);
+        assert_eq!(
+            Some(PathBuf::from("/usr/lib/jvm/java-21-openjdk-amd64/bin/java")),
+            query.best
+        );
+        assert_eq!(
+            Some(PathBuf::from("/usr/lib/jvm/java-17-openjdk-amd64/bin/java")),
+            query.value
+        );
+        assert_eq!(4, query.alternatives.len());
+        assert_eq!(
+            Alternative {
+                path: PathBuf::from(
+                    "/usr/lib/jvm/java-11-openjdk-amd64/bin/java"
+                ),
+                priority: 1111,
+            },
+            query.alternatives[0]
+        );
+        assert_eq!(
+            Alternative {
+                path: PathBuf::from("/opt/jdk-22/bin/java"),
+                priority: 100,
+            },
+            query.alternatives[3]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    jdk_finder::find_java_exe,
+    model::jdk_source::JdkSource,
+    util::paths::{gradle_user_home, user_home_dir},
+};
+
+use super::JdkDiscoverer;
+
+/// Jdks downloaded or bundled by IDEs and build tools, they are found by
+/// walking a few levels of sub dirs from the roots.
+pub struct ToolJdks {
+    source: JdkSource,
+    roots: Vec<PathBuf>,
+    max_depth: usize,
+    /// Only accept homes with these dir names, IDE installs contain other
+    /// runtimes we are not interested in. Empty to accept any home.
+    home_names: &'static [&'static str],
+}
+
+impl ToolJdks {
+    pub fn new(
+        source: JdkSource,
+        roots: Vec<PathBuf>,
+        max_depth: usize,
+        home_names: &'static [&'static str],
+    ) -> Self {
+        Self {
+            source,
+            roots,
+            max_depth,
+            home_names,
+        }
+    }
+
+    /// `~/.jdks/corretto-17.0.9`
+    pub fn intellij_idea() -> Self {
+        let mut roots: Vec<PathBuf> = home_join(".jdks").into_iter().collect();
+        if cfg!(target_os = "macos") {
+            roots.extend(home_join("Library/Java/JavaVirtualMachines"));
+        }
+        Self::new(JdkSource::IntellijIdea, roots, 1, &[])
+    }
+
+    /// `~/.gradle/jdks/eclipse_adoptium-17-amd64-windows/jdk-17.0.9+9`, newer
+    /// versions of Gradle do not nest the home.
+    pub fn gradle() -> Self {
+        let roots = gradle_user_home().map(|home| home.join("jdks"));
+        Self::new(JdkSource::Gradle, roots.into_iter().collect(), 2, &[])
+    }
+
+    /// `<install dir>/IntelliJ IDEA 2023.3/jbr`, or installed by the
+    /// Toolbox App like `apps/IDEA-U/ch-0/233.11799.241/jbr`.
+    pub fn jetbrains_ide() -> Self {
+        let mut roots = vec![];
+        if cfg!(target_os = "windows") {
+            roots.extend(env_join("ProgramFiles", "JetBrains"));
+            roots.extend(env_join("LOCALAPPDATA", "Programs"));
+            roots.extend(env_join("LOCALAPPDATA", "JetBrains/Toolbox/apps"));
+        } else if cfg!(target_os = "macos") {
+            roots.push(PathBuf::from("/Applications"));
+            roots.extend(home_join("Applications"));
+        } else {
+            roots.push(PathBuf::from("/opt"));
+            roots.push(PathBuf::from("/snap"));
+            roots.extend(home_join(".local/share/JetBrains/Toolbox/apps"));
+        }
+        Self::new(JdkSource::JetBrainsIde, roots, 4, &["jbr"])
+    }
+
+    /// `<install dir>/Android Studio/jbr`, older versions use `jre`.
+    pub fn android_studio() -> Self {
+        let mut roots = vec![];
+        if cfg!(target_os = "windows") {
+            roots.extend(env_join("ProgramFiles", "Android"));
+            roots.extend(env_join("LOCALAPPDATA", "Programs/Android Studio"));
+            roots.extend(env_join(
+                "LOCALAPPDATA",
+                "JetBrains/Toolbox/apps/AndroidStudio",
+            ));
+        } else if cfg!(target_os = "macos") {
+            roots.push(PathBuf::from("/Applications/Android Studio.app"));
+        } else {
+            roots.push(PathBuf::from("/opt/android-studio"));
+            roots.extend(home_join("android-studio"));
+            roots.extend(home_join(
+                ".local/share/JetBrains/Toolbox/apps/AndroidStudio",
+            ));
+        }
+        Self::new(JdkSource::AndroidStudio, roots, 3, &["jbr", "jre"])
+    }
+}
+
+impl JdkDiscoverer for ToolJdks {
+    fn source(&self) -> JdkSource {
+        self.source
+    }
+
+    fn candidate_homes(&self) -> Vec<PathBuf> {
+        let mut homes = vec![];
+        for root in &self.roots {
+            self.find_homes(root, 0, &mut homes);
+        }
+        homes
+    }
+}
+
+impl ToolJdks {
+    fn find_homes(&self, dir: &Path, depth: usize, homes: &mut Vec<PathBuf>) {
+        let is_accepted = self.home_names.is_empty()
+            || dir
+                .file_name()
+                .is_some_and(|name| self.home_names.iter().any(|n| name == *n));
+        if depth > 0 && find_java_exe(dir).is_some() {
+            if is_accepted {
+                homes.push(dir.to_path_buf());
+            }
+            // Do not look for jdks in jdks
+            return;
+        }
+        if depth >= self.max_depth {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(|item| item.ok())
+            .map(|item| item.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+        for dir in dirs {
+            self.find_homes(&dir, depth + 1, homes);
+        }
+    }
+}
+
+fn home_join(path: &str) -> Option<PathBuf> {
+    user_home_dir().map(|home| home.join(path))
+}
+
+fn env_join(key: &str, path: &str) -> Option<PathBuf> {
+    std::env::var_os(key)
+        .filter(|item| !item.is_empty())
+        .map(|dir| PathBuf::from(dir).join(path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::ToolJdks;
+    use crate::{
+        discovery::JdkDiscoverer, jdk_finder::java_executable_filename,
+        model::jdk_source::JdkSource, test_util::temp_dir,
+    };
+
+    #[test]
+    fn test_find_nested_homes() {
+        let root = temp_dir("jdk-discovery-tools");
+        for home in [
+            "gradle/eclipse_adoptium-17-amd64-linux/jdk-17.0.9+9",
+            "gradle/azul_systems-21-amd64-linux",
+            "ide/IDEA-U/ch-0/233.11799.241/jbr",
+            "ide/IDEA-U/ch-0/233.11799.241/plugins/foo/jre",
+        ] {
+            let bin_dir = root.join(home).join("bin");
+            std::fs::create_dir_all(&bin_dir).unwrap();
+            std::fs::write(bin_dir.join(java_executable_filename()), "")
+                .unwrap();
+        }
+
+        let gradle =
+            ToolJdks::new(JdkSource::Gradle, vec![root.join("gradle")], 2, &[]);
+        let ide = ToolJdks::new(
+            JdkSource::JetBrainsIde,
+            vec![root.join("ide")],
+            4,
+            &["jbr"],
+        );
+        let gradle_homes = gradle.candidate_homes();
+        let ide_homes = ide.candidate_homes();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            vec![
+                root.join("gradle/azul_systems-21-amd64-linux"),
+                root.join(
+                    "gradle/eclipse_adoptium-17-amd64-linux/jdk-17.0.9+9"
+                ),
+            ],
+            gradle_homes
+        );
+        assert_eq!(
+            vec![root.join("ide/IDEA-U/ch-0/233.11799.241/jbr")],
+            ide_homes
+        );
+    }
+}
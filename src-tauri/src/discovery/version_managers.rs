@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use crate::{model::jdk_source::JdkSource, util::paths::user_home_dir};
+
+use super::{is_alias, JdkDiscoverer};
+
+/// Version managers keep every installed jdk in a sub dir of a versions dir,
+/// e.g. `~/.sdkman/candidates/java/21.0.1-tem`.
+pub struct VersionManager {
+    source: JdkSource,
+    versions_dir: Option<PathBuf>,
+}
+
+impl VersionManager {
+    pub fn new(source: JdkSource, versions_dir: Option<PathBuf>) -> Self {
+        Self {
+            source,
+            versions_dir,
+        }
+    }
+
+    pub fn sdkman() -> Self {
+        let root = env_dir("SDKMAN_DIR").or_else(|| home_join(".sdkman"));
+        Self::new(
+            JdkSource::Sdkman,
+            root.map(|root| root.join("candidates").join("java")),
+        )
+    }
+
+    /// Versions of jenv are links to jdks installed elsewhere.
+    pub fn jenv() -> Self {
+        let root = env_dir("JENV_ROOT").or_else(|| home_join(".jenv"));
+        Self::new(JdkSource::Jenv, root.map(|root| root.join("versions")))
+    }
+
+    pub fn asdf() -> Self {
+        let root = env_dir("ASDF_DATA_DIR").or_else(|| home_join(".asdf"));
+        Self::new(
+            JdkSource::Asdf,
+            root.map(|root| root.join("installs").join("java")),
+        )
+    }
+
+    pub fn mise() -> Self {
+        let root = env_dir("MISE_DATA_DIR")
+            .or_else(|| env_dir("XDG_DATA_HOME").map(|dir| dir.join("mise")))
+            .or_else(|| home_join(".local/share/mise"));
+        Self::new(
+            JdkSource::Mise,
+            root.map(|root| root.join("installs").join("java")),
+        )
+    }
+}
+
+impl JdkDiscoverer for VersionManager {
+    fn source(&self) -> JdkSource {
+        self.source
+    }
+
+    fn candidate_homes(&self) -> Vec<PathBuf> {
+        let Some(versions_dir) = &self.versions_dir else {
+            return vec![];
+        };
+        let Ok(entries) = std::fs::read_dir(versions_dir) else {
+            return vec![];
+        };
+        let mut homes: Vec<PathBuf> = entries
+            .filter_map(|item| item.ok())
+            .map(|item| item.path())
+            .filter(|path| path.is_dir() && !is_alias(versions_dir, path))
+            .collect();
+        homes.sort();
+        homes
+    }
+}
+
+fn env_dir(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key)
+        .filter(|item| !item.is_empty())
+        .map(PathBuf::from)
+}
+
+fn home_join(path: &str) -> Option<PathBuf> {
+    user_home_dir().map(|home| home.join(path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::VersionManager;
+    use crate::{
+        discovery::JdkDiscoverer, model::jdk_source::JdkSource,
+        test_util::temp_dir,
+    };
+
+    #[test]
+    fn test_candidate_homes() {
+        let root = temp_dir("jdk-discovery-sdkman");
+        let versions_dir = root.join("candidates").join("java");
+        for version in ["17.0.9-tem", "21.0.1-zulu"] {
+            std::fs::create_dir_all(versions_dir.join(version)).unwrap();
+        }
+        std::fs::write(versions_dir.join("README"), "").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            versions_dir.join("21.0.1-zulu"),
+            versions_dir.join("current"),
+        )
+        .unwrap();
+
+        let manager =
+            VersionManager::new(JdkSource::Sdkman, Some(versions_dir.clone()));
+        let homes = manager.candidate_homes();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(
+            vec![
+                versions_dir.join("17.0.9-tem"),
+                versions_dir.join("21.0.1-zulu")
+            ],
+            homes
+        );
+        let missing = VersionManager::new(JdkSource::Asdf, None);
+        assert!(missing.candidate_homes().is_empty());
+    }
+}
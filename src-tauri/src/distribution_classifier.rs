@@ -0,0 +1,305 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::model::distribution::Distribution;
+
+/// Keywords to find the distribution from vendor strings, GraalVM is
+/// handled separately and Oracle comes last since other vendors (e.g.
+/// Oracle GraalVM) mention it too.
+const VENDOR_KEYWORDS: [(&str, Distribution); 21] = [
+    ("temurin", Distribution::Temurin),
+    ("adoptium", Distribution::Temurin),
+    ("adoptopenjdk", Distribution::Temurin),
+    ("zulu", Distribution::Zulu),
+    ("azul", Distribution::Zulu),
+    ("corretto", Distribution::Corretto),
+    ("amazon", Distribution::Corretto),
+    ("liberica", Distribution::Liberica),
+    ("bellsoft", Distribution::Liberica),
+    ("microsoft", Distribution::Microsoft),
+    ("semeru", Distribution::Semeru),
+    ("openj9", Distribution::Semeru),
+    ("ibm", Distribution::Semeru),
+    ("sapmachine", Distribution::SapMachine),
+    ("jetbrains", Distribution::JetBrains),
+    ("jbr", Distribution::JetBrains),
+    ("red hat", Distribution::RedHat),
+    ("red_hat", Distribution::RedHat),
+    ("dragonwell", Distribution::Dragonwell),
+    ("alibaba", Distribution::Dragonwell),
+    ("oracle", Distribution::Oracle),
+];
+
+/// Find the distribution of a jdk.
+///
+/// The `release` file is checked first, then the system properties reported
+/// by the jdk (may be empty), and then the directory names.
+pub fn classify_distribution(
+    jdk_home: &Path,
+    release: &HashMap<String, String>,
+    properties: &HashMap<String, String>,
+) -> Distribution {
+    let is_oracle_jdk = is_oracle_jdk(release, properties);
+    let accepted = |distribution: &Distribution| {
+        *distribution != Distribution::Oracle || is_oracle_jdk
+    };
+    let release_texts: Vec<&str> =
+        ["IMPLEMENTOR", "IMPLEMENTOR_VERSION", "JVM_VARIANT"]
+            .iter()
+            .filter_map(|key| release.get(*key).map(|s| s.as_str()))
+            .collect();
+    let is_graalvm = release.contains_key("GRAALVM_VERSION");
+    if let Some(distribution) =
+        classify_texts(&release_texts, is_graalvm).filter(accepted)
+    {
+        return distribution;
+    }
+
+    let property_texts: Vec<&str> = [
+        "java.vendor.version",
+        "java.vendor",
+        "java.runtime.name",
+        "java.vm.name",
+        "java.vm.vendor",
+    ]
+    .iter()
+    .filter_map(|key| properties.get(*key).map(|s| s.as_str()))
+    .collect();
+    let is_graalvm = property_texts
+        .iter()
+        .any(|text| text.to_lowercase().contains("graalvm"));
+    if let Some(distribution) =
+        classify_texts(&property_texts, is_graalvm).filter(accepted)
+    {
+        return distribution;
+    }
+
+    // Directory layout, e.g. 'zulu17.46.19-ca-jdk17.0.9-win_x64' or
+    // '.sdkman/candidates/java/21.0.1-tem'.
+    let dir_names: Vec<String> = jdk_home
+        .ancestors()
+        .take(3)
+        .filter_map(|item| item.file_name())
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .collect();
+    for name in &dir_names {
+        if let Some(distribution) = classify_dir_name(name) {
+            return distribution;
+        }
+    }
+
+    Distribution::Unknown
+}
+
+/// Oracle is also the vendor of the OpenJDK builds from jdk.java.net, only
+/// the commercial builds are Oracle JDK.
+fn is_oracle_jdk(
+    release: &HashMap<String, String>,
+    properties: &HashMap<String, String>,
+) -> bool {
+    let is_commercial = release
+        .get("BUILD_TYPE")
+        .is_some_and(|value| value == "commercial");
+    // The OpenJDK builds are not LTS releases
+    let is_lts = release
+        .get("JAVA_RUNTIME_VERSION")
+        .is_some_and(|value| value.contains("-LTS"));
+    // 'Java(TM) SE Runtime Environment' instead of 'OpenJDK Runtime
+    // Environment'
+    let is_trademarked = ["java.runtime.name", "java.vm.name"]
+        .iter()
+        .filter_map(|key| properties.get(*key))
+        .any(|value| value.contains("(TM)"));
+    is_commercial || is_lts || is_trademarked
+}
+
+fn classify_texts(texts: &[&str], is_graalvm: bool) -> Option<Distribution> {
+    let texts: Vec<String> =
+        texts.iter().map(|text| text.to_lowercase()).collect();
+    if is_graalvm {
+        let is_community = texts.iter().any(|text| {
+            text.contains("community") || text.contains("graalvm ce")
+        });
+        return if is_community {
+            Some(Distribution::GraalvmCe)
+        } else {
+            Some(Distribution::GraalvmEe)
+        };
+    }
+    VENDOR_KEYWORDS
+        .iter()
+        .find(|(keyword, _)| {
+            texts.iter().any(|text| contains_word(text, keyword))
+        })
+        .map(|(_, distribution)| *distribution)
+}
+
+fn classify_dir_name(name: &str) -> Option<Distribution> {
+    if name.contains("graalvm") {
+        return if name.contains("community") || name.contains("-ce") {
+            Some(Distribution::GraalvmCe)
+        } else {
+            Some(Distribution::GraalvmEe)
+        };
+    }
+    // SDKMAN style suffixes: 21.0.1-tem
+    if let Some((version, suffix)) = name.rsplit_once('-') {
+        if version.starts_with(|c: char| c.is_ascii_digit()) {
+            if let Ok(distribution) = suffix.parse::<Distribution>() {
+                return Some(distribution);
+            }
+        }
+    }
+    VENDOR_KEYWORDS
+        .iter()
+        .filter(|(keyword, _)| *keyword != "oracle")
+        .find(|(keyword, _)| contains_word(name, keyword))
+        .map(|(_, distribution)| *distribution)
+}
+
+/// Check if a keyword appears in the text and does not start in the middle
+/// of a word, so 'jbr' matches 'jbr-17' but not 'libjbrowse'.
+fn contains_word(text: &str, keyword: &str) -> bool {
+    text.match_indices(keyword).any(|(index, _)| {
+        index == 0
+            || !text[..index]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::Path};
+
+    use super::classify_distribution;
+    use crate::{
+        model::distribution::Distribution,
+        util::release_file::parse_release_file,
+    };
+
+    fn classify_release(release: &str) -> Distribution {
+        classify_distribution(
+            Path::new("/opt/jdk"),
+            &parse_release_file(release),
+            &HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_classify_from_release() {
+        assert_eq!(
+            Distribution::Temurin,
+            classify_release("IMPLEMENTOR=\"Eclipse Adoptium\"")
+        );
+        assert_eq!(
+            Distribution::Zulu,
+            classify_release("IMPLEMENTOR=\"Azul Systems, Inc.\"")
+        );
+        assert_eq!(
+            Distribution::Corretto,
+            classify_release("IMPLEMENTOR=\"Amazon.com Inc.\"")
+        );
+        assert_eq!(
+            Distribution::GraalvmCe,
+            classify_release(
+                "IMPLEMENTOR=\"GraalVM Community\"\nGRAALVM_VERSION=\"23.1.1\""
+            )
+        );
+        assert_eq!(
+            Distribution::GraalvmEe,
+            classify_release(
+                "IMPLEMENTOR=\"Oracle Corporation\"\nGRAALVM_VERSION=\"23.1.1\""
+            )
+        );
+        assert_eq!(
+            Distribution::Semeru,
+            classify_release(
+                "IMPLEMENTOR=\"International Business Machines Corporation\"\n\
+                 JVM_VARIANT=\"Openj9\""
+            )
+        );
+        assert_eq!(
+            Distribution::JetBrains,
+            classify_release("IMPLEMENTOR=\"JetBrains s.r.o.\"")
+        );
+        assert_eq!(
+            Distribution::Oracle,
+            classify_release(
+                "IMPLEMENTOR=\"Oracle Corporation\"\nBUILD_TYPE=\"commercial\""
+            )
+        );
+        assert_eq!(
+            Distribution::Oracle,
+            classify_release(
+                "IMPLEMENTOR=\"Oracle Corporation\"\n\
+                 JAVA_RUNTIME_VERSION=\"21.0.1+12-LTS-29\""
+            )
+        );
+        // An OpenJDK build from jdk.java.net
+        assert_eq!(
+            Distribution::Unknown,
+            classify_release(
+                "IMPLEMENTOR=\"Oracle Corporation\"\n\
+                 JAVA_RUNTIME_VERSION=\"21.0.1+12-29\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_classify_from_properties() {
+        let properties = HashMap::from([
+            ("java.vendor".to_string(), "Oracle Corporation".to_string()),
+            ("java.vendor.version".to_string(), "SapMachine".to_string()),
+        ]);
+        assert_eq!(
+            Distribution::SapMachine,
+            classify_distribution(
+                Path::new("/opt/jdk"),
+                &HashMap::new(),
+                &properties,
+            )
+        );
+
+        let classify = |runtime_name: &str| {
+            let properties = HashMap::from([
+                ("java.vendor".to_string(), "Oracle Corporation".to_string()),
+                ("java.runtime.name".to_string(), runtime_name.to_string()),
+            ]);
+            classify_distribution(
+                Path::new("/opt/jdk"),
+                &HashMap::new(),
+                &properties,
+            )
+        };
+        assert_eq!(
+            Distribution::Oracle,
+            classify("Java(TM) SE Runtime Environment")
+        );
+        assert_eq!(
+            Distribution::Unknown,
+            classify("OpenJDK Runtime Environment")
+        );
+    }
+
+    #[test]
+    fn test_classify_from_dir_layout() {
+        let classify = |path: &str| {
+            classify_distribution(
+                Path::new(path),
+                &HashMap::new(),
+                &HashMap::new(),
+            )
+        };
+        assert_eq!(
+            Distribution::Zulu,
+            classify("C:/Java/zulu17.46.19-ca-jdk17.0.9-win_x64")
+        );
+        assert_eq!(
+            Distribution::Temurin,
+            classify("/home/me/.sdkman/candidates/java/21.0.1-tem")
+        );
+        assert_eq!(Distribution::JetBrains, classify("/opt/idea/jbr"));
+        assert_eq!(Distribution::Unknown, classify("/opt/jdk-17"));
+    }
+}
@@ -1,4 +1,5 @@
 use std::{fs::OpenOptions, io::Write, process::exit, vec};
+#[cfg(windows)]
 use winreg::{
     enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_SET_VALUE},
     RegKey,
@@ -148,6 +149,7 @@ fn remove_from_env_path(value: &str) -> Result<(), String> {
 /// ```rust no_run
 /// update_env_path(|path| path.replace("/path/to/my_program;", ""));
 /// ```
+#[cfg(windows)]
 fn update_env_path<F>(closure: F) -> Result<(), String>
 where
     F: FnOnce(String) -> String,
@@ -182,3 +184,11 @@ where
         .set_value("Path", &updated)
         .map_err(|e| format!("Failed to update PATH var: {}", e.to_string()))
 }
+
+#[cfg(not(windows))]
+fn update_env_path<F>(_closure: F) -> Result<(), String>
+where
+    F: FnOnce(String) -> String,
+{
+    Err("The system's PATH variable can only be updated on Windows".to_string())
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use crate::{errors::AppError, util::paths::user_home_dir};
+
+use super::IdeJdkEntry;
+
+const PREFS_PATH: &str = ".metadata/.plugins/org.eclipse.core.runtime/\
+    .settings/org.eclipse.jdt.launching.prefs";
+
+const VM_XML_KEY: &str = "org.eclipse.jdt.launching.PREF_VM_XML";
+
+/// Launching prefs of the default workspaces, other workspaces have to be
+/// picked by the user.
+pub fn default_config_files() -> Vec<PathBuf> {
+    let Some(home) = user_home_dir() else {
+        return vec![];
+    };
+    ["eclipse-workspace", "workspace"]
+        .iter()
+        .map(|workspace| home.join(workspace).join(PREFS_PATH))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Parse the installed jres of `org.eclipse.jdt.launching.prefs`, they are
+/// stored as an escaped XML property value:
+///
+/// ```text
+/// org.eclipse.jdt.launching.PREF_VM_XML=<?xml version\="1.0"?>\n<vmSettings>...
+/// ```
+pub fn parse_launching_prefs(
+    content: &str,
+) -> Result<Vec<IdeJdkEntry>, AppError> {
+    let Some(xml) = read_property(content, VM_XML_KEY) else {
+        return Ok(vec![]);
+    };
+    let doc = roxmltree::Document::parse(&xml)
+        .map_err(|e| AppError::new(format!("Invalid vm settings: {}", e)))?;
+    let entries = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("vm"))
+        .filter_map(|node| {
+            Some(IdeJdkEntry {
+                name: node.attribute("name")?.to_string(),
+                home: PathBuf::from(node.attribute("path")?),
+            })
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Read a value of a java properties file, lines ending with a backslash
+/// are continued.
+fn read_property(content: &str, key: &str) -> Option<String> {
+    let mut lines = content.lines();
+    let mut raw = loop {
+        let line = lines.next()?.trim_start();
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        if name.trim() == key {
+            break value.to_string();
+        }
+    };
+    while raw.ends_with('\\') && !raw.ends_with("\\\\") {
+        raw.pop();
+        raw.push_str(lines.next()?.trim_start());
+    }
+    Some(unescape_property(&raw))
+}
+
+fn unescape_property(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::parse_launching_prefs;
+
+    #[test]
+    fn test_parse_launching_prefs() {
+        let content = "eclipse.preferences.version=1\n\
+            org.eclipse.jdt.launching.PREF_VM_XML=<?xml version\\=\"1.0\" \
+            encoding\\=\"UTF-8\" standalone\\=\"no\"?>\\n<vmSettings \
+            defaultVM\\=\"57,1\">\\n<vmType id\\=\"StandardVMType\">\\n\
+            <vm id\\=\"1\" name\\=\"jdk-17\" \
+            path\\=\"C\\:\\\\Program Files\\\\Java\\\\jdk-17\"/>\\n\
+            </vmType>\\n</vmSettings>\\n\n";
+        let entries = parse_launching_prefs(content).unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!("jdk-17", entries[0].name);
+        assert_eq!(
+            PathBuf::from("C:\\Program Files\\Java\\jdk-17"),
+            entries[0].home
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use std::path::PathBuf;
+
+use crate::{
+    errors::AppError,
+    model::jdk::Jdk,
+    util::paths::{user_config_dir, user_home_dir},
+};
+
+use super::{same_home, IdeJdkEntry, MergedConfig};
+
+const USER_HOME_MACRO: &str = "$USER_HOME$";
+
+const COMPONENT_NAME: &str = "ProjectJdkTable";
+
+/// `options/jdk.table.xml` of all installed IntelliJ based IDEs, including
+/// Android Studio.
+pub fn default_config_files() -> Vec<PathBuf> {
+    let Some(config_dir) = user_config_dir() else {
+        return vec![];
+    };
+    [
+        "JetBrains/*/options/jdk.table.xml",
+        "Google/*/options/jdk.table.xml",
+    ]
+    .iter()
+    .map(|pattern| config_dir.join(pattern).to_string_lossy().to_string())
+    .filter_map(|pattern| glob::glob(&pattern).ok())
+    .flat_map(|paths| paths.filter_map(|item| item.ok()))
+    .collect()
+}
+
+/// Parse the java sdks of `jdk.table.xml`, other sdks like Python are
+/// skipped.
+///
+/// ```xml
+/// <jdk version="2">
+///   <name value="corretto-17" />
+///   <type value="JavaSDK" />
+///   <homePath value="$USER_HOME$/.jdks/corretto-17.0.9" />
+/// </jdk>
+/// ```
+pub fn parse_jdk_table(content: &str) -> Result<Vec<IdeJdkEntry>, AppError> {
+    let doc = roxmltree::Document::parse(content)
+        .map_err(|e| AppError::new(format!("Invalid jdk.table.xml: {}", e)))?;
+    let entries = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("jdk"))
+        .filter(|node| child_value(node, "type") == Some("JavaSDK"))
+        .filter_map(|node| {
+            let name = child_value(&node, "name")?;
+            let home = child_value(&node, "homePath")?;
+            Some(IdeJdkEntry {
+                name: name.to_string(),
+                home: expand_macros(home),
+            })
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Add the jdks to `jdk.table.xml`, jdks whose homes are defined already
+/// are skipped and existing entries are kept as is.
+pub fn merge_jdk_table(
+    content: &str,
+    jdks: &[Jdk],
+) -> Result<MergedConfig, AppError> {
+    let existing = if content.trim().is_empty() {
+        vec![]
+    } else {
+        parse_jdk_table(content)?
+    };
+    let mut names: Vec<String> =
+        existing.iter().map(|entry| entry.name.clone()).collect();
+    let mut merged = MergedConfig::default();
+    let mut entries = vec![];
+    for jdk in jdks {
+        let home = jdk.home_dir();
+        if existing.iter().any(|entry| same_home(&entry.home, &home)) {
+            merged.skipped.push(jdk.path.clone());
+            continue;
+        }
+        let name = unique_name(
+            &format!("{}-{}", jdk.distribution.id(), jdk.version),
+            &names,
+        );
+        entries.push(jdk_entry_xml(&name, jdk));
+        names.push(name.clone());
+        merged.added.push(name);
+    }
+    let entries = entries.join("\n");
+
+    if content.trim().is_empty() {
+        merged.content = format!(
+            "<application>\n  <component name=\"{}\">\n{}\n  \
+             </component>\n</application>\n",
+            COMPONENT_NAME, entries
+        );
+        return Ok(merged);
+    }
+    if entries.is_empty() {
+        merged.content = content.to_string();
+        return Ok(merged);
+    }
+
+    let doc = roxmltree::Document::parse(content)
+        .map_err(|e| AppError::new(format!("Invalid jdk.table.xml: {}", e)))?;
+    let component = doc.descendants().find(|node| {
+        node.has_tag_name("component")
+            && node.attribute("name") == Some(COMPONENT_NAME)
+    });
+    merged.content = match component {
+        Some(component) => {
+            let range = component.range();
+            match content[..range.end].rfind("</component>") {
+                Some(close) if close > range.start => {
+                    let line_start =
+                        content[..close].rfind('\n').map_or(0, |i| i + 1);
+                    format!(
+                        "{}{}\n{}",
+                        &content[..line_start],
+                        entries,
+                        &content[line_start..]
+                    )
+                }
+                // Self-closing component
+                _ => format!(
+                    "{}<component name=\"{}\">\n{}\n  </component>{}",
+                    &content[..range.start],
+                    COMPONENT_NAME,
+                    entries,
+                    &content[range.end..]
+                ),
+            }
+        }
+        None => {
+            let root = doc.root_element().range();
+            let close = content[..root.end]
+                .rfind("</")
+                .filter(|close| *close > root.start)
+                .ok_or_else(|| AppError::new("Invalid jdk.table.xml"))?;
+            format!(
+                "{}  <component name=\"{}\">\n{}\n  </component>\n{}",
+                &content[..close],
+                COMPONENT_NAME,
+                entries,
+                &content[close..]
+            )
+        }
+    };
+    Ok(merged)
+}
+
+/// IntelliJ fills the class roots when the sdk is loaded.
+fn jdk_entry_xml(name: &str, jdk: &Jdk) -> String {
+    let home = collapse_macros(&jdk.home_dir().display().to_string());
+    [
+        "    <jdk version=\"2\">".to_string(),
+        format!("      <name value=\"{}\" />", escape_attr(name)),
+        "      <type value=\"JavaSDK\" />".to_string(),
+        format!("      <version value=\"{}\" />", escape_attr(&jdk.version.to_string())),
+        format!("      <homePath value=\"{}\" />", escape_attr(&home)),
+        "      <roots>".to_string(),
+        "        <annotationsPath><root type=\"composite\" /></annotationsPath>".to_string(),
+        "        <classPath><root type=\"composite\" /></classPath>".to_string(),
+        "        <javadocPath><root type=\"composite\" /></javadocPath>".to_string(),
+        "        <sourcePath><root type=\"composite\" /></sourcePath>".to_string(),
+        "      </roots>".to_string(),
+        "      <additional />".to_string(),
+        "    </jdk>".to_string(),
+    ]
+    .join("\n")
+}
+
+fn unique_name(name: &str, names: &[String]) -> String {
+    let mut unique = name.to_string();
+    let mut index = 2;
+    while names.contains(&unique) {
+        unique = format!("{} ({})", name, index);
+        index += 1;
+    }
+    unique
+}
+
+fn escape_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn child_value<'a>(
+    node: &roxmltree::Node<'a, '_>,
+    tag: &str,
+) -> Option<&'a str> {
+    node.children()
+        .find(|child| child.has_tag_name(tag))
+        .and_then(|child| child.attribute("value"))
+}
+
+/// IntelliJ uses forward slashes and macros in paths.
+fn collapse_macros(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let Some(home) = user_home_dir() else {
+        return path;
+    };
+    let home = home.display().to_string().replace('\\', "/");
+    match path.strip_prefix(&home) {
+        Some(rest) if rest.starts_with('/') => {
+            format!("{}{}", USER_HOME_MACRO, rest)
+        }
+        _ => path,
+    }
+}
+
+fn expand_macros(path: &str) -> PathBuf {
+    match (path.strip_prefix(USER_HOME_MACRO), user_home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{merge_jdk_table, parse_jdk_table};
+    use crate::{
+        model::{distribution::Distribution, jdk::Jdk},
+        test_util,
+    };
+
+    #[test]
+    fn test_parse_jdk_table() {
+        let content = r#"
+            <application>
+              <component name="ProjectJdkTable">
+                <jdk version="2">
+                  <name value="17" />
+                  <type value="JavaSDK" />
+                  <version value="java version &quot;17.0.9&quot;" />
+                  <homePath value="C:/Program Files/Java/jdk-17" />
+                  <roots />
+                </jdk>
+                <jdk version="2">
+                  <name value="Python 3.12" />
+                  <type value="Python SDK" />
+                  <homePath value="C:/Python312/python.exe" />
+                </jdk>
+              </component>
+            </application>"#;
+        let entries = parse_jdk_table(content).unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!("17", entries[0].name);
+        assert_eq!(
+            PathBuf::from("C:/Program Files/Java/jdk-17"),
+            entries[0].home
+        );
+        assert!(parse_jdk_table("<application>").is_err());
+    }
+
+    #[test]
+    fn test_merge_jdk_table() {
+        let content = r#"<application>
+  <component name="ProjectJdkTable">
+    <jdk version="2">
+      <name value="17" />
+      <type value="JavaSDK" />
+      <homePath value="/opt/jdk-17" />
+    </jdk>
+  </component>
+</application>
+"#;
+        let jdk = |path: &str, version: &str| Jdk {
+            distribution: Distribution::Zulu,
+            ..test_util::jdk(path, version)
+        };
+        let jdks = [
+            jdk("/opt/jdk-17/bin", "17.0.9"),
+            jdk("/opt/jdk-21/bin", "21"),
+        ];
+        let merged = merge_jdk_table(content, &jdks).unwrap();
+        assert_eq!(vec!["zulu-21"], merged.added);
+        assert_eq!(vec!["/opt/jdk-17/bin"], merged.skipped);
+        let entries = parse_jdk_table(&merged.content).unwrap();
+        assert_eq!(2, entries.len());
+        assert_eq!("17", entries[0].name);
+        assert_eq!(PathBuf::from("/opt/jdk-21"), entries[1].home);
+        assert!(merged.content.ends_with("  </component>\n</application>\n"));
+
+        let created = merge_jdk_table("", &jdks).unwrap();
+        assert_eq!(2, parse_jdk_table(&created.content).unwrap().len());
+    }
+}
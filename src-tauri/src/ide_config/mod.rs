@@ -0,0 +1,178 @@
+pub mod eclipse;
+pub mod intellij;
+pub mod vscode;
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    errors::AppError,
+    jdk_finder::{find_java_exe, find_jdk_from_exe_path},
+    model::jdk::Jdk,
+};
+
+/// IDEs which keep a list of jdks in their config files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdeKind {
+    Intellij,
+    Eclipse,
+    VsCode,
+}
+
+/// A jdk defined in an IDE config file.
+#[derive(Debug, PartialEq)]
+pub struct IdeJdkEntry {
+    pub name: String,
+    pub home: PathBuf,
+}
+
+/// Result of importing jdks from IDE config files, items are jdk paths or
+/// the homes of invalid entries.
+#[derive(Default, serde::Serialize)]
+pub struct ImportReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub invalid: Vec<String>,
+}
+
+/// Content of a config file after merging jdks into it.
+#[derive(Default, serde::Serialize)]
+pub struct MergedConfig {
+    pub content: String,
+    /// Names of the added entries.
+    pub added: Vec<String>,
+    /// Paths of the jdks which are defined already.
+    pub skipped: Vec<String>,
+}
+
+/// Changes to be written to an IDE config file.
+#[derive(serde::Serialize)]
+pub struct ExportPreview {
+    pub path: String,
+    #[serde(flatten)]
+    pub merged: MergedConfig,
+}
+
+/// Config files of the IDE found on this machine.
+pub fn default_config_files(kind: IdeKind) -> Vec<PathBuf> {
+    match kind {
+        IdeKind::Intellij => intellij::default_config_files(),
+        IdeKind::Eclipse => eclipse::default_config_files(),
+        IdeKind::VsCode => vscode::default_config_files(),
+    }
+}
+
+pub fn read_entries(
+    kind: IdeKind,
+    path: &Path,
+) -> Result<Vec<IdeJdkEntry>, AppError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AppError::new(format!("Cannot read '{}': {}", path.display(), e))
+    })?;
+    match kind {
+        IdeKind::Intellij => intellij::parse_jdk_table(&content),
+        IdeKind::Eclipse => eclipse::parse_launching_prefs(&content),
+        IdeKind::VsCode => vscode::parse_runtimes(&content),
+    }
+}
+
+/// Validate the homes of the entries, jdks already in `saved` are reported
+/// as updated and keep their source.
+pub fn import_entries(
+    entries: &[IdeJdkEntry],
+    saved: &[Jdk],
+) -> (Vec<Jdk>, ImportReport) {
+    let mut jdks: Vec<Jdk> = vec![];
+    let mut report = ImportReport::default();
+    for entry in entries {
+        let probed = find_java_exe(&entry.home)
+            .ok_or_else(|| AppError::new("Java executable not found"))
+            .and_then(|java| find_jdk_from_exe_path(&java));
+        let mut jdk = match probed {
+            Ok(jdk) => jdk,
+            Err(e) => {
+                eprintln!(
+                    "Invalid jdk '{}' ({}): {}",
+                    entry.name,
+                    entry.home.display(),
+                    e.message
+                );
+                report.invalid.push(entry.home.display().to_string());
+                continue;
+            }
+        };
+        if jdks.iter().any(|item| item.path == jdk.path) {
+            // Defined by multiple IDEs or IDE versions
+            continue;
+        }
+        if let Some(prev) = saved.iter().find(|item| item.path == jdk.path) {
+            jdk.source = prev.source;
+            report.updated.push(jdk.path.clone());
+        } else {
+            report.added.push(jdk.path.clone());
+        }
+        jdks.push(jdk);
+    }
+    (jdks, report)
+}
+
+/// Merge the jdks into the config files of the IDE, nothing is written.
+pub fn preview_export(
+    kind: IdeKind,
+    jdks: &[Jdk],
+) -> Result<Vec<ExportPreview>, AppError> {
+    let files = default_config_files(kind);
+    if files.is_empty() {
+        return Err(AppError::new("No IDE config file found."));
+    }
+    let mut previews = vec![];
+    for path in files {
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            AppError::new(format!("Cannot read '{}': {}", path.display(), e))
+        })?;
+        let merged = match kind {
+            IdeKind::Intellij => intellij::merge_jdk_table(&content, jdks)?,
+            IdeKind::VsCode => vscode::merge_runtimes(&content, jdks)?,
+            IdeKind::Eclipse => {
+                return Err(AppError::new(
+                    "Exporting to Eclipse is not supported.",
+                ))
+            }
+        };
+        previews.push(ExportPreview {
+            path: path.display().to_string(),
+            merged,
+        });
+    }
+    Ok(previews)
+}
+
+/// Write the previews which add entries.
+pub fn write_previews(previews: &[ExportPreview]) -> Result<(), AppError> {
+    for preview in previews {
+        if preview.merged.added.is_empty() {
+            continue;
+        }
+        std::fs::write(&preview.path, &preview.merged.content).map_err(
+            |e| {
+                AppError::new(format!("Cannot write '{}': {}", preview.path, e))
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Compare jdk homes, ignoring the separators, trailing slashes and the
+/// case on Windows.
+fn same_home(a: &Path, b: &Path) -> bool {
+    let normalize = |path: &Path| {
+        let path = path.display().to_string().replace('\\', "/");
+        let path = path.trim_end_matches('/').to_string();
+        if cfg!(target_os = "windows") {
+            path.to_lowercase()
+        } else {
+            path
+        }
+    };
+    normalize(a) == normalize(b)
+}
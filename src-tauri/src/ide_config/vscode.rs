@@ -0,0 +1,370 @@
+use std::path::PathBuf;
+
+use crate::{errors::AppError, model::jdk::Jdk, util::paths::user_config_dir};
+
+use super::{same_home, IdeJdkEntry, MergedConfig};
+
+const RUNTIMES_KEY: &str = "java.configuration.runtimes";
+
+/// Settings keys of the jdk used to run the language server.
+const JAVA_HOME_KEYS: [&str; 2] = ["java.jdt.ls.java.home", "java.home"];
+
+/// User settings of VS Code and its variants.
+pub fn default_config_files() -> Vec<PathBuf> {
+    let Some(config_dir) = user_config_dir() else {
+        return vec![];
+    };
+    ["Code", "Code - Insiders", "VSCodium"]
+        .iter()
+        .map(|app| config_dir.join(app).join("User").join("settings.json"))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Parse the runtimes of the java extension from `settings.json`, which
+/// allows comments and trailing commas.
+///
+/// ```json
+/// "java.configuration.runtimes": [
+///   { "name": "JavaSE-17", "path": "C:\\Java\\jdk-17", "default": true }
+/// ]
+/// ```
+pub fn parse_runtimes(content: &str) -> Result<Vec<IdeJdkEntry>, AppError> {
+    let settings: serde_json::Value =
+        serde_json::from_str(&strip_jsonc(content)).map_err(|e| {
+            AppError::new(format!("Invalid settings.json: {}", e))
+        })?;
+    let mut entries: Vec<IdeJdkEntry> = settings
+        .get(RUNTIMES_KEY)
+        .and_then(|runtimes| runtimes.as_array())
+        .map(|runtimes| {
+            runtimes
+                .iter()
+                .filter_map(|runtime| {
+                    Some(IdeJdkEntry {
+                        name: runtime.get("name")?.as_str()?.to_string(),
+                        home: PathBuf::from(runtime.get("path")?.as_str()?),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    for key in JAVA_HOME_KEYS {
+        if let Some(home) = settings.get(key).and_then(|value| value.as_str()) {
+            entries.push(IdeJdkEntry {
+                name: key.to_string(),
+                home: PathBuf::from(home),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Add the jdks to `java.configuration.runtimes` of `settings.json`.
+///
+/// Runtime names are execution environments like `JavaSE-17` and must be
+/// unique, so only the first jdk of each feature version is added and
+/// environments defined by the user are kept. The content is edited in
+/// place to keep comments.
+pub fn merge_runtimes(
+    content: &str,
+    jdks: &[Jdk],
+) -> Result<MergedConfig, AppError> {
+    let existing: Vec<IdeJdkEntry> = if content.trim().is_empty() {
+        vec![]
+    } else {
+        parse_runtimes(content)?
+            .into_iter()
+            .filter(|entry| !JAVA_HOME_KEYS.contains(&entry.name.as_str()))
+            .collect()
+    };
+    let mut merged = MergedConfig::default();
+    let mut runtimes = vec![];
+    for jdk in jdks {
+        let name = execution_environment(jdk);
+        let home = jdk.home_dir();
+        let is_defined = existing
+            .iter()
+            .any(|entry| entry.name == name || same_home(&entry.home, &home));
+        if is_defined || merged.added.contains(&name) {
+            merged.skipped.push(jdk.path.clone());
+            continue;
+        }
+        runtimes.push(format!(
+            "{{ \"name\": {}, \"path\": {} }}",
+            serde_json::to_string(&name)?,
+            serde_json::to_string(&home.display().to_string())?
+        ));
+        merged.added.push(name);
+    }
+
+    if content.trim().is_empty() {
+        merged.content = format!(
+            "{{\n    \"{}\": [\n        {}\n    ]\n}}\n",
+            RUNTIMES_KEY,
+            runtimes.join(",\n        ")
+        );
+        return Ok(merged);
+    }
+    if runtimes.is_empty() {
+        merged.content = content.to_string();
+        return Ok(merged);
+    }
+
+    let tokens = tokenize(content);
+    let invalid = || AppError::new("Invalid settings.json");
+    merged.content = match find_top_level_array(&tokens, RUNTIMES_KEY) {
+        Some((open, close)) => {
+            let has_items = tokens
+                .iter()
+                .any(|token| token.start > open && token.start < close);
+            format!(
+                "{}\n        {}{}{}",
+                &content[..open + 1],
+                runtimes.join(",\n        "),
+                if has_items { "," } else { "\n    " },
+                &content[open + 1..]
+            )
+        }
+        None => {
+            let root = tokens.first().filter(|token| token.text == "{");
+            let root = root.ok_or_else(invalid)?;
+            let has_members =
+                tokens.get(1).is_some_and(|token| token.text != "}");
+            format!(
+                "{}\n    \"{}\": [\n        {}\n    ]{}{}",
+                &content[..root.start + 1],
+                RUNTIMES_KEY,
+                runtimes.join(",\n        "),
+                if has_members { "," } else { "\n" },
+                &content[root.start + 1..]
+            )
+        }
+    };
+    Ok(merged)
+}
+
+/// `J2SE-1.5` for java 5 and older, `JavaSE-1.8` for java 6 to 8 and
+/// `JavaSE-17` for newer versions.
+fn execution_environment(jdk: &Jdk) -> String {
+    match jdk.version.feature {
+        ..=5 => format!("J2SE-1.{}", jdk.version.feature),
+        6..=8 => format!("JavaSE-1.{}", jdk.version.feature),
+        _ => format!("JavaSE-{}", jdk.version.feature),
+    }
+}
+
+/// A significant token of JSON with comments, strings are single tokens
+/// including the quotes.
+struct Token<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+fn tokenize(content: &str) -> Vec<Token<'_>> {
+    let bytes = content.as_bytes();
+    let mut tokens = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        let start = index;
+        match bytes[index] {
+            b'"' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    index += if bytes[index] == b'\\' { 2 } else { 1 };
+                }
+                index = (index + 1).min(bytes.len());
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index = content[index + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| index + 2 + end + 2);
+                continue;
+            }
+            c if c.is_ascii_whitespace() => {
+                index += 1;
+                continue;
+            }
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => index += 1,
+            _ => {
+                while index < bytes.len()
+                    && !b"{}[]:,\" \t\r\n/".contains(&bytes[index])
+                {
+                    index += 1;
+                }
+            }
+        }
+        tokens.push(Token {
+            start,
+            text: &content[start..index],
+        });
+    }
+    tokens
+}
+
+/// Find the positions of the brackets of an array value of the root object.
+fn find_top_level_array(tokens: &[Token], key: &str) -> Option<(usize, usize)> {
+    let quoted_key = serde_json::to_string(key).ok()?;
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.text {
+            "{" | "[" => depth += 1,
+            "}" | "]" => depth -= 1,
+            text if depth == 1 && text == quoted_key => {
+                let is_key =
+                    tokens.get(index + 1).is_some_and(|next| next.text == ":");
+                let open = tokens.get(index + 2)?;
+                if !is_key || open.text != "[" {
+                    continue;
+                }
+                let mut array_depth = 0;
+                for next in &tokens[index + 2..] {
+                    match next.text {
+                        "{" | "[" => array_depth += 1,
+                        "}" | "]" => array_depth -= 1,
+                        _ => {}
+                    }
+                    if array_depth == 0 {
+                        return Some((open.start, next.start));
+                    }
+                }
+                return None;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Remove comments and trailing commas of JSON with comments.
+pub fn strip_jsonc(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            if c == '\\' {
+                result.extend(chars.next());
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                result.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|next| *next != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            (']' | '}', _) => {
+                // Drop the trailing comma
+                let trimmed = result.trim_end().len();
+                if result[..trimmed].ends_with(',') {
+                    result.truncate(trimmed - 1);
+                }
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{execution_environment, merge_runtimes, parse_runtimes};
+    use crate::test_util::jdk;
+
+    #[test]
+    fn test_parse_runtimes() {
+        let content = r#"{
+            // Runtimes of the java extension
+            "editor.fontSize": 14, /* "java.home": "C:\\Old" */
+            "java.configuration.runtimes": [
+                {
+                    "name": "JavaSE-17",
+                    "path": "C:\\Program Files\\Java\\jdk-17",
+                },
+                { "name": "JavaSE-21", "path": "/opt/jdk-21//", },
+            ],
+            "java.jdt.ls.java.home": "/opt/jdk-21",
+        }"#;
+        let entries = parse_runtimes(content).unwrap();
+        assert_eq!(3, entries.len());
+        assert_eq!("JavaSE-17", entries[0].name);
+        assert_eq!(
+            PathBuf::from("C:\\Program Files\\Java\\jdk-17"),
+            entries[0].home
+        );
+        assert_eq!(PathBuf::from("/opt/jdk-21//"), entries[1].home);
+        assert_eq!("java.jdt.ls.java.home", entries[2].name);
+    }
+
+    #[test]
+    fn test_merge_runtimes() {
+        let jdks = [
+            jdk("/opt/jdk-21/bin", "21.0.1"),
+            jdk("/opt/jdk-17.0.9/bin", "17.0.9"),
+            jdk("/opt/jdk-17.0.8/bin", "17.0.8"),
+            jdk("/opt/jdk-8/bin", "1.8.0_392"),
+        ];
+        let content = r#"{
+    // My runtimes
+    "java.configuration.runtimes": [
+        { "name": "JavaSE-1.8", "path": "/usr/lib/jvm/java-8" }, // old
+    ],
+    "editor.fontSize": 14
+}"#;
+        let merged = merge_runtimes(content, &jdks).unwrap();
+        assert_eq!(vec!["JavaSE-21", "JavaSE-17"], merged.added);
+        assert_eq!(2, merged.skipped.len());
+        assert!(merged.content.contains("// My runtimes"));
+        assert!(merged.content.contains("// old"));
+        let entries = parse_runtimes(&merged.content).unwrap();
+        let names: Vec<&str> =
+            entries.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(vec!["JavaSE-21", "JavaSE-17", "JavaSE-1.8"], names);
+        assert_eq!(PathBuf::from("/opt/jdk-17.0.9"), entries[1].home);
+
+        let content = "{\n    \"editor.fontSize\": 14\n}\n";
+        let merged = merge_runtimes(content, &jdks[..1]).unwrap();
+        let entries = parse_runtimes(&merged.content).unwrap();
+        assert_eq!(1, entries.len());
+        assert!(merged.content.contains("\"editor.fontSize\": 14"));
+
+        let merged = merge_runtimes("{}", &jdks[..1]).unwrap();
+        assert_eq!(1, parse_runtimes(&merged.content).unwrap().len());
+    }
+
+    #[test]
+    fn test_execution_environment() {
+        let name = |version: &str| {
+            execution_environment(&jdk("/opt/jdk/bin", version))
+        };
+        assert_eq!("J2SE-1.4", name("1.4.2_19"));
+        assert_eq!("J2SE-1.5", name("1.5.0_22"));
+        assert_eq!("JavaSE-1.8", name("1.8.0_392"));
+        assert_eq!("JavaSE-21", name("21.0.1"));
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use crate::{errors::AppError, model::arch::Arch};
+
+/// A source of installable jdk packages, e.g. the Disco API or an index
+/// published to a mirror.
+pub trait JdkCatalog: Send + Sync {
+    /// Identifies the catalog, packages keep it to be installed from the
+    /// same catalog.
+    fn id(&self) -> &str;
+
+    fn distributions(&self) -> Result<Vec<CatalogDistribution>, AppError>;
+
+    fn major_versions(&self) -> Result<Vec<CatalogMajorVersion>, AppError>;
+
+    fn packages(
+        &self,
+        query: &PackageQuery,
+    ) -> Result<Vec<CatalogPackage>, AppError>;
+
+    /// Find where to download the package and its checksum.
+    fn resolve(
+        &self,
+        package: &CatalogPackage,
+    ) -> Result<PackageDownload, AppError>;
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CatalogDistribution {
+    pub name: String,
+    /// The id used in queries, e.g. `temurin`.
+    pub api_parameter: String,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CatalogMajorVersion {
+    pub major_version: u32,
+    /// `LTS`, `MTS` or `STS`, empty if unknown.
+    #[serde(default)]
+    pub term_of_support: String,
+    #[serde(default)]
+    pub maintained: bool,
+}
+
+/// A downloadable package of a jdk build.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CatalogPackage {
+    pub id: String,
+    pub archive_type: String,
+    pub distribution: String,
+    pub major_version: u32,
+    pub java_version: String,
+    pub distribution_version: String,
+    pub operating_system: String,
+    pub architecture: String,
+    pub package_type: String,
+    pub filename: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub term_of_support: String,
+    /// The id of the catalog, see [JdkCatalog::id].
+    #[serde(default)]
+    pub catalog: String,
+}
+
+/// Where to get a package.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PackageLocation {
+    Url(String),
+    /// A local or shared file, used without copying.
+    File(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+pub struct PackageDownload {
+    pub location: PackageLocation,
+    /// The lowercase hex sha256 digest.
+    pub sha256: String,
+    /// A detached minisign or GPG signature of the package.
+    pub signature: Option<PackageLocation>,
+}
+
+/// Filters of the package search, the operating system and architecture
+/// default to the host.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct PackageQuery {
+    pub distribution: Option<String>,
+    /// A version or a major version, e.g. `21` or `17.0.9`.
+    pub version: Option<String>,
+    #[serde(default = "host_os")]
+    pub operating_system: String,
+    #[serde(default = "host_arch")]
+    pub architecture: String,
+    /// Only the latest build of each version.
+    #[serde(default)]
+    pub latest: bool,
+}
+
+pub fn host_os() -> String {
+    match std::env::consts::OS {
+        "macos" => "macos",
+        "windows" => "windows",
+        _ => "linux",
+    }
+    .to_string()
+}
+
+/// The architecture names used by the Disco API.
+pub fn host_arch() -> String {
+    match Arch::host() {
+        Arch::X86 => "x86",
+        Arch::Arm => "arm",
+        Arch::Arm64 => "aarch64",
+        Arch::Ppc64 => "ppc64le",
+        Arch::S390x => "s390x",
+        Arch::RiscV64 => "riscv64",
+        Arch::X64 | Arch::Unknown => "x64",
+    }
+    .to_string()
+}
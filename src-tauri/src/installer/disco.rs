@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use crate::errors::AppError;
+
+use super::catalog::{
+    CatalogDistribution, CatalogMajorVersion, CatalogPackage, JdkCatalog,
+    PackageDownload, PackageLocation, PackageQuery,
+};
+
+/// The foojay Disco API, see https://api.foojay.io/swagger-ui
+pub const DISCO_API_URL: &str = "https://api.foojay.io/disco/v3.0";
+
+/// Archive types the installer can extract.
+const ARCHIVE_TYPES: [&str; 3] = ["zip", "tar.gz", "tgz"];
+
+#[derive(serde::Deserialize)]
+struct DiscoResponse<T> {
+    result: Vec<T>,
+}
+
+/// Download links and checksum of a package.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PackageInfo {
+    pub filename: String,
+    pub direct_download_uri: String,
+    #[serde(default)]
+    pub checksum: String,
+    #[serde(default)]
+    pub checksum_type: String,
+    #[serde(default)]
+    pub checksum_uri: String,
+    #[serde(default)]
+    pub signature_uri: String,
+}
+
+fn packages_query_string(query: &PackageQuery) -> String {
+    let mut params = vec![
+        ("operating_system", query.operating_system.as_str()),
+        ("architecture", query.architecture.as_str()),
+        ("lib_c_type", host_libc()),
+        ("package_type", "jdk"),
+        ("release_status", "ga"),
+        ("javafx_bundled", "false"),
+        ("directly_downloadable", "true"),
+    ];
+    if let Some(distribution) = &query.distribution {
+        params.push(("distribution", distribution));
+    }
+    if let Some(version) = &query.version {
+        params.push(("version", version));
+    }
+    if query.latest {
+        params.push(("latest", "available"));
+    }
+    for archive_type in ARCHIVE_TYPES {
+        params.push(("archive_type", archive_type));
+    }
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, encode(value)))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+fn host_libc() -> &'static str {
+    match std::env::consts::OS {
+        "windows" => "c_std_lib",
+        "macos" => "libc",
+        _ => "glibc",
+    }
+}
+
+/// Percent-encode a query value, only unreserved chars are kept.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// A blocking client of the Disco API.
+pub struct DiscoCatalog {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl DiscoCatalog {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            agent: http_agent(),
+        }
+    }
+
+    pub fn package_info(&self, id: &str) -> Result<PackageInfo, AppError> {
+        self.get::<PackageInfo>(&format!("ids/{}", encode(id)))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::new(format!("Package '{}' not found", id)))
+    }
+
+    /// Fetch a text file, e.g. a published checksum.
+    pub fn get_text(&self, url: &str) -> Result<String, AppError> {
+        self.agent
+            .get(url)
+            .call()
+            .map_err(|e| request_error(url, e))?
+            .into_string()
+            .map_err(|e| AppError::new(format!("Cannot read '{}': {}", url, e)))
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Vec<T>, AppError> {
+        let url = format!("{}/{}", self.base_url, path);
+        let response: DiscoResponse<T> = self
+            .agent
+            .get(&url)
+            .call()
+            .map_err(|e| request_error(&url, e))?
+            .into_json()
+            .map_err(|e| {
+                AppError::new(format!("Invalid response of '{}': {}", url, e))
+            })?;
+        Ok(response.result)
+    }
+}
+
+impl JdkCatalog for DiscoCatalog {
+    fn id(&self) -> &str {
+        &self.base_url
+    }
+
+    fn distributions(&self) -> Result<Vec<CatalogDistribution>, AppError> {
+        self.get("distributions?include_versions=false&include_synonyms=false")
+    }
+
+    fn major_versions(&self) -> Result<Vec<CatalogMajorVersion>, AppError> {
+        self.get("major_versions?ea=false&ga=true&include_versions=false")
+    }
+
+    fn packages(
+        &self,
+        query: &PackageQuery,
+    ) -> Result<Vec<CatalogPackage>, AppError> {
+        let mut packages: Vec<CatalogPackage> =
+            self.get(&format!("packages?{}", packages_query_string(query)))?;
+        for package in packages.iter_mut() {
+            package.catalog = self.base_url.clone();
+        }
+        Ok(packages)
+    }
+
+    /// The checksum is published in the package info, or in a file linked
+    /// by it.
+    fn resolve(
+        &self,
+        package: &CatalogPackage,
+    ) -> Result<PackageDownload, AppError> {
+        let info = self.package_info(&package.id)?;
+        let checksum = if !info.checksum.is_empty() {
+            info.checksum.clone()
+        } else if !info.checksum_uri.is_empty() {
+            self.get_text(&info.checksum_uri)?
+        } else {
+            String::new()
+        };
+        let is_sha256 = info.checksum_type.is_empty()
+            || info.checksum_type.eq_ignore_ascii_case("sha256");
+        if checksum.trim().is_empty() || !is_sha256 {
+            return Err(AppError::new(format!(
+                "No sha256 checksum is published for '{}'",
+                package.filename
+            )));
+        }
+        let signature = (!info.signature_uri.is_empty())
+            .then(|| PackageLocation::Url(info.signature_uri.clone()));
+        Ok(PackageDownload {
+            location: PackageLocation::Url(info.direct_download_uri),
+            sha256: checksum,
+            signature,
+        })
+    }
+}
+
+pub fn http_agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(15))
+        .timeout_read(Duration::from_secs(60))
+        .user_agent(concat!("jdk-switcher/", env!("CARGO_PKG_VERSION")))
+        .build()
+}
+
+pub fn request_error(url: &str, error: ureq::Error) -> AppError {
+    match error {
+        ureq::Error::Status(code, _) => {
+            AppError::new(format!("Request '{}' failed with {}", url, code))
+        }
+        ureq::Error::Transport(e) => {
+            AppError::new(format!("Request '{}' failed: {}", url, e))
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    fs::OpenOptions,
+    io::{Read, Write},
+    path::Path,
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{errors::AppError, util::cancellation_token::CancellationToken};
+
+use super::disco::request_error;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Report the progress after this many bytes.
+const PROGRESS_STEP: u64 = 512 * 1024;
+
+/// Download a file, a partially downloaded file is resumed with a range
+/// request, it is downloaded again if the server does not support ranges.
+///
+/// `on_progress` is called with the downloaded bytes and the total size if
+/// known.
+pub fn download_file<F>(
+    agent: &ureq::Agent,
+    url: &str,
+    path: &Path,
+    token: &CancellationToken,
+    mut on_progress: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(u64, Option<u64>),
+{
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let existing = std::fs::metadata(path).map_or(0, |item| item.len());
+    let mut request = agent.get(url);
+    if existing > 0 {
+        request = request.set("Range", &format!("bytes={}-", existing));
+    }
+    let response = match request.call() {
+        Ok(response) => response,
+        // The file is complete already
+        Err(ureq::Error::Status(416, _)) if existing > 0 => {
+            on_progress(existing, Some(existing));
+            return Ok(());
+        }
+        Err(e) => return Err(request_error(url, e)),
+    };
+
+    let is_resumed = response.status() == 206;
+    let content_length = response
+        .header("Content-Length")
+        .and_then(|value| value.parse::<u64>().ok());
+    let mut downloaded = if is_resumed { existing } else { 0 };
+    let total = content_length.map(|length| downloaded + length);
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(is_resumed)
+        .truncate(!is_resumed)
+        .open(path)?;
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut reported = downloaded;
+    on_progress(downloaded, total);
+    loop {
+        if token.is_cancelled() {
+            return Err(AppError::new("Download cancelled"));
+        }
+        let count = reader.read(&mut buffer).map_err(|e| {
+            AppError::new(format!("Download of '{}' failed: {}", url, e))
+        })?;
+        if count == 0 {
+            break;
+        }
+        file.write_all(&buffer[..count])?;
+        downloaded += count as u64;
+        if downloaded - reported >= PROGRESS_STEP {
+            reported = downloaded;
+            on_progress(downloaded, total);
+        }
+    }
+    file.flush()?;
+    if total.is_some_and(|total| downloaded < total) {
+        return Err(AppError::new(format!(
+            "Download of '{}' is incomplete, try again to resume",
+            url
+        )));
+    }
+    on_progress(downloaded, total);
+    Ok(())
+}
+
+/// The lowercase hex sha256 digest of a file.
+pub fn sha256_file(path: &Path) -> Result<String, AppError> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    loop {
+        let count = file.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Check the sha256 digest of a file and return it, published checksum
+/// files may have a file name after the digest.
+pub fn verify_sha256(path: &Path, expected: &str) -> Result<String, AppError> {
+    let expected = expected
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        return Err(AppError::new(format!(
+            "Checksum mismatch of '{}', expected {}, got {}",
+            path.display(),
+            expected,
+            actual
+        )));
+    }
+    Ok(actual)
+}
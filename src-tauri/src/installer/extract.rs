@@ -0,0 +1,388 @@
+use std::{
+    fs::File,
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use xz2::read::XzDecoder;
+
+use crate::errors::AppError;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArchiveType {
+    Zip,
+    TarGz,
+    TarXz,
+}
+
+impl ArchiveType {
+    /// Find the type by the file name or the Disco API archive type.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.ends_with("zip") {
+            Some(ArchiveType::Zip)
+        } else if name.ends_with("tar.gz") || name.ends_with("tgz") {
+            Some(ArchiveType::TarGz)
+        } else if name.ends_with("tar.xz") || name.ends_with("txz") {
+            Some(ArchiveType::TarXz)
+        } else {
+            None
+        }
+    }
+
+    /// The Disco API archive type, e.g. `tar.gz`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ArchiveType::Zip => "zip",
+            ArchiveType::TarGz => "tar.gz",
+            ArchiveType::TarXz => "tar.xz",
+        }
+    }
+}
+
+/// Extract a jdk archive into the dest dir. The archive is extracted to a
+/// temporary dir first, if it has a single top level dir (e.g.
+/// `jdk-21.0.1+12/`), the content of that dir becomes the dest dir.
+///
+/// Entries which would be written outside the dest dir are rejected.
+pub fn extract_archive(
+    archive: &Path,
+    archive_type: ArchiveType,
+    dest: &Path,
+) -> Result<(), AppError> {
+    if dest.exists() {
+        return Err(AppError::new(format!(
+            "'{}' exists already",
+            dest.display()
+        )));
+    }
+    let Some(dest_name) = dest.file_name() else {
+        return Err(AppError::new("Invalid extract dir"));
+    };
+    let temp_dir = dest
+        .with_file_name(format!(".{}.partial", dest_name.to_string_lossy()));
+    if temp_dir.exists() {
+        std::fs::remove_dir_all(&temp_dir)?;
+    }
+    std::fs::create_dir_all(&temp_dir)?;
+
+    let ret = match archive_type {
+        ArchiveType::Zip => extract_zip(archive, &temp_dir),
+        ArchiveType::TarGz => {
+            let file = File::open(archive)?;
+            extract_tar(GzDecoder::new(file), &temp_dir)
+        }
+        ArchiveType::TarXz => {
+            let file = File::open(archive)?;
+            extract_tar(XzDecoder::new(file), &temp_dir)
+        }
+    }
+    .and_then(|_| {
+        let root = single_top_level_dir(&temp_dir)?.unwrap_or(temp_dir.clone());
+        std::fs::rename(&root, dest)?;
+        Ok(())
+    });
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    ret
+}
+
+fn single_top_level_dir(dir: &Path) -> Result<Option<PathBuf>, AppError> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|item| item.ok())
+        .map(|item| item.path())
+        .collect();
+    if entries.len() == 1 && entries[0].is_dir() {
+        Ok(Some(entries[0].clone()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Convert an entry path to a path inside the dest dir, `..`, absolute
+/// paths and drive prefixes are rejected. Returns `None` for the root.
+fn safe_relative_path(path: &Path) -> Result<Option<PathBuf>, AppError> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => relative.push(name),
+            Component::CurDir => {}
+            Component::ParentDir
+            | Component::RootDir
+            | Component::Prefix(_) => {
+                return Err(AppError::new(format!(
+                    "Unsafe path '{}' in the archive",
+                    path.display()
+                )));
+            }
+        }
+    }
+    Ok((!relative.as_os_str().is_empty()).then_some(relative))
+}
+
+/// Check that a link target, relative to the dir of the link, does not
+/// point outside the dest dir.
+fn check_link_target(link: &Path, target: &Path) -> Result<(), AppError> {
+    let mut depth = link.components().count() as isize - 1;
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => depth -= 1,
+            Component::RootDir | Component::Prefix(_) => depth = -1,
+        }
+        if depth < 0 {
+            return Err(AppError::new(format!(
+                "Unsafe link '{}' -> '{}' in the archive",
+                link.display(),
+                target.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check that no existing part of a path inside the dest dir is a symlink,
+/// links extracted before could otherwise redirect it outside the dest dir.
+fn check_no_symlinks(dest: &Path, relative: &Path) -> Result<(), AppError> {
+    let mut path = dest.to_path_buf();
+    for component in relative.components() {
+        path.push(component);
+        let is_symlink = std::fs::symlink_metadata(&path)
+            .is_ok_and(|item| item.file_type().is_symlink());
+        if is_symlink {
+            return Err(AppError::new(format!(
+                "Unsafe path '{}' through a link in the archive",
+                relative.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn extract_zip(archive: &Path, dest: &Path) -> Result<(), AppError> {
+    let invalid = |e: zip::result::ZipError| {
+        AppError::new(format!("Invalid archive '{}': {}", archive.display(), e))
+    };
+    let mut zip =
+        zip::ZipArchive::new(File::open(archive)?).map_err(invalid)?;
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index).map_err(invalid)?;
+        let Some(relative) = safe_relative_path(Path::new(entry.name()))?
+        else {
+            continue;
+        };
+        check_no_symlinks(dest, &relative)?;
+        let path = dest.join(&relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut entry, &mut File::create(&path)?)?;
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = std::fs::Permissions::from_mode(mode & 0o777);
+            std::fs::set_permissions(&path, permissions)?;
+        }
+    }
+    Ok(())
+}
+
+fn extract_tar<R: std::io::Read>(
+    reader: R,
+    dest: &Path,
+) -> Result<(), AppError> {
+    let mut tar = tar::Archive::new(reader);
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let Some(relative) = safe_relative_path(&entry.path()?)? else {
+            continue;
+        };
+        check_no_symlinks(dest, &relative)?;
+        let path = dest.join(&relative);
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            std::fs::create_dir_all(&path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if entry_type.is_file() {
+            // Permissions including the executable bits are kept
+            entry.unpack(&path)?;
+        } else if entry_type.is_symlink() {
+            let Some(target) = entry.link_name()? else {
+                continue;
+            };
+            check_link_target(&relative, &target)?;
+            // Links are skipped where they need extra privileges
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&target, &path)?;
+        } else if entry_type.is_hard_link() {
+            let Some(target) = entry.link_name()? else {
+                continue;
+            };
+            // Hard link targets are relative to the archive root
+            let Some(target) = safe_relative_path(&target)? else {
+                continue;
+            };
+            check_no_symlinks(dest, &target)?;
+            std::fs::copy(dest.join(target), &path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Write, path::Path};
+
+    use super::{extract_archive, ArchiveType};
+    use crate::test_util::temp_dir;
+
+    /// Build a tar with raw entry names, the tar builder rejects unsafe
+    /// paths itself.
+    fn tar_entries(entries: &[(&str, u32, &[u8])]) -> Vec<u8> {
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|(name, mode, data)| (*name, *mode, None, *data))
+            .collect();
+        tar_links(&entries)
+    }
+
+    /// Build a tar with raw entry names, entries with a link name are
+    /// symlinks.
+    fn tar_links(entries: &[(&str, u32, Option<&str>, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(vec![]);
+        for (name, mode, link, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            let gnu = header.as_gnu_mut().unwrap();
+            gnu.name[..name.len()].copy_from_slice(name.as_bytes());
+            if let Some(link) = link {
+                gnu.linkname[..link.len()].copy_from_slice(link.as_bytes());
+                header.set_entry_type(tar::EntryType::Symlink);
+            }
+            header.set_mode(*mode);
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            builder.append(&header, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn xz(data: &[u8], path: &Path) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut encoder = xz2::write::XzEncoder::new(file, 6);
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    #[test]
+    fn test_reject_zip_traversal() {
+        let dir = temp_dir("zip-traversal");
+        let archive = dir.join("evil.zip");
+        let mut zip =
+            zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+        let options = zip::write::FileOptions::default();
+        zip.start_file("jdk/bin/java", options).unwrap();
+        zip.start_file("../evil.txt", options).unwrap();
+        zip.write_all(b"evil").unwrap();
+        zip.finish().unwrap();
+
+        let dest = dir.join("jdks").join("jdk");
+        let ret = extract_archive(&archive, ArchiveType::Zip, &dest);
+        let escaped = dir.join("jdks").join("evil.txt").exists()
+            || dir.join("evil.txt").exists();
+        let dest_exists = dest.exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(ret.is_err());
+        assert!(!escaped);
+        assert!(!dest_exists);
+    }
+
+    #[test]
+    fn test_reject_absolute_tar_entry() {
+        let dir = temp_dir("tar-absolute");
+        let archive = dir.join("evil.tar.xz");
+        let evil = dir.join("evil.txt");
+        let evil_name = evil.to_string_lossy().to_string();
+        xz(
+            &tar_entries(&[
+                ("jdk/bin/java", 0o755, b""),
+                (&evil_name, 0o644, b"evil"),
+            ]),
+            &archive,
+        );
+
+        let dest = dir.join("jdks").join("jdk");
+        let ret = extract_archive(&archive, ArchiveType::TarXz, &dest);
+        let escaped = evil.exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(ret.is_err());
+        assert!(!escaped);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_reject_tar_link_traversal() {
+        let dir = temp_dir("tar-link");
+        let archive = dir.join("evil.tar.xz");
+        // Each link stays inside the dest dir on its own, together they
+        // point outside of it
+        xz(
+            &tar_links(&[
+                ("a", 0o777, Some("."), b""),
+                ("a/a/a/b", 0o777, Some("../../.."), b""),
+                ("b/evil.txt", 0o644, None, b"evil"),
+            ]),
+            &archive,
+        );
+
+        let dest = dir.join("x").join("jdks").join("jdk");
+        let ret = extract_archive(&archive, ArchiveType::TarXz, &dest);
+        let escaped = dir.join("evil.txt").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(ret.is_err());
+        assert!(!escaped);
+    }
+
+    #[test]
+    fn test_extract_tar_xz() {
+        let dir = temp_dir("tar-xz");
+        let archive = dir.join("OpenJDK21U-jdk_x64_linux_hotspot.tar.xz");
+        xz(
+            &tar_entries(&[
+                ("jdk-21.0.1+12/bin/java", 0o755, b"#!/bin/sh\n"),
+                ("jdk-21.0.1+12/release", 0o644, b"JAVA_VERSION=\"21\"\n"),
+            ]),
+            &archive,
+        );
+        assert_eq!(
+            Some(ArchiveType::TarXz),
+            ArchiveType::from_name(&archive.to_string_lossy())
+        );
+
+        let dest = dir.join("jdks").join("jdk-21");
+        let ret = extract_archive(&archive, ArchiveType::TarXz, &dest);
+        let java = dest.join("bin").join("java");
+        let java_exists = java.is_file();
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::metadata(&java).map(|item| item.permissions().mode())
+        };
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(ret.is_ok());
+        assert!(java_exists);
+        #[cfg(unix)]
+        assert_eq!(0o755, mode.unwrap() & 0o777);
+    }
+}
@@ -0,0 +1,492 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    errors::AppError,
+    model::{
+        distribution::Distribution, java_version::JavaVersion,
+        version_constraint::VersionConstraint,
+    },
+};
+
+use super::{
+    catalog::{
+        CatalogDistribution, CatalogMajorVersion, CatalogPackage, JdkCatalog,
+        PackageDownload, PackageLocation, PackageQuery,
+    },
+    disco::request_error,
+    extract::ArchiveType,
+};
+
+/// An index of jdk packages published to a mirror or a shared dir.
+///
+/// ```json
+/// {
+///   "packages": [
+///     {
+///       "distribution": "temurin",
+///       "version": "21.0.1+12",
+///       "operating_system": "linux",
+///       "architecture": "x64",
+///       "path": "temurin/OpenJDK21U-jdk_x64_linux_hotspot_21.0.1_12.tar.gz",
+///       "sha256": "..."
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Packages have either a `url`, or a `path` relative to the index. A
+/// detached minisign or GPG `signature` is an url or a relative path too.
+#[derive(serde::Deserialize)]
+struct CatalogIndex {
+    packages: Vec<IndexEntry>,
+}
+
+#[derive(Clone, serde::Deserialize)]
+struct IndexEntry {
+    distribution: String,
+    version: String,
+    operating_system: String,
+    architecture: String,
+    /// Found from the file name if not set.
+    #[serde(default)]
+    archive_type: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    sha256: String,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    size: u64,
+}
+
+impl IndexEntry {
+    fn location(&self) -> &str {
+        self.url.as_deref().or(self.path.as_deref()).unwrap_or("")
+    }
+}
+
+/// Where relative paths of the index are resolved from.
+enum IndexBase {
+    /// The url of the index dir, ends with a `/`.
+    Url(String),
+    Dir(PathBuf),
+}
+
+pub struct IndexCatalog {
+    source: String,
+    base: IndexBase,
+    entries: Vec<IndexEntry>,
+}
+
+impl IndexCatalog {
+    /// Load an index from a `http://`, `https://` or `file://` url, or a
+    /// plain path.
+    pub fn load(source: &str, agent: &ureq::Agent) -> Result<Self, AppError> {
+        let invalid = |e: serde_json::Error| {
+            AppError::new(format!("Invalid catalog index '{}': {}", source, e))
+        };
+        if source.starts_with("http://") || source.starts_with("https://") {
+            let index: CatalogIndex = agent
+                .get(source)
+                .call()
+                .map_err(|e| request_error(source, e))?
+                .into_json()
+                .map_err(|e| {
+                    AppError::new(format!(
+                        "Invalid catalog index '{}': {}",
+                        source, e
+                    ))
+                })?;
+            let dir_end = source.rfind('/').map_or(source.len(), |i| i + 1);
+            return Ok(Self {
+                source: source.to_string(),
+                base: IndexBase::Url(source[..dir_end].to_string()),
+                entries: index.packages,
+            });
+        }
+
+        let path = file_url_to_path(source);
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            AppError::new(format!("Cannot read '{}': {}", path.display(), e))
+        })?;
+        let index: CatalogIndex =
+            serde_json::from_str(&content).map_err(invalid)?;
+        let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        Ok(Self {
+            source: source.to_string(),
+            base: IndexBase::Dir(dir),
+            entries: index.packages,
+        })
+    }
+
+    fn to_package(&self, entry: &IndexEntry) -> Option<CatalogPackage> {
+        let version = entry.version.parse::<JavaVersion>().ok()?;
+        let location = entry.location();
+        let filename = location
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(location)
+            .to_string();
+        let archive_type = entry
+            .archive_type
+            .as_deref()
+            .and_then(ArchiveType::from_name)
+            .or_else(|| ArchiveType::from_name(&filename))
+            .map_or("", |item| item.id());
+        Some(CatalogPackage {
+            id: location.to_string(),
+            archive_type: archive_type.to_string(),
+            distribution: entry.distribution.clone(),
+            major_version: version.numbers()[0],
+            java_version: entry.version.clone(),
+            distribution_version: entry.version.clone(),
+            operating_system: entry.operating_system.clone(),
+            architecture: entry.architecture.clone(),
+            package_type: "jdk".to_string(),
+            filename,
+            size: entry.size,
+            term_of_support: String::new(),
+            catalog: self.source.clone(),
+        })
+    }
+
+    /// Resolve an url or a path relative to the index.
+    fn resolve_location(&self, reference: &str) -> PackageLocation {
+        if reference.starts_with("http://") || reference.starts_with("https://")
+        {
+            return PackageLocation::Url(reference.to_string());
+        }
+        if reference.starts_with("file://") {
+            return PackageLocation::File(file_url_to_path(reference));
+        }
+        let path = reference;
+        match &self.base {
+            IndexBase::Url(base) => {
+                PackageLocation::Url(format!("{}{}", base, path))
+            }
+            IndexBase::Dir(dir) => PackageLocation::File(dir.join(path)),
+        }
+    }
+}
+
+impl JdkCatalog for IndexCatalog {
+    fn id(&self) -> &str {
+        &self.source
+    }
+
+    fn distributions(&self) -> Result<Vec<CatalogDistribution>, AppError> {
+        let mut distributions: Vec<CatalogDistribution> = vec![];
+        for entry in &self.entries {
+            let id = &entry.distribution;
+            if distributions.iter().any(|item| &item.api_parameter == id) {
+                continue;
+            }
+            let name = id
+                .parse::<Distribution>()
+                .map_or(id.to_string(), |item| item.display_name().to_string());
+            distributions.push(CatalogDistribution {
+                name,
+                api_parameter: id.clone(),
+            });
+        }
+        Ok(distributions)
+    }
+
+    fn major_versions(&self) -> Result<Vec<CatalogMajorVersion>, AppError> {
+        let mut majors: Vec<u32> = self
+            .entries
+            .iter()
+            .filter_map(|entry| self.to_package(entry))
+            .map(|package| package.major_version)
+            .collect();
+        majors.sort_by(|a, b| b.cmp(a));
+        majors.dedup();
+        Ok(majors
+            .into_iter()
+            .map(|major| CatalogMajorVersion {
+                major_version: major,
+                term_of_support: String::new(),
+                maintained: true,
+            })
+            .collect())
+    }
+
+    fn packages(
+        &self,
+        query: &PackageQuery,
+    ) -> Result<Vec<CatalogPackage>, AppError> {
+        let constraint = query
+            .version
+            .as_deref()
+            .map(|version| version.parse::<VersionConstraint>())
+            .transpose()?;
+        let same_distribution = |id: &str| {
+            let Some(expected) = &query.distribution else {
+                return true;
+            };
+            match (id.parse::<Distribution>(), expected.parse::<Distribution>())
+            {
+                (Ok(a), Ok(b)) => a == b,
+                _ => id.eq_ignore_ascii_case(expected),
+            }
+        };
+        let mut packages: Vec<(JavaVersion, CatalogPackage)> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .operating_system
+                    .eq_ignore_ascii_case(&query.operating_system)
+                    && entry
+                        .architecture
+                        .eq_ignore_ascii_case(&query.architecture)
+                    && same_distribution(&entry.distribution)
+            })
+            .filter_map(|entry| {
+                let version = entry.version.parse::<JavaVersion>().ok()?;
+                if let Some(constraint) = &constraint {
+                    if !constraint.matches(&version) {
+                        return None;
+                    }
+                }
+                Some((version, self.to_package(entry)?))
+            })
+            .collect();
+        packages.sort_by(|a, b| b.0.cmp(&a.0));
+
+        if query.latest {
+            // The newest build of each distribution, major and archive type
+            let mut newest: HashMap<(String, u32, String), JavaVersion> =
+                HashMap::new();
+            for (version, package) in &packages {
+                let key = (
+                    package.distribution.clone(),
+                    package.major_version,
+                    package.archive_type.clone(),
+                );
+                newest.entry(key).or_insert(version.clone());
+            }
+            packages.retain(|(version, package)| {
+                let key = (
+                    package.distribution.clone(),
+                    package.major_version,
+                    package.archive_type.clone(),
+                );
+                newest.get(&key) == Some(version)
+            });
+        }
+        Ok(packages.into_iter().map(|(_, package)| package).collect())
+    }
+
+    fn resolve(
+        &self,
+        package: &CatalogPackage,
+    ) -> Result<PackageDownload, AppError> {
+        let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| entry.location() == package.id)
+        else {
+            return Err(AppError::new(format!(
+                "Package '{}' not found in '{}'",
+                package.filename, self.source
+            )));
+        };
+        Ok(PackageDownload {
+            location: self.resolve_location(entry.location()),
+            sha256: entry.sha256.clone(),
+            signature: entry
+                .signature
+                .as_deref()
+                .map(|signature| self.resolve_location(signature)),
+        })
+    }
+}
+
+/// `file:///C:/jdks/index.json` to `C:/jdks/index.json`, plain paths are
+/// kept. Percent-encoded chars are not decoded.
+pub fn file_url_to_path(value: &str) -> PathBuf {
+    let Some(path) = value.strip_prefix("file://") else {
+        return PathBuf::from(value);
+    };
+    // `file://server/share` is a UNC path on Windows
+    let path = if !path.starts_with('/') {
+        format!("//{}", path)
+    } else {
+        path.to_string()
+    };
+    let is_drive_path = path.len() >= 3
+        && path.as_bytes()[0] == b'/'
+        && path.as_bytes()[1].is_ascii_alphabetic()
+        && path.as_bytes()[2] == b':';
+    if is_drive_path {
+        PathBuf::from(&path[1..])
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{file_url_to_path, IndexCatalog};
+    use crate::{
+        installer::{
+            catalog::{JdkCatalog, PackageLocation, PackageQuery},
+            disco::http_agent,
+            test_server::TestServer,
+        },
+        test_util::temp_dir,
+    };
+
+    const INDEX_JSON: &str = r#"{
+        "packages": [
+            {
+                "distribution": "temurin",
+                "version": "21.0.1+12",
+                "operating_system": "linux",
+                "architecture": "x64",
+                "path": "temurin/OpenJDK21U-jdk_x64_linux_21.0.1_12.tar.gz",
+                "sha256": "aa"
+            },
+            {
+                "distribution": "temurin",
+                "version": "21.0.0+35",
+                "operating_system": "linux",
+                "architecture": "x64",
+                "path": "temurin/OpenJDK21U-jdk_x64_linux_21.0.0_35.tar.gz",
+                "sha256": "bb",
+                "signature": "temurin/OpenJDK21U-jdk_x64_linux_21.0.0_35.sig"
+            },
+            {
+                "distribution": "zulu",
+                "version": "17.0.9",
+                "operating_system": "linux",
+                "architecture": "x64",
+                "url": "https://cdn.example.com/zulu17.0.9-linux_x64.zip",
+                "sha256": "cc"
+            },
+            {
+                "distribution": "temurin",
+                "version": "21.0.1+12",
+                "operating_system": "windows",
+                "architecture": "x64",
+                "path": "temurin/OpenJDK21U-jdk_x64_windows_21.0.1_12.zip",
+                "sha256": "dd"
+            }
+        ]
+    }"#;
+
+    fn query(
+        distribution: Option<&str>,
+        version: Option<&str>,
+    ) -> PackageQuery {
+        PackageQuery {
+            distribution: distribution.map(|item| item.to_string()),
+            version: version.map(|item| item.to_string()),
+            operating_system: "linux".to_string(),
+            architecture: "x64".to_string(),
+            latest: true,
+        }
+    }
+
+    #[test]
+    fn test_http_index() {
+        let server = TestServer::start(HashMap::from([(
+            "/jdks/index.json".to_string(),
+            INDEX_JSON.as_bytes().to_vec(),
+        )]));
+        let source = format!("{}/jdks/index.json", server.url());
+        let catalog = IndexCatalog::load(&source, &http_agent()).unwrap();
+
+        let packages = catalog
+            .packages(&query(Some("adoptium"), Some("21")))
+            .unwrap();
+        assert_eq!(1, packages.len());
+        assert_eq!("21.0.1+12", packages[0].java_version);
+        assert_eq!("tar.gz", packages[0].archive_type);
+        assert_eq!(source, packages[0].catalog);
+
+        let download = catalog.resolve(&packages[0]).unwrap();
+        assert_eq!(
+            PackageLocation::Url(format!(
+                "{}/jdks/temurin/OpenJDK21U-jdk_x64_linux_21.0.1_12.tar.gz",
+                server.url()
+            )),
+            download.location
+        );
+        assert_eq!("aa", download.sha256);
+
+        let packages = catalog.packages(&query(None, None)).unwrap();
+        let versions: Vec<&str> = packages
+            .iter()
+            .map(|item| item.java_version.as_str())
+            .collect();
+        assert_eq!(vec!["21.0.1+12", "17.0.9"], versions);
+        let majors: Vec<u32> = catalog
+            .major_versions()
+            .unwrap()
+            .iter()
+            .map(|item| item.major_version)
+            .collect();
+        assert_eq!(vec![21, 17], majors);
+    }
+
+    #[test]
+    fn test_file_index() {
+        let dir = temp_dir("catalog-index");
+        let index = dir.join("index.json");
+        std::fs::write(&index, INDEX_JSON).unwrap();
+        let source = index.to_string_lossy().to_string();
+        let catalog = IndexCatalog::load(&source, &http_agent());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let catalog = catalog.unwrap();
+        let packages = catalog.packages(&query(Some("zulu"), None)).unwrap();
+        let download = catalog.resolve(&packages[0]).unwrap();
+        assert_eq!(
+            PackageLocation::Url(
+                "https://cdn.example.com/zulu17.0.9-linux_x64.zip".to_string()
+            ),
+            download.location
+        );
+        let packages = catalog
+            .packages(&query(Some("temurin"), Some("21.0.0")))
+            .unwrap();
+        let download = catalog.resolve(&packages[0]).unwrap();
+        assert_eq!(
+            PackageLocation::File(
+                dir.join("temurin/OpenJDK21U-jdk_x64_linux_21.0.0_35.tar.gz")
+            ),
+            download.location
+        );
+        assert_eq!(
+            Some(PackageLocation::File(
+                dir.join("temurin/OpenJDK21U-jdk_x64_linux_21.0.0_35.sig")
+            )),
+            download.signature
+        );
+    }
+
+    #[test]
+    fn test_file_url_to_path() {
+        assert_eq!(
+            std::path::PathBuf::from("/mnt/jdks/index.json"),
+            file_url_to_path("file:///mnt/jdks/index.json")
+        );
+        assert_eq!(
+            std::path::PathBuf::from("C:/jdks/index.json"),
+            file_url_to_path("file:///C:/jdks/index.json")
+        );
+        assert_eq!(
+            std::path::PathBuf::from("//server/share/index.json"),
+            file_url_to_path("file://server/share/index.json")
+        );
+    }
+}
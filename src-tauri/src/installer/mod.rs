@@ -0,0 +1,563 @@
+pub mod catalog;
+pub mod disco;
+pub mod download;
+pub mod extract;
+pub mod index;
+pub mod signature;
+pub mod uninstall;
+pub mod upgrade;
+
+#[cfg(test)]
+mod test_server;
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    errors::AppError, jdk_finder::find_java_exe,
+    model::settings::SettingsValues,
+    util::cancellation_token::CancellationToken,
+};
+
+use self::{
+    catalog::{CatalogPackage, JdkCatalog, PackageLocation},
+    disco::{DiscoCatalog, DISCO_API_URL},
+    extract::ArchiveType,
+    index::IndexCatalog,
+    signature::{check_signature, SignaturePolicy, SignatureStatus},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStage {
+    Downloading,
+    Verifying,
+    Extracting,
+    Finished,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct InstallProgress {
+    /// The file name of the package.
+    pub package: String,
+    pub stage: InstallStage,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+/// A package extracted into the jdks dir.
+pub struct InstalledPackage {
+    pub home: PathBuf,
+    /// The sha256 digest of the archive, if it matched a published
+    /// checksum.
+    pub verified_digest: Option<String>,
+    pub signature: SignatureStatus,
+}
+
+/// Download, verify and extract a package into the jdks dir. The package
+/// is downloaded into the downloads dir and resumed on the next try if the
+/// download fails, it is deleted after the jdk is extracted. Packages in
+/// local or shared dirs are used in place.
+///
+/// The checksum and the signature, if published, are verified before the
+/// package is extracted.
+pub fn install_package<F>(
+    catalog: &dyn JdkCatalog,
+    package: &CatalogPackage,
+    jdks_dir: &Path,
+    downloads_dir: &Path,
+    policy: &SignaturePolicy,
+    token: &CancellationToken,
+    on_progress: F,
+) -> Result<InstalledPackage, AppError>
+where
+    F: Fn(InstallProgress),
+{
+    let Some(archive_type) = ArchiveType::from_name(&package.archive_type)
+    else {
+        return Err(AppError::new(format!(
+            "Unsupported archive type '{}'",
+            package.archive_type
+        )));
+    };
+    let home = jdks_dir.join(install_dir_name(package));
+    if home.exists() {
+        return Err(AppError::new(format!(
+            "'{}' is installed already",
+            package.filename
+        )));
+    }
+
+    let progress = |stage, downloaded, total| {
+        on_progress(InstallProgress {
+            package: package.filename.clone(),
+            stage,
+            downloaded,
+            total,
+        })
+    };
+
+    let download = catalog.resolve(package)?;
+    let archive = match &download.location {
+        PackageLocation::Url(url) => {
+            let archive =
+                downloads_dir.join(format!("{}.part", package.filename));
+            download::download_file(
+                &disco::http_agent(),
+                url,
+                &archive,
+                token,
+                |downloaded, total| {
+                    progress(InstallStage::Downloading, downloaded, total)
+                },
+            )?;
+            archive
+        }
+        PackageLocation::File(path) => path.clone(),
+    };
+    let is_download = matches!(download.location, PackageLocation::Url(_));
+
+    progress(InstallStage::Verifying, 0, None);
+    let signature =
+        download.signature.as_ref().map(read_location).transpose()?;
+    let verified = download::verify_sha256(&archive, &download.sha256)
+        .and_then(|digest| {
+            let status =
+                check_signature(&archive, signature.as_deref(), policy)?;
+            Ok((digest, status))
+        });
+    let (digest, signature) = match verified {
+        Ok(verified) => verified,
+        Err(e) => {
+            // Broken downloads cannot be resumed
+            if is_download {
+                let _ = std::fs::remove_file(&archive);
+            }
+            return Err(e);
+        }
+    };
+
+    progress(InstallStage::Extracting, 0, None);
+    extract::extract_archive(&archive, archive_type, &home)?;
+    if is_download {
+        let _ = std::fs::remove_file(&archive);
+    }
+    if find_java_exe(&home).is_none() {
+        let _ = std::fs::remove_dir_all(&home);
+        return Err(AppError::new(format!(
+            "No java executable found in '{}'",
+            package.filename
+        )));
+    }
+
+    progress(InstallStage::Finished, 0, None);
+    Ok(InstalledPackage {
+        home,
+        verified_digest: Some(digest),
+        signature,
+    })
+}
+
+/// Read a small file, e.g. a signature.
+fn read_location(location: &PackageLocation) -> Result<Vec<u8>, AppError> {
+    match location {
+        PackageLocation::Url(url) => {
+            let mut content = vec![];
+            disco::http_agent()
+                .get(url)
+                .call()
+                .map_err(|e| disco::request_error(url, e))?
+                .into_reader()
+                .take(1024 * 1024)
+                .read_to_end(&mut content)?;
+            Ok(content)
+        }
+        PackageLocation::File(path) => Ok(std::fs::read(path)?),
+    }
+}
+
+/// The enabled catalogs, indexes which cannot be loaded are returned as
+/// errors.
+pub fn enabled_catalogs(
+    settings: &SettingsValues,
+) -> Vec<Result<Box<dyn JdkCatalog>, AppError>> {
+    let mut catalogs: Vec<Result<Box<dyn JdkCatalog>, AppError>> = vec![];
+    if settings.disco_catalog_enabled {
+        catalogs.push(Ok(Box::new(DiscoCatalog::new(DISCO_API_URL))));
+    }
+    let agent = disco::http_agent();
+    for source in &settings.catalog_sources {
+        catalogs.push(
+            IndexCatalog::load(source, &agent)
+                .map(|catalog| Box::new(catalog) as Box<dyn JdkCatalog>),
+        );
+    }
+    catalogs
+}
+
+/// Query every enabled catalog and merge the results, fails only if no
+/// catalog can be queried.
+pub fn query_catalogs<T, F>(
+    settings: &SettingsValues,
+    query: F,
+) -> Result<Vec<T>, AppError>
+where
+    F: Fn(&dyn JdkCatalog) -> Result<Vec<T>, AppError>,
+{
+    let mut results = vec![];
+    let mut first_error = None;
+    let mut succeeded = false;
+    for catalog in enabled_catalogs(settings) {
+        match catalog.and_then(|catalog| query(catalog.as_ref())) {
+            Ok(items) => {
+                succeeded = true;
+                results.extend(items);
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match first_error {
+        Some(e) if !succeeded => Err(e),
+        None if !succeeded => Err(AppError::new("No catalog is enabled.")),
+        _ => Ok(results),
+    }
+}
+
+/// Extract a local jdk archive into the jdks dir. The dir is named after
+/// the archive, e.g. `OpenJDK21U-jdk_x64_windows_hotspot` for
+/// `OpenJDK21U-jdk_x64_windows_hotspot.zip`.
+///
+/// A checksum (`.sha256`) or a signature (`.minisig`, `.sig` or `.asc`)
+/// next to the archive is verified before the archive is extracted.
+pub fn import_archive(
+    archive: &Path,
+    jdks_dir: &Path,
+    policy: &SignaturePolicy,
+) -> Result<InstalledPackage, AppError> {
+    let filename = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Some(archive_type) = ArchiveType::from_name(&filename) else {
+        return Err(AppError::new(format!(
+            "Unsupported archive '{}', expected .zip, .tar.gz or .tar.xz",
+            filename
+        )));
+    };
+    let lowercase = filename.to_lowercase();
+    let stem = [".zip", ".tar.gz", ".tgz", ".tar.xz", ".txz"]
+        .iter()
+        .find(|ext| lowercase.ends_with(*ext))
+        .map_or(filename.as_str(), |ext| {
+            &filename[..filename.len() - ext.len()]
+        });
+    let home = jdks_dir.join(safe_dir_name(stem));
+    if home.exists() {
+        return Err(AppError::new(format!(
+            "'{}' is imported already",
+            filename
+        )));
+    }
+
+    let sibling = |ext: &str| {
+        let path = archive.with_file_name(format!("{}.{}", filename, ext));
+        path.is_file().then_some(path)
+    };
+    let verified_digest = match sibling("sha256") {
+        Some(path) => {
+            let checksum = std::fs::read_to_string(path)?;
+            Some(download::verify_sha256(archive, &checksum)?)
+        }
+        None => None,
+    };
+    let signature = ["minisig", "sig", "asc"]
+        .iter()
+        .find_map(|ext| sibling(ext))
+        .map(std::fs::read)
+        .transpose()?;
+    let signature = check_signature(archive, signature.as_deref(), policy)?;
+
+    extract::extract_archive(archive, archive_type, &home)?;
+    Ok(InstalledPackage {
+        home,
+        verified_digest,
+        signature,
+    })
+}
+
+/// `temurin-21.0.1+12-x64-jdk`, so a jre or another architecture of the same
+/// release does not overwrite it.
+fn install_dir_name(package: &CatalogPackage) -> String {
+    safe_dir_name(&format!(
+        "{}-{}-{}-{}",
+        package.distribution,
+        package.java_version,
+        package.architecture,
+        package.package_type
+    ))
+}
+
+/// Chars which are not safe in file names are replaced.
+fn safe_dir_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "._+-".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, io::Write, path::Path};
+
+    use super::{
+        catalog::{JdkCatalog, PackageQuery},
+        disco::{http_agent, DiscoCatalog},
+        download::sha256_file,
+        import_archive,
+        index::IndexCatalog,
+        install_package,
+        signature::{SignaturePolicy, SignatureStatus},
+        test_server::TestServer,
+    };
+    use crate::{
+        jdk_finder::{find_java_exe, java_executable_filename},
+        test_util::temp_dir,
+        util::cancellation_token::CancellationToken,
+    };
+
+    const PACKAGES_JSON: &str = include_str!("fixtures/disco_packages.json");
+
+    const PACKAGE_INFO_JSON: &str =
+        include_str!("fixtures/disco_package_info.json");
+
+    fn jdk_zip(dir: &Path) -> Vec<u8> {
+        let path = dir.join("jdk.zip");
+        let mut zip =
+            zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options =
+            zip::write::FileOptions::default().unix_permissions(0o755);
+        let java = format!("jdk-21.0.1+12/bin/{}", java_executable_filename());
+        zip.start_file(java, options).unwrap();
+        zip.write_all(b"#!/bin/sh\n").unwrap();
+        zip.start_file("jdk-21.0.1+12/release", options).unwrap();
+        zip.write_all(b"JAVA_VERSION=\"21.0.1\"\n").unwrap();
+        zip.finish().unwrap();
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_query_packages() {
+        let server = TestServer::start(HashMap::from([(
+            "/disco/v3.0/packages".to_string(),
+            PACKAGES_JSON.as_bytes().to_vec(),
+        )]));
+        let catalog =
+            DiscoCatalog::new(&format!("{}/disco/v3.0", server.url()));
+        let query = PackageQuery {
+            distribution: Some("temurin".to_string()),
+            version: Some("21".to_string()),
+            operating_system: "windows".to_string(),
+            architecture: "x64".to_string(),
+            latest: true,
+        };
+        let packages = catalog.packages(&query).unwrap();
+        assert_eq!(2, packages.len());
+        assert_eq!("21.0.1+12", packages[0].java_version);
+        assert_eq!("zip", packages[0].archive_type);
+
+        let requested = server.requested_paths();
+        assert!(requested[0].contains("distribution=temurin"));
+        assert!(requested[0].contains("operating_system=windows"));
+        assert!(requested[0].contains("archive_type=tar.gz"));
+    }
+
+    #[test]
+    fn test_install_package_resumes_download() {
+        let dir = temp_dir("install");
+        let archive = jdk_zip(&dir);
+        let digest = sha256_file(&dir.join("jdk.zip")).unwrap();
+        let checksum = digest.clone();
+
+        let mut routes = HashMap::from([(
+            "/files/OpenJDK21U-jdk_x64_windows_hotspot_21.0.1_12.zip"
+                .to_string(),
+            archive.clone(),
+        )]);
+        let server = TestServer::start_with(move |url| {
+            routes.insert(
+                "/disco/v3.0/ids/4c8b5f8f9c4a8a7d3b4e3f1a2f0b9d41".to_string(),
+                PACKAGE_INFO_JSON
+                    .replace("{{server}}", url)
+                    .replace("{{checksum}}", &checksum)
+                    .into_bytes(),
+            );
+            routes
+        });
+        let catalog =
+            DiscoCatalog::new(&format!("{}/disco/v3.0", server.url()));
+        let packages: serde_json::Value =
+            serde_json::from_str(PACKAGES_JSON).unwrap();
+        let package =
+            serde_json::from_value(packages["result"][0].clone()).unwrap();
+
+        // A partial download from a previous try
+        let downloads_dir = dir.join("downloads");
+        std::fs::create_dir_all(&downloads_dir).unwrap();
+        std::fs::write(
+            downloads_dir
+                .join("OpenJDK21U-jdk_x64_windows_hotspot_21.0.1_12.zip.part"),
+            &archive[..100],
+        )
+        .unwrap();
+
+        let jdks_dir = dir.join("jdks");
+        let installed = install_package(
+            &catalog,
+            &package,
+            &jdks_dir,
+            &downloads_dir,
+            &SignaturePolicy::default(),
+            &CancellationToken::new(),
+            |_| {},
+        )
+        .unwrap();
+        let home = installed.home;
+        let range_requested = server
+            .requested_ranges()
+            .iter()
+            .any(|item| item == "bytes=100-");
+        let java_exe = find_java_exe(&home);
+        let reinstall_failed = install_package(
+            &catalog,
+            &package,
+            &jdks_dir,
+            &downloads_dir,
+            &SignaturePolicy::default(),
+            &CancellationToken::new(),
+            |_| {},
+        )
+        .is_err();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(range_requested);
+        assert!(home.ends_with("temurin-21.0.1+12-x64-jdk"));
+        assert_eq!(Some(digest), installed.verified_digest);
+        assert_eq!(SignatureStatus::Unsigned, installed.signature);
+        assert!(java_exe.is_some());
+        assert!(reinstall_failed);
+    }
+
+    #[test]
+    fn test_install_package_from_index() {
+        let dir = temp_dir("install-index");
+        jdk_zip(&dir);
+        let digest = sha256_file(&dir.join("jdk.zip")).unwrap();
+        let index = format!(
+            r#"{{"packages": [{{
+                "distribution": "temurin",
+                "version": "21.0.1+12",
+                "operating_system": "linux",
+                "architecture": "x64",
+                "path": "jdk.zip",
+                "sha256": "{}",
+                "signature": "jdk.zip.minisig"
+            }}]}}"#,
+            digest
+        );
+        std::fs::write(dir.join("index.json"), index).unwrap();
+        std::fs::write(
+            dir.join("jdk.zip.minisig"),
+            "untrusted comment: signature from an unknown key\ninvalid\n",
+        )
+        .unwrap();
+        let source = format!("file://{}", dir.join("index.json").display());
+        let catalog = IndexCatalog::load(&source, &http_agent()).unwrap();
+        let query = PackageQuery {
+            distribution: None,
+            version: None,
+            operating_system: "linux".to_string(),
+            architecture: "x64".to_string(),
+            latest: true,
+        };
+        let package = catalog.packages(&query).unwrap().remove(0);
+
+        let install = |strict| {
+            install_package(
+                &catalog,
+                &package,
+                &dir.join("jdks"),
+                &dir.join("downloads"),
+                &SignaturePolicy {
+                    strict,
+                    ..Default::default()
+                },
+                &CancellationToken::new(),
+                |_| {},
+            )
+        };
+        let refused = install(true).is_err();
+        let installed = install(false);
+        let java_exe = installed
+            .as_ref()
+            .ok()
+            .and_then(|installed| find_java_exe(&installed.home));
+        let archive_kept = dir.join("jdk.zip").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(refused);
+        let installed = installed.unwrap();
+        assert!(installed.home.ends_with("temurin-21.0.1+12-x64-jdk"));
+        assert_eq!(Some(digest), installed.verified_digest);
+        assert!(matches!(
+            installed.signature,
+            SignatureStatus::Unverified { .. }
+        ));
+        assert!(java_exe.is_some());
+        assert!(archive_kept);
+    }
+
+    #[test]
+    fn test_import_archive() {
+        let dir = temp_dir("import");
+        jdk_zip(&dir);
+        let archive = dir.join("OpenJDK21U-jdk_x64_windows_hotspot.zip");
+        std::fs::rename(dir.join("jdk.zip"), &archive).unwrap();
+        let digest = sha256_file(&archive).unwrap();
+        std::fs::write(
+            dir.join("OpenJDK21U-jdk_x64_windows_hotspot.zip.sha256"),
+            format!("{}  OpenJDK21U-jdk_x64_windows_hotspot.zip\n", digest),
+        )
+        .unwrap();
+
+        let jdks_dir = dir.join("jdks");
+        let policy = SignaturePolicy::default();
+        let imported = import_archive(&archive, &jdks_dir, &policy);
+        let java_exe = imported
+            .as_ref()
+            .ok()
+            .and_then(|imported| find_java_exe(&imported.home));
+        let reimport_failed =
+            import_archive(&archive, &jdks_dir, &policy).is_err();
+        let unsupported =
+            import_archive(&dir.join("jdk.7z"), &jdks_dir, &policy);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let imported = imported.unwrap();
+        assert!(imported
+            .home
+            .ends_with("OpenJDK21U-jdk_x64_windows_hotspot"));
+        assert_eq!(Some(digest), imported.verified_digest);
+        assert!(java_exe.is_some());
+        assert!(reimport_failed);
+        assert!(unsupported.is_err());
+    }
+}
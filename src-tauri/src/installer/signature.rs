@@ -0,0 +1,261 @@
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use minisign_verify::{PublicKey, Signature};
+
+use crate::errors::AppError;
+
+/// Trusted keys to verify detached signatures of jdk packages.
+#[derive(Clone, Debug, Default)]
+pub struct SignaturePolicy {
+    /// Minisign public keys, the base64 key or the content of a `.pub`
+    /// file.
+    pub minisign_keys: Vec<String>,
+    /// Files of exported GPG public keys.
+    pub gpg_key_files: Vec<PathBuf>,
+    /// Refuse packages whose signature cannot be verified, they are only
+    /// reported otherwise.
+    pub strict: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Verified,
+    /// No signature is published for the package.
+    Unsigned,
+    Unverified {
+        reason: String,
+    },
+}
+
+/// Check the detached signature of a file if there is one, a missing
+/// signature or one which cannot be verified fails in strict mode.
+pub fn check_signature(
+    file: &Path,
+    signature: Option<&[u8]>,
+    policy: &SignaturePolicy,
+) -> Result<SignatureStatus, AppError> {
+    let Some(signature) = signature else {
+        if policy.strict {
+            return Err(AppError::new(format!(
+                "No signature is published for '{}'",
+                file.display()
+            )));
+        }
+        return Ok(SignatureStatus::Unsigned);
+    };
+    match verify_signature(file, signature, policy) {
+        Ok(()) => Ok(SignatureStatus::Verified),
+        Err(e) if policy.strict => Err(AppError::new(format!(
+            "Signature of '{}' cannot be verified: {}",
+            file.display(),
+            e.message
+        ))),
+        Err(e) => Ok(SignatureStatus::Unverified { reason: e.message }),
+    }
+}
+
+/// Verify a minisign or GPG signature, the kind is told by the content.
+pub fn verify_signature(
+    file: &Path,
+    signature: &[u8],
+    policy: &SignaturePolicy,
+) -> Result<(), AppError> {
+    let text = String::from_utf8_lossy(signature);
+    if text.starts_with("untrusted comment:") {
+        verify_minisign(file, &text, &policy.minisign_keys)
+    } else {
+        verify_gpg(file, signature, &policy.gpg_key_files)
+    }
+}
+
+fn verify_minisign(
+    file: &Path,
+    signature: &str,
+    keys: &[String],
+) -> Result<(), AppError> {
+    if keys.is_empty() {
+        return Err(AppError::new("No trusted minisign key is configured"));
+    }
+    let signature = Signature::decode(signature).map_err(|e| {
+        AppError::new(format!("Invalid minisign signature: {}", e))
+    })?;
+    for key in keys {
+        let key = key.trim();
+        let public_key = if key.contains('\n') {
+            PublicKey::decode(key)
+        } else {
+            PublicKey::from_base64(key)
+        };
+        let Ok(public_key) = public_key else {
+            continue;
+        };
+        // Fails if the signature is made by another key
+        let Ok(mut verifier) = public_key.verify_stream(&signature) else {
+            continue;
+        };
+        let mut reader = std::fs::File::open(file)?;
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let size = reader.read(&mut buf)?;
+            if size == 0 {
+                break;
+            }
+            verifier.update(&buf[..size]);
+        }
+        if verifier.finalize().is_ok() {
+            return Ok(());
+        }
+    }
+    Err(AppError::new(
+        "The signature does not match any trusted minisign key",
+    ))
+}
+
+/// Verify with `gpg` in a temporary home, only the trusted keys are
+/// imported into it.
+fn verify_gpg(
+    file: &Path,
+    signature: &[u8],
+    key_files: &[PathBuf],
+) -> Result<(), AppError> {
+    if key_files.is_empty() {
+        return Err(AppError::new("No trusted GPG key is configured"));
+    }
+    let home = create_gpg_home()?;
+    let ret = (|| {
+        for key_file in key_files {
+            gpg(&home)
+                .arg("--import")
+                .arg(key_file)
+                .output()
+                .map_err(|e| AppError::new(format!("Cannot run gpg: {}", e)))?;
+        }
+        let signature_file = home.join("package.sig");
+        std::fs::write(&signature_file, signature)?;
+        let output = gpg(&home)
+            .arg("--status-fd")
+            .arg("1")
+            .arg("--verify")
+            .arg(&signature_file)
+            .arg(file)
+            .output()
+            .map_err(|e| AppError::new(format!("Cannot run gpg: {}", e)))?;
+        let status = String::from_utf8_lossy(&output.stdout);
+        if output.status.success() && status.contains("[GNUPG:] VALIDSIG ") {
+            Ok(())
+        } else {
+            Err(AppError::new(
+                "The signature does not match any trusted GPG key",
+            ))
+        }
+    })();
+    let _ = std::fs::remove_dir_all(&home);
+    ret
+}
+
+/// Create a new temporary gpg home, concurrent verifications must not
+/// share the imported keys.
+fn create_gpg_home() -> Result<PathBuf, AppError> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |item| item.as_nanos());
+    loop {
+        let home = std::env::temp_dir().join(format!(
+            "jdk-switcher-gpg-{}-{}-{}",
+            std::process::id(),
+            timestamp,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        // Fails if the dir exists, it is never reused
+        match std::fs::create_dir(&home) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            // gpg refuses homes which others can read
+            let permissions = std::fs::Permissions::from_mode(0o700);
+            std::fs::set_permissions(&home, permissions)?;
+        }
+        return Ok(home);
+    }
+}
+
+fn gpg(home: &Path) -> Command {
+    let mut command = Command::new("gpg");
+    command
+        .arg("--homedir")
+        .arg(home)
+        .arg("--batch")
+        .arg("--quiet");
+    #[cfg(windows)]
+    command.creation_flags(0x08000000);
+    command
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_signature, SignaturePolicy, SignatureStatus};
+    use crate::test_util::temp_dir;
+
+    const PUBLIC_KEY: &str =
+        "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+
+    /// A prehashed signature of `test`.
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==";
+
+    #[test]
+    fn test_check_minisign_signature() {
+        let dir = temp_dir("signature");
+        let file = dir.join("test.txt");
+        std::fs::write(&file, "test").unwrap();
+        let tampered = file.with_extension("tampered");
+        std::fs::write(&tampered, "Test").unwrap();
+        let trusted = SignaturePolicy {
+            minisign_keys: vec![PUBLIC_KEY.to_string()],
+            ..Default::default()
+        };
+        let strict = SignaturePolicy {
+            strict: true,
+            ..trusted.clone()
+        };
+        let signature = Some(SIGNATURE.as_bytes());
+
+        let verified = check_signature(&file, signature, &strict);
+        let unsigned = check_signature(&tampered, None, &trusted);
+        let unsigned_refused = check_signature(&tampered, None, &strict);
+        let warned = check_signature(&tampered, signature, &trusted);
+        let refused = check_signature(&tampered, signature, &strict);
+        let untrusted =
+            check_signature(&file, signature, &SignaturePolicy::default());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(SignatureStatus::Verified, verified.unwrap());
+        assert_eq!(SignatureStatus::Unsigned, unsigned.unwrap());
+        assert!(unsigned_refused.is_err());
+        assert!(matches!(
+            warned.unwrap(),
+            SignatureStatus::Unverified { .. }
+        ));
+        assert!(refused.is_err());
+        assert!(matches!(
+            untrusted.unwrap(),
+            SignatureStatus::Unverified { .. }
+        ));
+    }
+}
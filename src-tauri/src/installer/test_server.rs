@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+};
+
+/// The requested path with the query, and the range header.
+type Request = (String, Option<String>);
+
+/// A local HTTP server which serves fixed responses by path, standing in for
+/// the Disco API and download sites in tests. Range requests are supported.
+pub struct TestServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestServer {
+    pub fn start(routes: HashMap<String, Vec<u8>>) -> Self {
+        Self::start_with(|_| routes)
+    }
+
+    /// Start the server, the routes are created with the server url so
+    /// responses can link to other routes.
+    pub fn start_with<F>(routes: F) -> Self
+    where
+        F: FnOnce(&str) -> HashMap<String, Vec<u8>>,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let routes = routes(&url);
+        let requests = Arc::new(Mutex::new(vec![]));
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().filter_map(|item| item.ok()) {
+                let _ = handle(stream, &routes, &recorded);
+            }
+        });
+        Self { url, requests }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn requested_paths(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    pub fn requested_ranges(&self) -> Vec<String> {
+        let requests = self.requests.lock().unwrap();
+        requests
+            .iter()
+            .filter_map(|(_, range)| range.clone())
+            .collect()
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    routes: &HashMap<String, Vec<u8>>,
+    requests: &Mutex<Vec<Request>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let target = request_line.split(' ').nth(1).unwrap_or("/").to_string();
+    let mut range = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("range") {
+                range = Some(value.trim().to_string());
+            }
+        }
+    }
+    requests
+        .lock()
+        .unwrap()
+        .push((target.clone(), range.clone()));
+
+    let path = target.split('?').next().unwrap_or("");
+    let Some(body) = routes.get(path) else {
+        let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\
+                        Connection: close\r\n\r\n";
+        return stream.write_all(response.as_bytes());
+    };
+    let start = range
+        .as_deref()
+        .and_then(|item| item.strip_prefix("bytes="))
+        .and_then(|item| item.strip_suffix('-'))
+        .and_then(|item| item.parse::<usize>().ok());
+    let (status, body, content_range) = match start {
+        Some(start) if start >= body.len() => {
+            ("416 Range Not Satisfiable", &body[..0], None)
+        }
+        Some(start) => (
+            "206 Partial Content",
+            &body[start..],
+            Some(format!("bytes {}-{}/{}", start, body.len() - 1, body.len())),
+        ),
+        None => ("200 OK", &body[..], None),
+    };
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        body.len()
+    );
+    if let Some(content_range) = content_range {
+        head.push_str(&format!("Content-Range: {}\r\n", content_range));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(body)
+}
@@ -0,0 +1,119 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::errors::AppError;
+
+/// The dir a jdk is installed to, the top level dir under the jdks dir
+/// which contains the jdk home.
+pub fn install_dir_of(home: &Path, jdks_dir: &Path) -> Option<PathBuf> {
+    let relative = home.strip_prefix(jdks_dir).ok()?;
+    let first = relative.components().next()?;
+    match first {
+        std::path::Component::Normal(name) => Some(jdks_dir.join(name)),
+        _ => None,
+    }
+}
+
+/// Move an install dir into the trash dir, it can be moved back until it
+/// is deleted. Returns the path in the trash.
+pub fn move_to_trash(
+    install_dir: &Path,
+    trash_dir: &Path,
+) -> Result<PathBuf, AppError> {
+    let Some(name) = install_dir.file_name() else {
+        return Err(AppError::new(format!(
+            "Invalid install dir '{}'",
+            install_dir.display()
+        )));
+    };
+    std::fs::create_dir_all(trash_dir)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |item| item.as_millis());
+    let target =
+        trash_dir.join(format!("{}-{}", name.to_string_lossy(), timestamp));
+    std::fs::rename(install_dir, &target).map_err(|e| {
+        AppError::new(format!(
+            "Cannot move '{}' to the trash: {}",
+            install_dir.display(),
+            e
+        ))
+    })?;
+    Ok(target)
+}
+
+/// Delete a dir moved to the trash, returns the freed bytes.
+pub fn delete_trashed(trashed: &Path) -> Result<u64, AppError> {
+    let size = dir_size(trashed);
+    if trashed.is_dir() {
+        std::fs::remove_dir_all(trashed)?;
+    } else {
+        std::fs::remove_file(trashed)?;
+    }
+    Ok(size)
+}
+
+/// The total size of the files in a dir, symlinks are not followed.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries.flatten().map(|entry| dir_size(&entry.path())).sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::{delete_trashed, install_dir_of, move_to_trash};
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_install_dir_of() {
+        let jdks_dir = Path::new("/data/jdks");
+        assert_eq!(
+            Some(jdks_dir.join("temurin-21")),
+            install_dir_of(
+                Path::new("/data/jdks/temurin-21/jdk-21/Contents/Home"),
+                jdks_dir
+            )
+        );
+        assert_eq!(None, install_dir_of(Path::new("/opt/jdk-21"), jdks_dir));
+        assert_eq!(None, install_dir_of(jdks_dir, jdks_dir));
+    }
+
+    #[test]
+    fn test_move_to_trash() {
+        let dir = temp_dir("uninstall");
+        let install_dir = dir.join("jdks").join("temurin-21");
+        let trash_dir = dir.join("trash");
+        std::fs::create_dir_all(install_dir.join("bin")).unwrap();
+        std::fs::write(install_dir.join("release"), "0123456789").unwrap();
+        std::fs::write(install_dir.join("bin").join("java"), "01234").unwrap();
+
+        std::fs::create_dir_all(&trash_dir).unwrap();
+        std::fs::write(trash_dir.join("other"), "other").unwrap();
+
+        let trashed = move_to_trash(&install_dir, &trash_dir).unwrap();
+        let moved = !install_dir.exists() && trashed.exists();
+        let freed = delete_trashed(&trashed);
+        let deleted = !trashed.exists();
+        let others_kept = trash_dir.join("other").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(trashed.starts_with(&trash_dir));
+        assert!(moved);
+        assert_eq!(15, freed.unwrap());
+        assert!(deleted);
+        assert!(others_kept);
+    }
+}
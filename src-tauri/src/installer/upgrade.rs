@@ -0,0 +1,166 @@
+use crate::{
+    errors::AppError,
+    model::{distribution::Distribution, java_version::JavaVersion, jdk::Jdk},
+};
+
+use super::catalog::{
+    host_arch, host_os, CatalogDistribution, CatalogPackage, JdkCatalog,
+    PackageQuery,
+};
+
+/// The query parameter of a catalog for a distribution, catalogs may name
+/// it differently, e.g. `graalvm_community` for `graalvm_ce`.
+pub fn catalog_distribution(
+    distribution: Distribution,
+    distributions: &[CatalogDistribution],
+) -> Option<String> {
+    if distribution == Distribution::Unknown {
+        return None;
+    }
+    distributions
+        .iter()
+        .find(|item| {
+            item.api_parameter.parse::<Distribution>().ok()
+                == Some(distribution)
+        })
+        .map(|item| item.api_parameter.clone())
+}
+
+/// Find the newest package of the same distribution and feature version
+/// which is newer than the jdk, pre-releases are skipped unless the jdk is
+/// one.
+pub fn newest_patch<'a>(
+    jdk: &Jdk,
+    packages: &'a [CatalogPackage],
+) -> Option<&'a CatalogPackage> {
+    packages
+        .iter()
+        .filter_map(|item| {
+            let version = item.java_version.parse::<JavaVersion>().ok()?;
+            let distribution =
+                item.distribution.parse::<Distribution>().ok()?;
+            // Probed versions often lack the build number, which must not
+            // make the same version look newer
+            let mut comparable = version.clone();
+            comparable.build = jdk.version.build.and(version.build);
+            let is_upgrade = distribution == jdk.distribution
+                && version.feature == jdk.version.feature
+                && (version.pre.is_none() || jdk.version.pre.is_some())
+                && comparable > jdk.version;
+            is_upgrade.then_some((version, item))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, item)| item)
+}
+
+/// Query the packages which may upgrade the jdks, each distribution and
+/// feature version is queried once.
+pub fn query_upgrades(
+    catalog: &dyn JdkCatalog,
+    jdks: &[Jdk],
+) -> Result<Vec<CatalogPackage>, AppError> {
+    let distributions = catalog.distributions()?;
+    let mut queried = vec![];
+    let mut packages = vec![];
+    for jdk in jdks {
+        let key = (jdk.distribution, jdk.version.feature);
+        if queried.contains(&key) {
+            continue;
+        }
+        queried.push(key);
+        let Some(distribution) =
+            catalog_distribution(jdk.distribution, &distributions)
+        else {
+            continue;
+        };
+        packages.extend(catalog.packages(&PackageQuery {
+            distribution: Some(distribution),
+            version: Some(jdk.version.feature.to_string()),
+            operating_system: host_os(),
+            architecture: host_arch(),
+            latest: true,
+        })?);
+    }
+    Ok(packages)
+}
+
+/// Record the newest patch release of each jdk, it is cleared for jdks
+/// which are up to date.
+pub fn record_updates<'a>(
+    jdks: impl IntoIterator<Item = &'a mut Jdk>,
+    packages: &[CatalogPackage],
+) {
+    for jdk in jdks {
+        jdk.latest_available = newest_patch(jdk, packages)
+            .map(|package| package.java_version.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{catalog_distribution, newest_patch, record_updates};
+    use crate::{
+        installer::catalog::{CatalogDistribution, CatalogPackage},
+        model::{distribution::Distribution, jdk::Jdk},
+        test_util,
+    };
+
+    fn package(distribution: &str, java_version: &str) -> CatalogPackage {
+        serde_json::from_value(serde_json::json!({
+            "id": java_version,
+            "archive_type": "zip",
+            "distribution": distribution,
+            "major_version": 17,
+            "java_version": java_version,
+            "distribution_version": java_version,
+            "operating_system": "windows",
+            "architecture": "x64",
+            "package_type": "jdk",
+            "filename": format!("{}-{}.zip", distribution, java_version),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_newest_patch() {
+        let jdk = Jdk {
+            distribution: Distribution::Temurin,
+            ..test_util::jdk("/opt/jdk-17/bin", "17.0.9")
+        };
+        let packages = [
+            package("temurin", "17.0.8+7"),
+            package("temurin", "17.0.9+9"),
+            package("temurin", "17.0.10+7"),
+            package("temurin", "17.0.11-ea+1"),
+            package("temurin", "21.0.2+13"),
+            package("zulu", "17.0.12+7"),
+        ];
+        assert_eq!(
+            "17.0.10+7",
+            newest_patch(&jdk, &packages).unwrap().java_version
+        );
+        assert!(newest_patch(&jdk, &packages[..2]).is_none());
+
+        let mut jdks = [jdk.clone(), jdk];
+        record_updates(&mut jdks[..1], &packages);
+        record_updates(&mut jdks[1..], &packages[..2]);
+        assert_eq!(Some("17.0.10+7"), jdks[0].latest_available.as_deref());
+        assert_eq!(None, jdks[1].latest_available);
+    }
+
+    #[test]
+    fn test_catalog_distribution() {
+        let distributions = [CatalogDistribution {
+            name: "GraalVM Community".to_string(),
+            api_parameter: "graalvm_community".to_string(),
+        }];
+        assert_eq!(
+            Some("graalvm_community".to_string()),
+            catalog_distribution(Distribution::GraalvmCe, &distributions)
+        );
+        assert_eq!(
+            None,
+            catalog_distribution(Distribution::Temurin, &distributions)
+        );
+    }
+}
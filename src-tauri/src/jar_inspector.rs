@@ -0,0 +1,244 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Child, Command},
+};
+
+use crate::{
+    errors::AppError,
+    jdk_finder::java_executable_filename,
+    jdk_selector::{self, JdkRequirement},
+    model::jdk::Jdk,
+};
+
+const CLASS_MAGIC: [u8; 4] = [0xCA, 0xFE, 0xBA, 0xBE];
+
+const MANIFEST_PATH: &str = "META-INF/MANIFEST.MF";
+
+/// Classes of multi-release jars for newer java versions, e.g.
+/// `META-INF/versions/17/Foo.class`.
+const VERSIONS_DIR: &str = "META-INF/versions/";
+
+/// What a jar or class file needs to run.
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct JarInfo {
+    /// The highest class file major version, 61 for java 17.
+    pub class_major: Option<u16>,
+    /// The lowest java version which can load all classes.
+    pub min_java: Option<u32>,
+    pub multi_release: bool,
+    /// Java versions of `META-INF/versions/<n>` of multi-release jars.
+    pub release_versions: Vec<u32>,
+    pub created_by: Option<String>,
+    pub build_jdk_spec: Option<String>,
+    pub main_class: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct JarInspection {
+    pub info: JarInfo,
+    /// The newest stored jdk which can run the jar.
+    pub jdk: Option<Jdk>,
+}
+
+/// Inspect a `.jar` or `.class` file and find the best jdk to run it.
+pub fn inspect(path: &Path, jdks: &[Jdk]) -> Result<JarInspection, AppError> {
+    let info = read_jar_info(path)?;
+    let jdk = info
+        .min_java
+        .and_then(|java| format!("{}+", java).parse::<JdkRequirement>().ok())
+        .and_then(|requirement| jdk_selector::select_best(jdks, &requirement))
+        .cloned();
+    Ok(JarInspection { info, jdk })
+}
+
+pub fn read_jar_info(path: &Path) -> Result<JarInfo, AppError> {
+    let file = std::fs::File::open(path).map_err(|e| {
+        AppError::new(format!("Cannot open '{}': {}", path.display(), e))
+    })?;
+    let is_class = path
+        .extension()
+        .is_some_and(|item| item.eq_ignore_ascii_case("class"));
+    if is_class {
+        let major = read_class_major(file)?;
+        return Ok(JarInfo {
+            class_major: Some(major),
+            min_java: Some(java_version_of_major(major)),
+            ..Default::default()
+        });
+    }
+
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| {
+        AppError::new(format!("Invalid jar '{}': {}", path.display(), e))
+    })?;
+    let mut info = JarInfo::default();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|e| {
+            AppError::new(format!("Invalid jar '{}': {}", path.display(), e))
+        })?;
+        let name = entry.name().to_string();
+        if name == MANIFEST_PATH {
+            let mut manifest = String::new();
+            entry.read_to_string(&mut manifest)?;
+            apply_manifest(&mut info, &manifest);
+            continue;
+        }
+        if let Some(versioned) = name.strip_prefix(VERSIONS_DIR) {
+            let version = versioned
+                .split('/')
+                .next()
+                .and_then(|item| item.parse::<u32>().ok());
+            if let Some(version) = version {
+                if !info.release_versions.contains(&version) {
+                    info.release_versions.push(version);
+                }
+            }
+            // Only loaded by newer java versions, not a requirement
+            continue;
+        }
+        if !name.ends_with(".class") || name.ends_with("module-info.class") {
+            continue;
+        }
+        // Skip broken entries instead of failing the whole jar
+        if let Ok(major) = read_class_major(&mut entry) {
+            info.class_major = info.class_major.max(Some(major));
+        }
+    }
+    info.release_versions.sort();
+    info.min_java = info.class_major.map(java_version_of_major);
+    Ok(info)
+}
+
+/// Read the major version from the class file header:
+/// `u4 magic, u2 minor_version, u2 major_version`.
+fn read_class_major<R: Read>(mut reader: R) -> Result<u16, AppError> {
+    let mut header = [0u8; 8];
+    reader.read_exact(&mut header)?;
+    if header[..4] != CLASS_MAGIC {
+        return Err(AppError::new("Not a java class file"));
+    }
+    Ok(u16::from_be_bytes([header[6], header[7]]))
+}
+
+/// 52 -> 8, 61 -> 17. Versions before java 5 are reported as 1 to 4.
+pub fn java_version_of_major(major: u16) -> u32 {
+    (major as u32).saturating_sub(44).max(1)
+}
+
+/// Parse the main attributes of the manifest, long values continue on lines
+/// starting with a space.
+fn apply_manifest(info: &mut JarInfo, manifest: &str) {
+    let mut attributes: Vec<(String, String)> = vec![];
+    for line in manifest.lines() {
+        if line.is_empty() {
+            // The end of the main section
+            break;
+        }
+        if let Some(continuation) = line.strip_prefix(' ') {
+            if let Some((_, value)) = attributes.last_mut() {
+                value.push_str(continuation);
+            }
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            attributes.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    for (key, value) in attributes {
+        match key.as_str() {
+            "Multi-Release" => {
+                info.multi_release = value.eq_ignore_ascii_case("true")
+            }
+            "Created-By" => info.created_by = Some(value),
+            "Build-Jdk-Spec" => info.build_jdk_spec = Some(value),
+            "Main-Class" => info.main_class = Some(value),
+            _ => {}
+        }
+    }
+}
+
+/// Start `java -jar` with the jdk, the process is not waited.
+pub fn run_jar(
+    jar: &Path,
+    jdk: &Jdk,
+    args: &[String],
+) -> Result<Child, AppError> {
+    let java = PathBuf::from(&jdk.path).join(java_executable_filename());
+    // Relative paths would not resolve in the changed working dir
+    let jar = std::env::current_dir()?.join(jar);
+    Command::new(&java)
+        .arg("-jar")
+        .arg(&jar)
+        .args(args)
+        .current_dir(jar.parent().unwrap_or(Path::new(".")))
+        .spawn()
+        .map_err(|e| {
+            AppError::new(format!("Cannot run '{}': {}", java.display(), e))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::{java_version_of_major, read_jar_info};
+    use crate::test_util::temp_dir;
+
+    fn class_bytes(major: u16) -> Vec<u8> {
+        let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0];
+        bytes.extend(major.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_read_jar_info() {
+        let dir = temp_dir("jar-inspector");
+        let path = dir.join("app.jar");
+        let file = std::fs::File::create(&path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default();
+        let entries = [
+            (
+                "META-INF/MANIFEST.MF",
+                b"Manifest-Version: 1.0\r\n\
+                  Created-By: Maven JAR Plugin 3.3.0\r\n\
+                  Build-Jdk-Spec: 21\r\nMulti-Release: true\r\n\
+                  Main-Class: com.example.very.long.package.na\r\n me.Main\r\n\
+                  \r\nName: foo\r\nMain-Class: ignored\r\n"
+                    .to_vec(),
+            ),
+            ("com/example/Main.class", class_bytes(52)),
+            ("com/example/Util.class", class_bytes(55)),
+            ("module-info.class", class_bytes(53)),
+            (
+                "META-INF/versions/17/com/example/Util.class",
+                class_bytes(61),
+            ),
+        ];
+        for (name, content) in entries {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(&content).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let info = read_jar_info(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(Some(55), info.class_major);
+        assert_eq!(Some(11), info.min_java);
+        assert!(info.multi_release);
+        assert_eq!(vec![17], info.release_versions);
+        assert_eq!(Some("21".to_string()), info.build_jdk_spec);
+        assert_eq!(
+            Some("com.example.very.long.package.name.Main".to_string()),
+            info.main_class
+        );
+    }
+
+    #[test]
+    fn test_java_version_of_major() {
+        assert_eq!(8, java_version_of_major(52));
+        assert_eq!(21, java_version_of_major(65));
+        assert_eq!(1, java_version_of_major(45));
+    }
+}
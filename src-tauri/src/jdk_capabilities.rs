@@ -0,0 +1,87 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::model::capabilities::{JdkCapabilities, JvmImpl};
+
+/// Detect tools and files of a jdk home, the `release` file and the system
+/// properties (may be empty) are used to find the JVM implementation.
+pub fn detect_capabilities(
+    jdk_home: &Path,
+    release: &HashMap<String, String>,
+    properties: &HashMap<String, String>,
+) -> JdkCapabilities {
+    let bin_dir = jdk_home.join("bin");
+    let has_tool = |name: &str| has_executable(&bin_dir, name);
+    // Jdk 8 keeps the runtime in the 'jre' dir
+    let has_file = |path: &str| {
+        jdk_home.join(path).exists() || jdk_home.join("jre").join(path).exists()
+    };
+    JdkCapabilities {
+        javac: has_tool("javac"),
+        jlink: has_tool("jlink"),
+        jpackage: has_tool("jpackage"),
+        jshell: has_tool("jshell"),
+        native_image: has_tool("native-image")
+            || has_executable(&jdk_home.join("lib/svm/bin"), "native-image"),
+        jfr: has_tool("jfr") || has_file("lib/jfr/default.jfc"),
+        src_zip: jdk_home.join("lib/src.zip").exists()
+            || jdk_home.join("src.zip").exists(),
+        jmods: jdk_home.join("jmods").is_dir(),
+        jvm: detect_jvm_impl(release, properties),
+    }
+}
+
+fn detect_jvm_impl(
+    release: &HashMap<String, String>,
+    properties: &HashMap<String, String>,
+) -> JvmImpl {
+    let texts = [release.get("JVM_VARIANT"), properties.get("java.vm.name")];
+    for text in texts.into_iter().flatten() {
+        let text = text.to_lowercase();
+        if text.contains("openj9") {
+            return JvmImpl::OpenJ9;
+        }
+        if text.contains("hotspot") || text.contains("server vm") {
+            return JvmImpl::HotSpot;
+        }
+    }
+    JvmImpl::Unknown
+}
+
+fn has_executable(dir: &Path, name: &str) -> bool {
+    if cfg!(target_os = "windows") {
+        // GraalVM ships some tools as scripts, e.g. 'native-image.cmd'
+        ["exe", "cmd", "bat"]
+            .iter()
+            .any(|ext| dir.join(format!("{}.{}", name, ext)).is_file())
+    } else {
+        dir.join(name).is_file()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::detect_jvm_impl;
+    use crate::model::capabilities::JvmImpl;
+
+    #[test]
+    fn test_detect_jvm_impl() {
+        let release =
+            HashMap::from([("JVM_VARIANT".to_string(), "Openj9".to_string())]);
+        assert_eq!(JvmImpl::OpenJ9, detect_jvm_impl(&release, &HashMap::new()));
+
+        let properties = HashMap::from([(
+            "java.vm.name".to_string(),
+            "OpenJDK 64-Bit Server VM".to_string(),
+        )]);
+        assert_eq!(
+            JvmImpl::HotSpot,
+            detect_jvm_impl(&HashMap::new(), &properties)
+        );
+        assert_eq!(
+            JvmImpl::Unknown,
+            detect_jvm_impl(&HashMap::new(), &HashMap::new())
+        );
+    }
+}
@@ -1,98 +1,266 @@
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
 use std::{
-    fs::DirEntry, os::windows::process::CommandExt, path::PathBuf,
+    collections::{HashMap, HashSet},
+    fs::DirEntry,
+    ops::ControlFlow,
+    path::{Path, PathBuf},
     process::Command,
 };
 
-use crate::{errors::AppError, model::jdk::Jdk};
+use glob::Pattern;
 
+use crate::{
+    distribution_classifier::classify_distribution,
+    errors::AppError,
+    jdk_capabilities::detect_capabilities,
+    model::{
+        arch::Arch, distribution::Distribution, integrity::IntegrityStatus,
+        java_version::JavaVersion, jdk::Jdk, jdk_source::JdkSource,
+        lifecycle::SupportStatus,
+    },
+    util::{exe_header::read_exe_arch, release_file::read_release_file},
+};
+
+/// Options of walking dirs to find java executables.
+pub struct WalkOptions {
+    /// How many levels of sub dirs to visit, the root dir is level 0.
+    pub max_depth: usize,
+    /// Dirs to skip, matched against the dir name and the full path.
+    pub excludes: Vec<Pattern>,
+    /// Follow symbolic links and junctions in sub dirs.
+    pub follow_links: bool,
+}
+
+impl WalkOptions {
+    fn is_excluded(&self, path: &Path) -> bool {
+        let name = path.file_name().map(|name| name.to_string_lossy());
+        self.excludes.iter().any(|pattern| {
+            name.as_ref().is_some_and(|name| pattern.matches(name))
+                || pattern.matches_path(path)
+        })
+    }
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 16,
+            excludes: vec![],
+            follow_links: false,
+        }
+    }
+}
+
+/// Events of walking dirs, see [walk_java_executables].
+pub enum WalkEvent<'a> {
+    /// A dir is going to be visited.
+    Dir(&'a Path),
+    /// A java executable is found.
+    JavaExe(PathBuf),
+}
+
+/// Find jdks from a dir and its sub dirs, jdks are probed one by one.
 pub fn find_jdks_from_dir(path: &PathBuf) -> Result<Vec<Jdk>, AppError> {
-    if !path.exists() {
+    let mut jdks: Vec<Jdk> = vec![];
+    walk_java_executables(path, &WalkOptions::default(), |event| {
+        if let WalkEvent::JavaExe(exe) = event {
+            if let Ok(jdk) = find_jdk_from_exe_path(&exe) {
+                jdks.push(jdk);
+            }
+        }
+        ControlFlow::Continue(())
+    })?;
+    Ok(jdks)
+}
+
+/// Walk a dir to find java executables.
+///
+/// The walk stops going deeper once a java executable or a `bin` dir is
+/// found, return [ControlFlow::Break] from the visitor to stop walking.
+pub fn walk_java_executables<F>(
+    root: &Path,
+    options: &WalkOptions,
+    mut visitor: F,
+) -> Result<(), AppError>
+where
+    F: FnMut(WalkEvent) -> ControlFlow<()>,
+{
+    if !root.exists() {
         return Err(AppError::new("Dir does not exist."));
     }
-    let mut jdks: Vec<Jdk> = vec![];
-    if path.is_dir() {
-        let Ok(entries) = std::fs::read_dir(path) else {
-            return Err(AppError::new("Cannot read dir."));
+    if !root.is_dir() {
+        return Err(AppError::new("Target path is not a directory."));
+    }
+
+    // Canonical paths of visited dirs, links may point to visited dirs
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut stack: Vec<(PathBuf, usize)> = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        let canonical = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+        if !visited.insert(canonical) {
+            continue;
+        }
+        if visitor(WalkEvent::Dir(&dir)).is_break() {
+            return Ok(());
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            if depth == 0 {
+                return Err(AppError::new("Cannot read dir."));
+            }
+            continue;
         };
-        let files: Vec<DirEntry> = entries
-            .filter(|item| item.is_ok())
-            .map(|item| item.unwrap())
-            .collect();
+        let files: Vec<DirEntry> =
+            entries.filter_map(|item| item.ok()).collect();
 
         // Find the java exe
         let java = files.iter().find(|item| {
             item.path().is_file()
                 && item.file_name() == java_executable_filename()
         });
-        if java.is_some() {
-            let jdk = find_jdk_from_exe_path(&java.unwrap().path());
-            if jdk.is_ok() {
-                jdks.push(jdk.unwrap());
+        if let Some(java) = java {
+            if visitor(WalkEvent::JavaExe(java.path())).is_break() {
+                return Ok(());
             }
-            return Ok(jdks);
+            continue;
         }
 
-        // Find the bin dir
+        // Find the bin dir, a jdk home at the max depth is still found
         let bin_dir = files.iter().find(|item| {
             let path = item.path();
             path.is_dir() && path.file_name().unwrap() == "bin"
         });
-        if bin_dir.is_some() {
-            let sub_jdks = find_jdks_from_dir(&bin_dir.unwrap().path());
-            if sub_jdks.is_ok() {
-                jdks.extend(sub_jdks.unwrap());
-            }
-            return Ok(jdks);
+        if let Some(bin_dir) = bin_dir {
+            stack.push((bin_dir.path(), depth + 1));
+            continue;
+        }
+
+        if depth >= options.max_depth {
+            continue;
         }
 
-        // Try every sub dir
-        for file in files {
+        // Try every sub dir, in reversed order to visit them in order
+        for file in files.iter().rev() {
             let file_path = file.path();
-            if file_path.is_dir() {
-                let sub_jdks = find_jdks_from_dir(&file_path);
-                if sub_jdks.is_ok() {
-                    jdks.extend(sub_jdks.unwrap());
-                }
+            if !file_path.is_dir() || options.is_excluded(&file_path) {
+                continue;
             }
+            let is_link = file.file_type().is_ok_and(|t| t.is_symlink());
+            if is_link && !options.follow_links {
+                continue;
+            }
+            stack.push((file_path, depth + 1));
         }
-
-        Ok(jdks)
-    } else {
-        Err(AppError::new("Target path is not a directory."))
     }
+
+    Ok(())
+}
+
+/// Find the java executable of a jdk home, macOS bundles keep the real
+/// home in `Contents/Home`.
+pub fn find_java_exe(home: &Path) -> Option<PathBuf> {
+    [home.to_path_buf(), home.join("Contents").join("Home")]
+        .into_iter()
+        .map(|item| item.join("bin").join(java_executable_filename()))
+        .find(|item| item.is_file())
 }
 
 pub fn find_jdk_from_exe_path(path: &PathBuf) -> Result<Jdk, AppError> {
     if !path.exists() {
         return Err(AppError::new("Target exe does not exist."));
     }
-    let output = Command::new(path.as_os_str())
-        .arg("-version")
-        .stdout(std::process::Stdio::piped())
-        .creation_flags(0x08000000)
-        .output()?;
-    // java --version -> stderr
-    // java -version  -> stdout
-    let Ok(stderr) = String::from_utf8(output.stderr) else {
-        return Err(AppError::new("Cannot not read jdk -version output"));
+    // Java 6 and older reject -XshowSettings, they are probed without
+    // properties
+    let stderr = match java_version_output(path, true)? {
+        Some(output) => output,
+        None => java_version_output(path, false)?
+            .ok_or_else(|| AppError::new("Unsupported -version output"))?,
     };
-    let lines: Vec<&str> = stderr.lines().take(3).collect();
+    let properties = parse_properties(&stderr);
+    // The version lines come after the property settings
+    let lines: Vec<&str> = stderr
+        .lines()
+        .skip_while(|line| !line.contains(" version \""))
+        .take(3)
+        .collect();
     if lines.len() < 3 {
         return Err(AppError::new("Unsupported -version output"));
     }
+    // Jdks with an unusual version are still added
     let version = parse_version(lines[0]).unwrap_or("Unknown".to_string());
-    let name = parse_name(lines[1]);
-    let arch = parse_arch(lines[2]);
+    let version = version
+        .parse::<JavaVersion>()
+        .unwrap_or_else(|_| JavaVersion::unknown(&version));
+    let arch = match read_exe_arch(path) {
+        Ok(Arch::Unknown) | Err(_) => parse_arch(lines[2]),
+        Ok(arch) => arch,
+    };
+    let bin_dir = path.parent().unwrap();
+    let jdk_home = bin_dir.parent().unwrap_or(bin_dir);
+    let release = read_release_file(jdk_home).unwrap_or_default();
+    let distribution = classify_distribution(jdk_home, &release, &properties);
+    let capabilities = detect_capabilities(jdk_home, &release, &properties);
+    let name = if distribution != Distribution::Unknown {
+        distribution.display_name().to_string()
+    } else {
+        parse_name(lines[1])
+    };
     Ok(Jdk {
         name,
-        path: path.parent().unwrap().to_str().unwrap().to_string(),
+        path: bin_dir.to_str().unwrap().to_string(),
         version,
         arch,
+        distribution,
+        capabilities,
+        source: JdkSource::Manual,
         is_current: false,
         is_valid: true,
+        is_foreign_arch: arch.is_foreign(),
+        verified_digest: None,
+        fingerprint: None,
+        integrity: IntegrityStatus::Unknown,
+        latest_available: None,
+        support: SupportStatus::Unknown,
+        support_until: None,
     })
 }
 
+/// Parse the output of `-XshowSettings:properties`, lines are like
+/// `    java.vendor = Eclipse Adoptium`, multi-line values only keep their
+/// first line.
+/// Run `java -version`, the output is `None` if it fails or prints no
+/// version.
+fn java_version_output(
+    path: &Path,
+    show_settings: bool,
+) -> Result<Option<String>, AppError> {
+    let mut command = Command::new(path.as_os_str());
+    if show_settings {
+        command.arg("-XshowSettings:properties");
+    }
+    command.arg("-version").stdout(std::process::Stdio::piped());
+    #[cfg(windows)]
+    command.creation_flags(0x08000000);
+    let output = command.output()?;
+    // java --version -> stderr
+    // java -version  -> stdout
+    let Ok(stderr) = String::from_utf8(output.stderr) else {
+        return Err(AppError::new("Cannot not read jdk -version output"));
+    };
+    let has_version = stderr.lines().any(|line| line.contains(" version \""));
+    Ok((output.status.success() && has_version).then_some(stderr))
+}
+
+fn parse_properties(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .take_while(|line| !line.contains(" version \""))
+        .filter(|line| line.starts_with("    ") && !line.starts_with("       "))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
 fn parse_version(first_line: &str) -> Option<String> {
     if let Some(start) = first_line.find('"') {
         if let Some(end) = first_line[start + 1..].find('"') {
@@ -107,18 +275,20 @@ fn parse_version(first_line: &str) -> Option<String> {
 
 fn parse_name(second_line: &str) -> String {
     let mut splits = second_line.split("Runtime Environment");
-    splits.nth(0).unwrap().to_string()
+    splits.nth(0).unwrap().trim().to_string()
 }
 
-fn parse_arch(third_line: &str) -> String {
+/// Guess the architecture from the bitness if the executable header is
+/// unreadable, a 64-bit vm may be any 64-bit architecture.
+fn parse_arch(third_line: &str) -> Arch {
     if third_line.contains("64-Bit") {
-        "64-Bit".to_string()
+        Arch::Unknown
     } else {
-        "32-Bit".to_string()
+        Arch::X86
     }
 }
 
-const fn java_executable_filename() -> &'static str {
+pub const fn java_executable_filename() -> &'static str {
     #[cfg(target_os = "windows")]
     {
         "java.exe"
@@ -132,7 +302,15 @@ const fn java_executable_filename() -> &'static str {
 
 #[cfg(test)]
 mod test {
-    use super::parse_version;
+    use std::ops::ControlFlow;
+
+    use glob::Pattern;
+
+    use super::{
+        find_jdk_from_exe_path, java_executable_filename, parse_properties,
+        parse_version, walk_java_executables, WalkEvent, WalkOptions,
+    };
+    use crate::test_util::temp_dir;
 
     #[test]
     fn test_parse_jdk_version() {
@@ -141,4 +319,97 @@ mod test {
             parse_version("openjdk version \"21\" 2023-09-19").unwrap()
         );
     }
+
+    #[test]
+    fn test_parse_properties() {
+        let output = "Property settings:\n    \
+            java.class.path = \n    \
+            java.library.path = /usr/lib\n        /lib\n    \
+            java.vendor = Eclipse Adoptium\n\n\
+            openjdk version \"17.0.9\" 2023-10-17\n";
+        let properties = parse_properties(output);
+        assert_eq!("Eclipse Adoptium", properties["java.vendor"]);
+        assert_eq!("/usr/lib", properties["java.library.path"]);
+        assert!(!properties.contains_key("/lib"));
+    }
+
+    #[test]
+    fn test_walk_skips_excluded_dirs() {
+        let root = temp_dir("jdk-finder-walk");
+        for dir in ["jdks/jdk-17/bin", "project/node_modules/jdk/bin"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            let java = root.join(dir).join(java_executable_filename());
+            std::fs::write(java, "").unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&root, root.join("jdks/loop")).unwrap();
+
+        let options = WalkOptions {
+            excludes: vec![Pattern::new("node_modules").unwrap()],
+            follow_links: true,
+            ..Default::default()
+        };
+        let mut found = vec![];
+        walk_java_executables(&root, &options, |event| {
+            if let WalkEvent::JavaExe(exe) = event {
+                found.push(exe);
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(1, found.len());
+        assert!(found[0].to_string_lossy().contains("jdk-17"));
+    }
+
+    #[test]
+    fn test_walk_finds_jdk_home_at_max_depth() {
+        let root = temp_dir("jdk-finder-depth");
+        for dir in ["jdk-17/bin", "jdks/jdk-21/bin"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            let java = root.join(dir).join(java_executable_filename());
+            std::fs::write(java, "").unwrap();
+        }
+
+        let options = WalkOptions {
+            max_depth: 1,
+            ..Default::default()
+        };
+        let mut found = vec![];
+        walk_java_executables(&root, &options, |event| {
+            if let WalkEvent::JavaExe(exe) = event {
+                found.push(exe);
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(1, found.len());
+        assert!(found[0].to_string_lossy().contains("jdk-17"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_legacy_jdk() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("jdk-finder-legacy");
+        let bin = dir.join("jdk1.6.0_45").join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let java = bin.join(java_executable_filename());
+        let script = "#!/bin/sh\n\
+            [ \"$1\" = -version ] || exit 1\n\
+            echo 'java version \"1.6.0_45\"' >&2\n\
+            echo 'Java(TM) SE Runtime Environment (build 1.6.0_45-b06)' >&2\n\
+            echo 'Java HotSpot(TM) 64-Bit Server VM (build 20.45-b01)' >&2\n";
+        std::fs::write(&java, script).unwrap();
+        std::fs::set_permissions(&java, PermissionsExt::from_mode(0o755))
+            .unwrap();
+        let jdk = find_jdk_from_exe_path(&java);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!("1.6.0_45", jdk.unwrap().version.to_string());
+    }
 }
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    errors::AppError,
+    installer::download::sha256_file,
+    model::integrity::{IntegrityStatus, JdkFingerprint},
+};
+
+/// Fingerprint the files which make up a jdk: the `release` file and all
+/// files in `bin` and `lib`.
+pub fn fingerprint(home: &Path) -> Result<JdkFingerprint, AppError> {
+    let files = fingerprint_files(home);
+    if files.is_empty() {
+        return Err(AppError::new(format!(
+            "No file to fingerprint in '{}'",
+            home.display()
+        )));
+    }
+    let digest = digest_files(home, &files)?;
+    Ok(JdkFingerprint { digest, files })
+}
+
+/// Compare the files of a jdk with its fingerprint.
+pub fn check_integrity(
+    home: &Path,
+    fingerprint: &JdkFingerprint,
+) -> IntegrityStatus {
+    let is_missing = fingerprint
+        .files
+        .iter()
+        .any(|file| !home.join(file).is_file());
+    if is_missing {
+        return IntegrityStatus::Corrupted;
+    }
+    // Files added after fingerprinting are not hashed
+    if fingerprint_files(home) != fingerprint.files {
+        return IntegrityStatus::Modified;
+    }
+    match digest_files(home, &fingerprint.files) {
+        Ok(digest) if digest == fingerprint.digest => IntegrityStatus::Intact,
+        Ok(_) => IntegrityStatus::Modified,
+        Err(_) => IntegrityStatus::Corrupted,
+    }
+}
+
+/// The files to fingerprint relative to the home, sorted.
+fn fingerprint_files(home: &Path) -> Vec<String> {
+    let mut files = vec![];
+    if home.join("release").is_file() {
+        files.push("release".to_string());
+    }
+    for dir in ["bin", "lib"] {
+        list_files(home, dir, &mut files);
+    }
+    files
+}
+
+/// List the files in a dir and its sub dirs, `dir` is relative to the home.
+fn list_files(home: &Path, dir: &str, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(home.join(dir)) else {
+        return;
+    };
+    let mut entries: Vec<(String, bool)> = entries
+        .filter_map(|item| item.ok())
+        .map(|item| {
+            let path =
+                format!("{}/{}", dir, item.file_name().to_string_lossy());
+            (path, item.path().is_dir())
+        })
+        .collect();
+    entries.sort();
+    for (path, is_dir) in entries {
+        if is_dir {
+            list_files(home, &path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Hash the paths and the digests of the files, renaming a file changes
+/// the digest too.
+fn digest_files(home: &Path, files: &[String]) -> Result<String, AppError> {
+    let mut hasher = Sha256::new();
+    for file in files {
+        hasher.update(file.as_bytes());
+        hasher.update([0]);
+        hasher.update(sha256_file(&home.join(file))?.as_bytes());
+        hasher.update([b'\n']);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_integrity, fingerprint};
+    use crate::{model::integrity::IntegrityStatus, test_util::temp_dir};
+
+    #[test]
+    fn test_check_integrity() {
+        let home = temp_dir("jdk-integrity");
+        std::fs::create_dir_all(home.join("bin")).unwrap();
+        std::fs::create_dir_all(home.join("lib")).unwrap();
+        std::fs::write(home.join("release"), "JAVA_VERSION=\"21\"\n").unwrap();
+        std::fs::write(home.join("lib/modules"), "modules").unwrap();
+        std::fs::write(home.join("bin/java"), "java").unwrap();
+        std::fs::write(home.join("bin/javac"), "javac").unwrap();
+        std::fs::create_dir_all(home.join("lib/server")).unwrap();
+        std::fs::write(home.join("lib/server/libjvm.so"), "jvm").unwrap();
+
+        let fingerprint = fingerprint(&home).unwrap();
+        let intact = check_integrity(&home, &fingerprint);
+        std::fs::write(home.join("lib/evil.so"), "evil").unwrap();
+        let added = check_integrity(&home, &fingerprint);
+        std::fs::remove_file(home.join("lib/evil.so")).unwrap();
+        std::fs::write(home.join("bin/java"), "evil").unwrap();
+        let modified = check_integrity(&home, &fingerprint);
+        std::fs::remove_file(home.join("lib/modules")).unwrap();
+        let corrupted = check_integrity(&home, &fingerprint);
+        let _ = std::fs::remove_dir_all(&home);
+
+        assert_eq!(
+            vec![
+                "release",
+                "bin/java",
+                "bin/javac",
+                "lib/modules",
+                "lib/server/libjvm.so"
+            ],
+            fingerprint.files
+        );
+        assert_eq!(IntegrityStatus::Intact, intact);
+        assert_eq!(IntegrityStatus::Modified, added);
+        assert_eq!(IntegrityStatus::Modified, modified);
+        assert_eq!(IntegrityStatus::Corrupted, corrupted);
+    }
+}
@@ -0,0 +1,195 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    errors::AppError,
+    installer::{
+        disco::{http_agent, request_error},
+        index::file_url_to_path,
+    },
+    model::{
+        jdk::Jdk,
+        lifecycle::{LifecycleEntry, LifecycleTable, SupportStatus},
+    },
+    util::paths,
+};
+
+/// The table shipped with the app, a downloaded one is used instead if it
+/// is newer.
+const BUNDLED_TABLE: &str = include_str!("jdk_lifecycle.json");
+
+/// The support is ending soon within this many days.
+const ENDING_SOON_DAYS: i64 = 180;
+
+/// Load the newest lifecycle table, the downloaded or the bundled one.
+pub fn load_table() -> LifecycleTable {
+    let bundled: LifecycleTable = serde_json::from_str(BUNDLED_TABLE)
+        .expect("The bundled lifecycle table is invalid");
+    let downloaded = std::fs::read_to_string(paths::lifecycle_json_path())
+        .ok()
+        .and_then(|content| {
+            serde_json::from_str::<LifecycleTable>(&content).ok()
+        });
+    match downloaded {
+        Some(table) if table.updated >= bundled.updated => table,
+        _ => bundled,
+    }
+}
+
+/// Download a lifecycle table from an url or a path and save it, it is
+/// used from now on if it is newer than the bundled one.
+pub fn update_table(source: &str) -> Result<LifecycleTable, AppError> {
+    let invalid = |e: String| {
+        AppError::new(format!("Invalid lifecycle table '{}': {}", source, e))
+    };
+    let content = if source.starts_with("http://")
+        || source.starts_with("https://")
+    {
+        http_agent()
+            .get(source)
+            .call()
+            .map_err(|e| request_error(source, e))?
+            .into_string()
+            .map_err(|e| invalid(e.to_string()))?
+    } else {
+        let path = file_url_to_path(source);
+        std::fs::read_to_string(&path).map_err(|e| {
+            AppError::new(format!("Cannot read '{}': {}", path.display(), e))
+        })?
+    };
+    let table: LifecycleTable =
+        serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let path = paths::lifecycle_json_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(table)
+}
+
+/// Find the entry of a release, the OpenJDK entry is used if the
+/// distribution has none.
+pub fn find_entry<'a>(
+    table: &'a LifecycleTable,
+    jdk: &Jdk,
+) -> Option<&'a LifecycleEntry> {
+    let major = jdk.version.feature;
+    let entry = |distribution: &str| {
+        table.releases.iter().find(|item| {
+            item.major == major && item.distribution == distribution
+        })
+    };
+    entry(jdk.distribution.id()).or_else(|| entry("*"))
+}
+
+/// Set the support status of the jdks.
+pub fn annotate(jdks: &mut [Jdk], table: &LifecycleTable) {
+    let today = today();
+    for jdk in jdks {
+        let entry = find_entry(table, jdk);
+        jdk.support = entry.map_or(SupportStatus::Unknown, |entry| {
+            support_status(entry, today)
+        });
+        jdk.support_until = entry
+            .and_then(|entry| entry.support_until())
+            .map(|date| date.to_string());
+    }
+}
+
+/// The support status on a day, days are counted from the unix epoch.
+pub fn support_status(entry: &LifecycleEntry, today: i64) -> SupportStatus {
+    let Some(until) = entry.support_until().and_then(parse_date) else {
+        return SupportStatus::Unknown;
+    };
+    if today > until {
+        SupportStatus::EndOfLife
+    } else if until - today <= ENDING_SOON_DAYS {
+        SupportStatus::EndingSoon
+    } else {
+        SupportStatus::Supported
+    }
+}
+
+fn today() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |item| item.as_secs() as i64 / 86400)
+}
+
+/// Parse a `YYYY-MM-DD` date to days since the unix epoch.
+fn parse_date(value: &str) -> Option<i64> {
+    let mut parts = value.trim().splitn(3, '-');
+    let year = parts.next()?.parse::<i64>().ok()?;
+    let month = parts.next()?.parse::<i64>().ok()?;
+    let day = parts.next()?.parse::<i64>().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Howard Hinnant's days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146097 + day_of_era - 719468)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_entry, parse_date, support_status, BUNDLED_TABLE};
+    use crate::{
+        model::{
+            distribution::Distribution,
+            jdk::Jdk,
+            lifecycle::{LifecycleTable, SupportStatus},
+        },
+        test_util,
+    };
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(Some(0), parse_date("1970-01-01"));
+        assert_eq!(Some(19723), parse_date("2024-01-01"));
+        assert_eq!(Some(19782), parse_date("2024-02-29"));
+        assert_eq!(None, parse_date("2024-13-01"));
+    }
+
+    #[test]
+    fn test_support_status() {
+        let table: LifecycleTable =
+            serde_json::from_str(BUNDLED_TABLE).unwrap();
+        let jdk = |distribution: Distribution, version: &str| Jdk {
+            distribution,
+            ..test_util::jdk("/opt/jdk/bin", version)
+        };
+        let status = |jdk: &Jdk, date: &str| {
+            let entry = find_entry(&table, jdk).unwrap();
+            support_status(entry, parse_date(date).unwrap())
+        };
+
+        let temurin = jdk(Distribution::Temurin, "11.0.21");
+        assert_eq!(
+            Some("2027-10-31"),
+            find_entry(&table, &temurin).unwrap().support_until()
+        );
+        assert_eq!(SupportStatus::Supported, status(&temurin, "2026-01-01"));
+        assert_eq!(SupportStatus::EndingSoon, status(&temurin, "2027-07-01"));
+        assert_eq!(SupportStatus::EndOfLife, status(&temurin, "2027-11-01"));
+
+        // Falls back to the OpenJDK release
+        let unknown = jdk(Distribution::Unknown, "20.0.2");
+        assert_eq!(SupportStatus::Supported, status(&unknown, "2023-03-01"));
+        assert_eq!(SupportStatus::EndOfLife, status(&unknown, "2024-01-01"));
+        assert!(find_entry(&table, &jdk(Distribution::Unknown, "7")).is_none());
+
+        // Upstream LTS releases have no end date, unlike Oracle's
+        let unknown_lts = jdk(Distribution::Unknown, "17.0.9");
+        assert_eq!(SupportStatus::Unknown, status(&unknown_lts, "2026-01-01"));
+        let oracle = jdk(Distribution::Oracle, "17.0.9");
+        assert_eq!(
+            Some("2029-09-30"),
+            find_entry(&table, &oracle).unwrap().support_until()
+        );
+    }
+}
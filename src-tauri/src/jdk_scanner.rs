@@ -0,0 +1,137 @@
+use std::{
+    ops::ControlFlow,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+use crate::{
+    errors::AppError,
+    jdk_finder::{
+        find_jdk_from_exe_path, walk_java_executables, WalkEvent, WalkOptions,
+    },
+    model::jdk::Jdk,
+    util::cancellation_token::CancellationToken,
+};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Progress of a running scan.
+#[derive(Clone, Default, serde::Serialize)]
+pub struct ScanProgress {
+    pub dirs_visited: usize,
+    pub jdks_found: usize,
+    pub current_path: String,
+    pub is_finished: bool,
+}
+
+/// Scan jdks from a dir, java executables are probed in parallel.
+///
+/// `on_progress` is called periodically and once more when the scan is
+/// finished. Returns an error if the scan is cancelled.
+pub async fn scan_jdks<F>(
+    root: PathBuf,
+    options: WalkOptions,
+    token: CancellationToken,
+    on_progress: F,
+) -> Result<Vec<Jdk>, AppError>
+where
+    F: Fn(ScanProgress) + Send + Sync + 'static,
+{
+    let on_progress = Arc::new(on_progress);
+    let jdks_found = Arc::new(AtomicUsize::new(0));
+    let probe_limit = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let semaphore = Arc::new(Semaphore::new(probe_limit));
+
+    // Walking dirs is blocking, probes are spawned while walking
+    let walker = {
+        let on_progress = on_progress.clone();
+        let jdks_found = jdks_found.clone();
+        let token = token.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut probes: Vec<JoinHandle<Option<Jdk>>> = vec![];
+            let mut dirs_visited = 0;
+            let mut last_emit = Instant::now();
+            walk_java_executables(&root, &options, |event| {
+                if token.is_cancelled() {
+                    return ControlFlow::Break(());
+                }
+                match event {
+                    WalkEvent::Dir(dir) => {
+                        dirs_visited += 1;
+                        if last_emit.elapsed() >= PROGRESS_INTERVAL {
+                            last_emit = Instant::now();
+                            on_progress(ScanProgress {
+                                dirs_visited,
+                                jdks_found: jdks_found.load(Ordering::SeqCst),
+                                current_path: dir.display().to_string(),
+                                is_finished: false,
+                            });
+                        }
+                    }
+                    WalkEvent::JavaExe(exe) => {
+                        probes.push(spawn_probe(
+                            exe,
+                            semaphore.clone(),
+                            jdks_found.clone(),
+                        ));
+                    }
+                }
+                ControlFlow::Continue(())
+            })?;
+            Ok::<_, AppError>((probes, dirs_visited))
+        })
+    };
+
+    let (probes, dirs_visited) = walker
+        .await
+        .map_err(|e| AppError::new(format!("Scan failed: {}", e)))??;
+
+    let mut jdks = vec![];
+    for probe in probes {
+        if token.is_cancelled() {
+            probe.abort();
+            continue;
+        }
+        if let Ok(Some(jdk)) = probe.await {
+            jdks.push(jdk);
+        }
+    }
+
+    on_progress(ScanProgress {
+        dirs_visited,
+        jdks_found: jdks.len(),
+        current_path: String::new(),
+        is_finished: true,
+    });
+
+    if token.is_cancelled() {
+        return Err(AppError::new("Scan cancelled."));
+    }
+    Ok(jdks)
+}
+
+fn spawn_probe(
+    exe: PathBuf,
+    semaphore: Arc<Semaphore>,
+    jdks_found: Arc<AtomicUsize>,
+) -> JoinHandle<Option<Jdk>> {
+    tokio::spawn(async move {
+        let _permit = semaphore.acquire_owned().await.ok()?;
+        let jdk = tokio::task::spawn_blocking(move || {
+            find_jdk_from_exe_path(&exe).ok()
+        })
+        .await
+        .ok()
+        .flatten()?;
+        jdks_found.fetch_add(1, Ordering::SeqCst);
+        Some(jdk)
+    })
+}
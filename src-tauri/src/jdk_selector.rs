@@ -0,0 +1,264 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    errors::AppError,
+    model::{
+        distribution::Distribution, jdk::Jdk,
+        version_constraint::VersionConstraint,
+    },
+};
+
+/// The file name of version pins, used by jenv, asdf and others.
+pub const PIN_FILE_NAME: &str = ".java-version";
+
+/// Requirement to select jdks, in forms of `<distribution>@<constraint>`,
+/// `<distribution>` or `<constraint>`, e.g. `temurin@>=17 <21`, `zulu`
+/// or `21+`.
+#[derive(Clone, Debug)]
+pub struct JdkRequirement {
+    pub distribution: Option<Distribution>,
+    pub version: Option<VersionConstraint>,
+}
+
+impl JdkRequirement {
+    pub fn matches(&self, jdk: &Jdk) -> bool {
+        let distribution_matched = self
+            .distribution
+            .map_or(true, |distribution| jdk.distribution == distribution);
+        let version_matched = self
+            .version
+            .as_ref()
+            .map_or(true, |version| version.matches(&jdk.version));
+        distribution_matched && version_matched
+    }
+}
+
+impl From<VersionConstraint> for JdkRequirement {
+    fn from(value: VersionConstraint) -> Self {
+        Self {
+            distribution: None,
+            version: Some(value),
+        }
+    }
+}
+
+impl FromStr for JdkRequirement {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some((distribution, version)) = s.split_once('@') {
+            return Ok(Self {
+                distribution: Some(distribution.parse()?),
+                version: Some(version.parse()?),
+            });
+        }
+        if let Ok(distribution) = s.parse::<Distribution>() {
+            return Ok(Self {
+                distribution: Some(distribution),
+                version: None,
+            });
+        }
+        Ok(Self {
+            distribution: None,
+            version: Some(s.parse()?),
+        })
+    }
+}
+
+impl Display for JdkRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.distribution, &self.version) {
+            (Some(distribution), Some(version)) => {
+                write!(f, "{}@{}", distribution.id(), version)
+            }
+            (Some(distribution), None) => write!(f, "{}", distribution.id()),
+            (None, Some(version)) => write!(f, "{}", version),
+            (None, None) => write!(f, "*"),
+        }
+    }
+}
+
+/// Sort jdks by version, the newest first.
+pub fn sort_jdks(jdks: &mut [Jdk]) {
+    jdks.sort_by(|a, b| {
+        b.version.cmp(&a.version).then_with(|| a.path.cmp(&b.path))
+    });
+}
+
+/// Find the newest valid jdk which matches the requirement.
+pub fn select_best<'a>(
+    jdks: &'a [Jdk],
+    requirement: &JdkRequirement,
+) -> Option<&'a Jdk> {
+    jdks.iter()
+        .filter(|item| item.is_valid && requirement.matches(item))
+        .max_by(|a, b| a.version.cmp(&b.version))
+}
+
+/// Find the pin file from a dir or its ancestors.
+pub fn find_pin_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|item| item.join(PIN_FILE_NAME))
+        .find(|item| item.is_file())
+}
+
+/// Read the requirement from a pin file.
+pub fn read_pin_file(path: &Path) -> Result<JdkRequirement, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    let Some(line) = content
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+    else {
+        return Err(AppError::new(format!(
+            "Empty pin file '{}'",
+            path.display()
+        )));
+    };
+    parse_pin(line)
+}
+
+/// Parse a pinned version, vendor prefixes like `temurin-17.0.9` are used
+/// as the distribution if known, unknown ones like `openjdk64-17.0.9` are
+/// ignored.
+pub fn parse_pin(value: &str) -> Result<JdkRequirement, AppError> {
+    let value = value.trim();
+    let Some(index) = value.rfind(|c: char| c == '-' || c == '@') else {
+        return value.parse();
+    };
+    let (vendor, version) = (&value[..index], &value[index + 1..]);
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return value.parse();
+    }
+    Ok(JdkRequirement {
+        distribution: vendor.parse().ok(),
+        version: Some(version.parse()?),
+    })
+}
+
+/// Resolve the pinned jdk for a dir, returns `None` if no pin file is found.
+pub fn resolve_pin<'a>(
+    dir: &Path,
+    jdks: &'a [Jdk],
+) -> Result<Option<&'a Jdk>, AppError> {
+    let Some(pin_file) = find_pin_file(dir) else {
+        return Ok(None);
+    };
+    let requirement = read_pin_file(&pin_file)?;
+    Ok(select_best(jdks, &requirement))
+}
+
+/// Point a pin file which matches the old jdk but not the new one to the
+/// new one, e.g. `temurin-17.0.9` becomes `temurin-17.0.10`. Returns
+/// whether the file is rewritten.
+pub fn migrate_pin(
+    path: &Path,
+    old: &Jdk,
+    new: &Jdk,
+) -> Result<bool, AppError> {
+    let requirement = read_pin_file(path)?;
+    if !requirement.matches(old) || requirement.matches(new) {
+        return Ok(false);
+    }
+    let version = &new.version;
+    let mut pinned =
+        format!("{}.{}.{}", version.feature, version.interim, version.update);
+    if version.patch > 0 {
+        pinned += &format!(".{}", version.patch);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let mut migrated = false;
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            let value = line.trim();
+            if migrated || value.is_empty() || value.starts_with('#') {
+                return line.to_string();
+            }
+            migrated = true;
+            // Keep the vendor prefix, see `parse_pin`
+            let prefix = value
+                .rfind(['-', '@'])
+                .filter(|index| {
+                    value[index + 1..].starts_with(|c: char| c.is_ascii_digit())
+                })
+                .map_or("", |index| &value[..=index]);
+            format!("{}{}", prefix, pinned)
+        })
+        .collect();
+    let mut content = lines.join("\n");
+    content.push('\n');
+    std::fs::write(path, content)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{migrate_pin, parse_pin, JdkRequirement};
+    use crate::{
+        model::{distribution::Distribution, jdk::Jdk},
+        test_util::{self, temp_dir},
+    };
+
+    #[test]
+    fn test_parse_requirement() {
+        let requirement: JdkRequirement = "temurin@>=17 <21".parse().unwrap();
+        assert_eq!(Some(Distribution::Temurin), requirement.distribution);
+        assert!(requirement.version.is_some());
+
+        let requirement: JdkRequirement = "zulu".parse().unwrap();
+        assert_eq!(Some(Distribution::Zulu), requirement.distribution);
+        assert!(requirement.version.is_none());
+
+        let requirement: JdkRequirement = "21+".parse().unwrap();
+        assert_eq!(None, requirement.distribution);
+
+        assert!("foo@17".parse::<JdkRequirement>().is_err());
+    }
+
+    #[test]
+    fn test_parse_pin() {
+        let matches = |pin: &str, version: &str| {
+            let version = version.parse().unwrap();
+            parse_pin(pin).unwrap().version.unwrap().matches(&version)
+        };
+        assert!(matches("17", "17.0.9"));
+        assert!(matches("temurin-17.0.9", "17.0.9+9"));
+        assert!(matches("openjdk64-17.0.9", "17.0.9"));
+        assert!(matches("1.8", "1.8.0_392"));
+        assert!(!matches("temurin-21", "17.0.9"));
+
+        let pin = parse_pin("corretto-17").unwrap();
+        assert_eq!(Some(Distribution::Corretto), pin.distribution);
+        let pin = parse_pin("openjdk64-17.0.9").unwrap();
+        assert_eq!(None, pin.distribution);
+    }
+
+    #[test]
+    fn test_migrate_pin() {
+        let jdk = |version: &str| Jdk {
+            distribution: Distribution::Temurin,
+            ..test_util::jdk(&format!("/opt/jdk-{}/bin", version), version)
+        };
+        let (old, new) = (jdk("17.0.9+9"), jdk("17.0.10+7"));
+        let dir = temp_dir("migrate-pin");
+        let exact = dir.join("exact");
+        std::fs::write(&exact, "# pinned\ntemurin-17.0.9\n").unwrap();
+        let feature = dir.join("feature");
+        std::fs::write(&feature, "17\n").unwrap();
+
+        let exact_migrated = migrate_pin(&exact, &old, &new).unwrap();
+        let exact_content = std::fs::read_to_string(&exact).unwrap();
+        let feature_migrated = migrate_pin(&feature, &old, &new).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(exact_migrated);
+        assert_eq!("# pinned\ntemurin-17.0.10\n", exact_content);
+        assert!(!feature_migrated);
+    }
+}
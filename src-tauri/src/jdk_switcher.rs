@@ -4,9 +4,7 @@ use std::{
 };
 
 use crate::{
-    errors::AppError,
-    model::jdk::Jdk,
-    util::{self, paths::find_command_exe_path},
+    errors::AppError, model::jdk::Jdk, util::paths::find_command_exe_path,
 };
 
 pub fn switch_to_jdk(jdk: &Jdk) -> Result<(), AppError> {
@@ -28,9 +26,10 @@ pub fn switch_to_jdk(jdk: &Jdk) -> Result<(), AppError> {
     // Updater system env var
     let ret = exec_env_path_updater(exec_args);
 
+    #[cfg(windows)]
     if ret.is_ok() {
         // Update path var of the current process
-        util::env::use_sys_env_path_var()?;
+        crate::util::env::use_sys_env_path_var()?;
     }
 
     ret
@@ -3,26 +3,92 @@
 
 mod app_settings;
 mod app_view_model;
+mod build_tools;
+mod cli;
+mod discovery;
+mod distribution_classifier;
 mod errors;
+mod ide_config;
+mod installer;
+mod jar_inspector;
+mod jdk_capabilities;
 mod jdk_finder;
+mod jdk_integrity;
+mod jdk_lifecycle;
+mod jdk_scanner;
+mod jdk_selector;
 mod jdk_switcher;
 mod model;
+mod project_analyzer;
 mod repo;
+#[cfg(test)]
+mod test_util;
 mod util;
 
-use app_view_model::AppViewModel;
+use app_view_model::{
+    AppViewModel, InstallResult, IntegrityReport, RescanSummary,
+    UninstallReport, UpdateReport, UpgradeResult,
+};
 use errors::AppError;
+use ide_config::{ExportPreview, IdeKind, ImportReport};
 use indoc::formatdoc;
+use installer::catalog::{
+    CatalogDistribution, CatalogMajorVersion, CatalogPackage, PackageQuery,
+};
+use jar_inspector::JarInspection;
 use model::jdk::Jdk;
+use project_analyzer::ProjectAnalysis;
 use std::path::PathBuf;
 use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
         .setup(|app| {
             let view_model = AppViewModel::new();
+            let settings = view_model.settings();
             app.manage(view_model);
+            if !settings.discovery_offered {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let view_model = app_handle.state::<AppViewModel>();
+                    if let Err(e) = view_model.discover_jdks().await {
+                        eprintln!("Failed to discover jdks: {}", e);
+                    }
+                });
+            }
+            if settings.rescan_on_startup {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let view_model = app_handle.state::<AppViewModel>();
+                    if let Err(e) = view_model.rescan_scan_roots().await {
+                        eprintln!("Failed to rescan jdks: {}", e);
+                    }
+                });
+            }
+            if settings.verify_on_startup {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let view_model = app_handle.state::<AppViewModel>();
+                    if let Err(e) = view_model.verify_jdks().await {
+                        eprintln!("Failed to verify jdks: {}", e);
+                    }
+                });
+            }
+            if settings.check_updates_on_startup {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let view_model = app_handle.state::<AppViewModel>();
+                    if let Err(e) = view_model.check_jdk_updates().await {
+                        eprintln!("Failed to check jdk updates: {}", e);
+                    }
+                });
+            }
             Ok(())
         })
         .plugin(tauri_plugin_dialog::init())
@@ -30,12 +96,58 @@ fn main() {
             open_about_dialog,
             open_folder,
             listen_ui_state_stream,
+            listen_scan_progress_stream,
+            listen_install_progress_stream,
             load_jdks,
             add_jdks_from_dir,
+            cancel_scan,
+            catalog_distributions,
+            catalog_major_versions,
+            catalog_packages,
+            install_jdk,
+            cancel_install,
+            import_jdk_archive,
+            uninstall_jdk,
+            find_jdk_upgrade,
+            upgrade_jdk,
+            check_jdk_updates,
+            update_lifecycle_table,
+            update_check_updates_on_startup,
+            update_strict_signatures,
+            update_trusted_keys,
+            update_disco_catalog_enabled,
+            add_catalog_source,
+            add_known_project,
+            remove_known_project,
+            remove_catalog_source,
+            rescan_scan_roots,
+            verify_jdks,
+            remove_scan_root,
+            update_scan_options,
+            discover_jdks,
+            add_discovered_jdks,
+            dismiss_discovered_jdks,
+            import_ide_jdks,
+            export_maven_toolchains,
+            export_gradle_paths,
+            preview_ide_export,
+            export_jdks_to_ide,
             remove_jdk_by_path,
+            select_jdk,
+            resolve_pinned_jdk,
+            analyze_project,
+            export_workspace_report,
+            inspect_jar,
+            run_jar,
             switch_to_jdk,
             update_app_theme,
             update_skip_dir_selection_hint,
+            update_hide_jres,
+            update_reject_jres,
+            update_rescan_on_startup,
+            update_verify_on_startup,
+            update_sync_maven_toolchains,
+            update_sync_gradle_paths,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -91,6 +203,32 @@ async fn listen_ui_state_stream(
     Ok(())
 }
 
+#[tauri::command]
+async fn listen_scan_progress_stream(
+    window: tauri::Window,
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<(), ()> {
+    let receiver = view_model.scan_progress_stream();
+    while !receiver.is_closed() {
+        let progress = receiver.recv().await.unwrap();
+        window.emit("scan-progress-stream", progress).unwrap();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn listen_install_progress_stream(
+    window: tauri::Window,
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<(), ()> {
+    let receiver = view_model.install_progress_stream();
+    while !receiver.is_closed() {
+        let progress = receiver.recv().await.unwrap();
+        window.emit("install-progress-stream", progress).unwrap();
+    }
+    Ok(())
+}
+
 #[tauri::command]
 async fn load_jdks(
     view_model: tauri::State<'_, AppViewModel>,
@@ -104,7 +242,303 @@ async fn add_jdks_from_dir(
     view_model: tauri::State<'_, AppViewModel>,
     dir: String,
 ) -> Result<usize, AppError> {
-    view_model.try_add_jdks_from_dir(&dir)
+    view_model.try_add_jdks_from_dir(&dir).await
+}
+
+#[tauri::command]
+async fn rescan_scan_roots(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<RescanSummary, AppError> {
+    view_model.rescan_scan_roots().await
+}
+
+#[tauri::command]
+async fn update_scan_options(
+    view_model: tauri::State<'_, AppViewModel>,
+    max_depth: usize,
+    excludes: Vec<String>,
+    follow_links: bool,
+) -> Result<(), AppError> {
+    view_model.update_scan_options(max_depth, excludes, follow_links)
+}
+
+#[tauri::command]
+async fn remove_scan_root(
+    view_model: tauri::State<'_, AppViewModel>,
+    root: String,
+) -> Result<(), AppError> {
+    view_model.remove_scan_root(&root)
+}
+
+#[tauri::command]
+async fn discover_jdks(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<usize, AppError> {
+    view_model.discover_jdks().await
+}
+
+#[tauri::command]
+async fn add_discovered_jdks(
+    view_model: tauri::State<'_, AppViewModel>,
+    paths: Vec<String>,
+) -> Result<usize, AppError> {
+    view_model.add_discovered_jdks(&paths).await
+}
+
+#[tauri::command]
+async fn dismiss_discovered_jdks(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<(), AppError> {
+    view_model.dismiss_discovered_jdks()
+}
+
+#[tauri::command]
+async fn import_ide_jdks(
+    view_model: tauri::State<'_, AppViewModel>,
+    kind: IdeKind,
+    path: Option<String>,
+) -> Result<ImportReport, AppError> {
+    view_model.import_ide_jdks(kind, path).await
+}
+
+#[tauri::command]
+async fn export_maven_toolchains(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<String, AppError> {
+    view_model.export_maven_toolchains()
+}
+
+#[tauri::command]
+async fn export_gradle_paths(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<String, AppError> {
+    view_model.export_gradle_paths()
+}
+
+#[tauri::command]
+async fn preview_ide_export(
+    view_model: tauri::State<'_, AppViewModel>,
+    kind: IdeKind,
+) -> Result<Vec<ExportPreview>, AppError> {
+    view_model.preview_ide_export(kind)
+}
+
+#[tauri::command]
+async fn export_jdks_to_ide(
+    view_model: tauri::State<'_, AppViewModel>,
+    kind: IdeKind,
+) -> Result<Vec<ExportPreview>, AppError> {
+    view_model.export_jdks_to_ide(kind)
+}
+
+#[tauri::command]
+async fn cancel_scan(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<(), ()> {
+    view_model.cancel_scan();
+    Ok(())
+}
+
+#[tauri::command]
+async fn catalog_distributions(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<Vec<CatalogDistribution>, AppError> {
+    view_model.catalog_distributions().await
+}
+
+#[tauri::command]
+async fn catalog_major_versions(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<Vec<CatalogMajorVersion>, AppError> {
+    view_model.catalog_major_versions().await
+}
+
+#[tauri::command]
+async fn catalog_packages(
+    view_model: tauri::State<'_, AppViewModel>,
+    query: PackageQuery,
+) -> Result<Vec<CatalogPackage>, AppError> {
+    view_model.catalog_packages(query).await
+}
+
+#[tauri::command]
+async fn install_jdk(
+    view_model: tauri::State<'_, AppViewModel>,
+    package: CatalogPackage,
+) -> Result<InstallResult, AppError> {
+    view_model.install_jdk(package).await
+}
+
+#[tauri::command]
+async fn cancel_install(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<(), ()> {
+    view_model.cancel_install();
+    Ok(())
+}
+
+#[tauri::command]
+async fn import_jdk_archive(
+    view_model: tauri::State<'_, AppViewModel>,
+    path: PathBuf,
+) -> Result<InstallResult, AppError> {
+    view_model.import_jdk_archive(path).await
+}
+
+#[tauri::command]
+async fn uninstall_jdk(
+    view_model: tauri::State<'_, AppViewModel>,
+    path: String,
+) -> Result<UninstallReport, AppError> {
+    view_model.uninstall_jdk(&path).await
+}
+
+#[tauri::command]
+async fn find_jdk_upgrade(
+    view_model: tauri::State<'_, AppViewModel>,
+    path: String,
+) -> Result<Option<CatalogPackage>, AppError> {
+    view_model.find_jdk_upgrade(&path).await
+}
+
+#[tauri::command]
+async fn upgrade_jdk(
+    view_model: tauri::State<'_, AppViewModel>,
+    path: String,
+) -> Result<UpgradeResult, AppError> {
+    view_model.upgrade_jdk(&path).await
+}
+
+#[tauri::command]
+async fn check_jdk_updates(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<UpdateReport, AppError> {
+    view_model.check_jdk_updates().await
+}
+
+#[tauri::command]
+async fn update_lifecycle_table(
+    view_model: tauri::State<'_, AppViewModel>,
+    source: String,
+) -> Result<String, AppError> {
+    view_model.update_lifecycle_table(source).await
+}
+
+#[tauri::command]
+async fn update_check_updates_on_startup(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_check_updates_on_startup(value)
+}
+
+#[tauri::command]
+async fn update_strict_signatures(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_strict_signatures(value)
+}
+
+#[tauri::command]
+async fn update_trusted_keys(
+    view_model: tauri::State<'_, AppViewModel>,
+    minisign_keys: Vec<String>,
+    gpg_keys: Vec<String>,
+) -> Result<(), AppError> {
+    view_model.update_trusted_keys(minisign_keys, gpg_keys)
+}
+
+#[tauri::command]
+async fn update_disco_catalog_enabled(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_disco_catalog_enabled(value)
+}
+
+#[tauri::command]
+async fn add_catalog_source(
+    view_model: tauri::State<'_, AppViewModel>,
+    source: String,
+) -> Result<(), AppError> {
+    view_model.add_catalog_source(&source).await
+}
+
+#[tauri::command]
+async fn remove_catalog_source(
+    view_model: tauri::State<'_, AppViewModel>,
+    source: String,
+) -> Result<(), AppError> {
+    view_model.remove_catalog_source(&source)
+}
+
+#[tauri::command]
+async fn add_known_project(
+    view_model: tauri::State<'_, AppViewModel>,
+    dir: String,
+) -> Result<(), AppError> {
+    view_model.add_known_project(&dir)
+}
+
+#[tauri::command]
+async fn remove_known_project(
+    view_model: tauri::State<'_, AppViewModel>,
+    dir: String,
+) -> Result<(), AppError> {
+    view_model.remove_known_project(&dir)
+}
+
+#[tauri::command]
+async fn select_jdk(
+    view_model: tauri::State<'_, AppViewModel>,
+    requirement: String,
+) -> Result<Option<Jdk>, AppError> {
+    view_model.select_jdk(&requirement)
+}
+
+#[tauri::command]
+async fn resolve_pinned_jdk(
+    view_model: tauri::State<'_, AppViewModel>,
+    dir: String,
+) -> Result<Option<Jdk>, AppError> {
+    view_model.resolve_pinned_jdk(&dir)
+}
+
+#[tauri::command]
+async fn analyze_project(
+    view_model: tauri::State<'_, AppViewModel>,
+    dir: String,
+) -> Result<ProjectAnalysis, AppError> {
+    view_model.analyze_project(&dir)
+}
+
+#[tauri::command]
+async fn export_workspace_report(
+    view_model: tauri::State<'_, AppViewModel>,
+    root: String,
+    path: String,
+) -> Result<usize, AppError> {
+    view_model.export_workspace_report(&root, &path).await
+}
+
+#[tauri::command]
+async fn inspect_jar(
+    view_model: tauri::State<'_, AppViewModel>,
+    path: String,
+) -> Result<JarInspection, AppError> {
+    view_model.inspect_jar(&path)
+}
+
+#[tauri::command]
+async fn run_jar(
+    window: tauri::Window,
+    view_model: tauri::State<'_, AppViewModel>,
+    path: String,
+) -> Result<Jdk, AppError> {
+    view_model.run_jar(&path, move |exit| {
+        let _ = window.emit("jar-exit", exit);
+    })
 }
 
 #[tauri::command]
@@ -119,8 +553,9 @@ async fn switch_to_jdk(
 async fn remove_jdk_by_path(
     view_model: tauri::State<'_, AppViewModel>,
     path: String,
+    force: bool,
 ) -> Result<(), AppError> {
-    view_model.remove_jdk_by_path(&path)
+    view_model.remove_jdk_by_path(&path, force)
 }
 
 #[tauri::command]
@@ -138,3 +573,58 @@ async fn update_skip_dir_selection_hint(
 ) -> Result<(), AppError> {
     view_model.update_skip_dir_selection_hint(value)
 }
+
+#[tauri::command]
+async fn update_hide_jres(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_hide_jres(value)
+}
+
+#[tauri::command]
+async fn update_reject_jres(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_reject_jres(value)
+}
+
+#[tauri::command]
+async fn verify_jdks(
+    view_model: tauri::State<'_, AppViewModel>,
+) -> Result<IntegrityReport, AppError> {
+    view_model.verify_jdks().await
+}
+
+#[tauri::command]
+async fn update_verify_on_startup(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_verify_on_startup(value)
+}
+
+#[tauri::command]
+async fn update_rescan_on_startup(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_rescan_on_startup(value)
+}
+
+#[tauri::command]
+async fn update_sync_maven_toolchains(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_sync_maven_toolchains(value)
+}
+
+#[tauri::command]
+async fn update_sync_gradle_paths(
+    view_model: tauri::State<'_, AppViewModel>,
+    value: bool,
+) -> Result<(), AppError> {
+    view_model.update_sync_gradle_paths(value)
+}
@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+/// The machine architecture of a jdk.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub enum Arch {
+    // Old saved jdks use the bitness from the -version output, the arch of
+    // 64-bit ones is detected again when they are loaded.
+    #[serde(rename = "x86", alias = "32-Bit")]
+    X86,
+    #[serde(rename = "x64")]
+    X64,
+    #[serde(rename = "arm")]
+    Arm,
+    #[serde(rename = "arm64")]
+    Arm64,
+    #[serde(rename = "ppc64")]
+    Ppc64,
+    #[serde(rename = "s390x")]
+    S390x,
+    #[serde(rename = "riscv64")]
+    RiscV64,
+    #[serde(rename = "unknown", alias = "64-Bit", other)]
+    Unknown,
+}
+
+impl Arch {
+    /// The architecture of the running app.
+    pub fn host() -> Arch {
+        match std::env::consts::ARCH {
+            "x86" => Arch::X86,
+            "x86_64" => Arch::X64,
+            "arm" => Arch::Arm,
+            "aarch64" => Arch::Arm64,
+            "powerpc64" => Arch::Ppc64,
+            "s390x" => Arch::S390x,
+            "riscv64" => Arch::RiscV64,
+            _ => Arch::Unknown,
+        }
+    }
+
+    /// Check if this architecture is different from the host architecture,
+    /// such jdks run under emulation (or not at all).
+    pub fn is_foreign(&self) -> bool {
+        *self != Arch::Unknown && *self != Arch::host()
+    }
+}
+
+impl Display for Arch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Arch::X86 => "x86",
+            Arch::X64 => "x64",
+            Arch::Arm => "arm",
+            Arch::Arm64 => "arm64",
+            Arch::Ppc64 => "ppc64",
+            Arch::S390x => "s390x",
+            Arch::RiscV64 => "riscv64",
+            Arch::Unknown => "unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Arch;
+
+    #[test]
+    fn test_deserialize_arch() {
+        let parse = |json: &str| serde_json::from_str::<Arch>(json).unwrap();
+        assert_eq!(Arch::Arm64, parse("\"arm64\""));
+        assert_eq!(Arch::X64, parse("\"x64\""));
+        assert_eq!(Arch::Unknown, parse("\"64-Bit\""));
+        assert_eq!(Arch::X86, parse("\"32-Bit\""));
+        assert_eq!(Arch::Unknown, parse("\"sparc\""));
+    }
+}
@@ -0,0 +1,43 @@
+/// The JVM implementation of a jdk.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum JvmImpl {
+    #[serde(rename = "hotspot")]
+    HotSpot,
+    #[serde(rename = "openj9")]
+    OpenJ9,
+    #[default]
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+/// Tools and files found in a jdk home.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct JdkCapabilities {
+    pub javac: bool,
+    pub jlink: bool,
+    pub jpackage: bool,
+    pub jshell: bool,
+    pub native_image: bool,
+    /// Java Flight Recorder.
+    pub jfr: bool,
+    pub src_zip: bool,
+    pub jmods: bool,
+    pub jvm: JvmImpl,
+}
+
+impl JdkCapabilities {
+    /// A runtime without a compiler.
+    pub fn is_jre(&self) -> bool {
+        !self.javac
+    }
+}
@@ -0,0 +1,132 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::errors::AppError;
+
+/// The vendor distribution of a jdk.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Distribution {
+    Temurin,
+    Zulu,
+    Corretto,
+    Liberica,
+    Microsoft,
+    Oracle,
+    GraalvmCe,
+    GraalvmEe,
+    Semeru,
+    SapMachine,
+    JetBrains,
+    RedHat,
+    Dragonwell,
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 13] = [
+        Distribution::Temurin,
+        Distribution::Zulu,
+        Distribution::Corretto,
+        Distribution::Liberica,
+        Distribution::Microsoft,
+        Distribution::Oracle,
+        Distribution::GraalvmCe,
+        Distribution::GraalvmEe,
+        Distribution::Semeru,
+        Distribution::SapMachine,
+        Distribution::JetBrains,
+        Distribution::RedHat,
+        Distribution::Dragonwell,
+    ];
+
+    /// The identifier used in constraints and settings, e.g. `temurin`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Distribution::Temurin => "temurin",
+            Distribution::Zulu => "zulu",
+            Distribution::Corretto => "corretto",
+            Distribution::Liberica => "liberica",
+            Distribution::Microsoft => "microsoft",
+            Distribution::Oracle => "oracle",
+            Distribution::GraalvmCe => "graalvm_ce",
+            Distribution::GraalvmEe => "graalvm_ee",
+            Distribution::Semeru => "semeru",
+            Distribution::SapMachine => "sap_machine",
+            Distribution::JetBrains => "jet_brains",
+            Distribution::RedHat => "red_hat",
+            Distribution::Dragonwell => "dragonwell",
+            Distribution::Unknown => "unknown",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Distribution::Temurin => "Eclipse Temurin",
+            Distribution::Zulu => "Azul Zulu",
+            Distribution::Corretto => "Amazon Corretto",
+            Distribution::Liberica => "BellSoft Liberica",
+            Distribution::Microsoft => "Microsoft Build of OpenJDK",
+            Distribution::Oracle => "Oracle JDK",
+            Distribution::GraalvmCe => "GraalVM CE",
+            Distribution::GraalvmEe => "Oracle GraalVM",
+            Distribution::Semeru => "IBM Semeru",
+            Distribution::SapMachine => "SapMachine",
+            Distribution::JetBrains => "JetBrains Runtime",
+            Distribution::RedHat => "Red Hat OpenJDK",
+            Distribution::Dragonwell => "Alibaba Dragonwell",
+            Distribution::Unknown => "Unknown",
+        }
+    }
+
+    /// Other names used by version managers and vendors.
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Distribution::Temurin => &["tem", "adoptium", "adoptopenjdk"],
+            Distribution::Zulu => &["azul"],
+            Distribution::Corretto => &["amzn", "amazon"],
+            Distribution::Liberica => &["librca", "bellsoft"],
+            Distribution::Microsoft => &["ms"],
+            Distribution::Oracle => &["oracle_jdk"],
+            Distribution::GraalvmCe => &["graalce", "graalvm_community"],
+            Distribution::GraalvmEe => &["graal", "graalvm", "oracle_graalvm"],
+            Distribution::Semeru => &["sem", "ibm", "openj9"],
+            Distribution::SapMachine => &["sapmchn", "sapmachine", "sap"],
+            Distribution::JetBrains => &["jbr", "jetbrains"],
+            Distribution::RedHat => &["redhat", "rh"],
+            Distribution::Dragonwell => &["albba", "alibaba"],
+            Distribution::Unknown => &[],
+        }
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['-', ' '], "_");
+        Distribution::ALL
+            .into_iter()
+            .find(|item| item.id() == name || item.aliases().contains(&&*name))
+            .ok_or_else(|| {
+                AppError::new(format!("Unknown distribution '{}'", s))
+            })
+    }
+}
+
+impl Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
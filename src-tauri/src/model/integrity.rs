@@ -0,0 +1,31 @@
+/// Digest of the files of a jdk, taken when the jdk is added.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JdkFingerprint {
+    /// The lowercase hex sha256 digest of the hashed files.
+    pub digest: String,
+    /// The hashed files relative to the jdk home, e.g. `bin/java`.
+    pub files: Vec<String>,
+}
+
+/// The result of the last integrity check of a jdk.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    /// Not fingerprinted yet.
+    #[default]
+    Unknown,
+    Intact,
+    /// Files were changed since the jdk was added.
+    Modified,
+    /// Files went missing or cannot be read.
+    Corrupted,
+}
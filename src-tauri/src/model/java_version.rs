@@ -0,0 +1,327 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use crate::errors::AppError;
+
+/// A parsed java version.
+///
+/// Supports both the JEP 223 scheme (`17.0.9+9`, `21-ea`, `22.0.1.0.1`) and
+/// the legacy `1.x` scheme (`1.8.0_392-b08`), the legacy versions are
+/// normalized so `1.8.0_392` has the feature version `8` and the update
+/// version `392`.
+#[derive(Clone, Debug)]
+pub struct JavaVersion {
+    pub feature: u32,
+    pub interim: u32,
+    pub update: u32,
+    pub patch: u32,
+    /// Version numbers after the patch number, e.g. `[1]` in `22.0.1.0.1`.
+    pub extra: Vec<u32>,
+    /// Pre-release identifier, e.g. `ea` in `21-ea`.
+    pub pre: Option<String>,
+    pub build: Option<u32>,
+    /// Additional build information, e.g. `LTS` in `17.0.9+9-LTS`.
+    pub opt: Option<String>,
+    raw: String,
+}
+
+impl JavaVersion {
+    /// A version which cannot be parsed, e.g. `Unknown` saved by older
+    /// versions of the app. It is ordered before all known versions.
+    pub fn unknown(raw: &str) -> Self {
+        JavaVersion {
+            feature: 0,
+            interim: 0,
+            update: 0,
+            patch: 0,
+            extra: vec![],
+            pre: None,
+            build: None,
+            opt: None,
+            raw: raw.to_string(),
+        }
+    }
+
+    /// Whether the version could not be parsed.
+    pub fn is_unknown(&self) -> bool {
+        self.feature == 0
+    }
+
+    /// Version numbers in order, starting from the feature version.
+    pub fn numbers(&self) -> Vec<u32> {
+        let mut numbers =
+            vec![self.feature, self.interim, self.update, self.patch];
+        numbers.extend(&self.extra);
+        numbers
+    }
+
+    fn parse(value: &str) -> Result<Self, AppError> {
+        let raw = value.trim();
+        let invalid =
+            || AppError::new(format!("Invalid java version '{}'", raw));
+
+        let number_end = raw
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+            .unwrap_or(raw.len());
+        let (vnum, rest) = raw.split_at(number_end);
+        if vnum.is_empty() {
+            return Err(invalid());
+        }
+
+        let (vnum, legacy_update) = match vnum.split_once('_') {
+            Some((vnum, update)) => {
+                (vnum, Some(update.parse::<u32>().map_err(|_| invalid())?))
+            }
+            None => (vnum, None),
+        };
+        let numbers = vnum
+            .split('.')
+            .map(|item| item.parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| invalid())?;
+
+        let is_legacy = numbers[0] == 1 && numbers.len() >= 2;
+        if legacy_update.is_some() && !is_legacy {
+            return Err(invalid());
+        }
+        let number_at = |index: usize| numbers.get(index).copied().unwrap_or(0);
+
+        let mut version = if is_legacy {
+            // 1.<feature>.<interim>_<update>
+            JavaVersion {
+                feature: numbers[1],
+                interim: number_at(2),
+                update: legacy_update.unwrap_or(0),
+                patch: 0,
+                extra: vec![],
+                pre: None,
+                build: None,
+                opt: None,
+                raw: raw.to_string(),
+            }
+        } else {
+            JavaVersion {
+                feature: numbers[0],
+                interim: number_at(1),
+                update: number_at(2),
+                patch: number_at(3),
+                extra: numbers.iter().skip(4).copied().collect(),
+                pre: None,
+                build: None,
+                opt: None,
+                raw: raw.to_string(),
+            }
+        };
+
+        // $VNUM(-$PRE)?(\+$BUILD)?(-$OPT)?
+        let (pre_part, build_part) = match rest.split_once('+') {
+            Some((pre, build)) => (pre, Some(build)),
+            None => (rest, None),
+        };
+        if !pre_part.is_empty() {
+            let Some(pre_part) = pre_part.strip_prefix('-') else {
+                return Err(invalid());
+            };
+            let mut opts = vec![];
+            for token in pre_part.split('-') {
+                if token.is_empty() {
+                    return Err(invalid());
+                }
+                // Legacy build numbers: 1.8.0_392-b08
+                let legacy_build = token
+                    .strip_prefix('b')
+                    .and_then(|number| number.parse::<u32>().ok());
+                if is_legacy && legacy_build.is_some() {
+                    version.build = legacy_build;
+                } else if version.pre.is_none() && opts.is_empty() {
+                    version.pre = Some(token.to_string());
+                } else {
+                    opts.push(token);
+                }
+            }
+            if !opts.is_empty() {
+                version.opt = Some(opts.join("-"));
+            }
+        }
+        if let Some(build_part) = build_part {
+            let (build, opt) = match build_part.split_once('-') {
+                Some((build, opt)) => (build, Some(opt)),
+                None => (build_part, None),
+            };
+            if !build.is_empty() {
+                version.build = Some(build.parse().map_err(|_| invalid())?);
+            }
+            if let Some(opt) = opt {
+                version.opt = Some(opt.to_string());
+            }
+        }
+
+        Ok(version)
+    }
+}
+
+impl FromStr for JavaVersion {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        JavaVersion::parse(s)
+    }
+}
+
+impl Display for JavaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// Compare version numbers, missing numbers are treated as zeros.
+pub fn compare_numbers(a: &[u32], b: &[u32]) -> Ordering {
+    let len = a.len().max(b.len());
+    for index in 0..len {
+        let left = a.get(index).copied().unwrap_or(0);
+        let right = b.get(index).copied().unwrap_or(0);
+        match left.cmp(&right) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+impl Ord for JavaVersion {
+    /// Orders by the version numbers, then the pre-release identifier (a
+    /// pre-release is lower than the release) and the build number. The
+    /// optional build information is ignored, as per JEP 223.
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_numbers(&self.numbers(), &other.numbers())
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+            .then_with(|| {
+                self.build.unwrap_or(0).cmp(&other.build.unwrap_or(0))
+            })
+    }
+}
+
+impl PartialOrd for JavaVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for JavaVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for JavaVersion {}
+
+impl serde::Serialize for JavaVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for JavaVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        // Saved jdks must load even if their version is not parsable
+        let value = String::deserialize(deserializer)?;
+        Ok(value
+            .parse()
+            .unwrap_or_else(|_| JavaVersion::unknown(&value)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JavaVersion;
+
+    fn version(value: &str) -> JavaVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_legacy_version() {
+        let v = version("1.8.0_392-b08");
+        assert_eq!(vec![8, 0, 392, 0], v.numbers());
+        assert_eq!(Some(8), v.build);
+        assert_eq!(None, v.pre);
+        assert_eq!("1.8.0_392-b08", v.to_string());
+    }
+
+    #[test]
+    fn test_parse_jep223_version() {
+        let v = version("17.0.9+9-LTS");
+        assert_eq!(vec![17, 0, 9, 0], v.numbers());
+        assert_eq!(Some(9), v.build);
+        assert_eq!(Some("LTS".to_string()), v.opt);
+
+        let v = version("21-ea+35");
+        assert_eq!(21, v.feature);
+        assert_eq!(Some("ea".to_string()), v.pre);
+        assert_eq!(Some(35), v.build);
+
+        let v = version("22.0.1.0.1");
+        assert_eq!(vec![22, 0, 1, 0, 1], v.numbers());
+    }
+
+    #[test]
+    fn test_parse_invalid_version() {
+        assert!("".parse::<JavaVersion>().is_err());
+        assert!("Unknown".parse::<JavaVersion>().is_err());
+        assert!("17.0.9_1".parse::<JavaVersion>().is_err());
+        assert!("17.0.x".parse::<JavaVersion>().is_err());
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let mut versions = vec![
+            version("21"),
+            version("17.0.9+9"),
+            version("1.8.0_392"),
+            version("21-ea"),
+            version("22.0.1.0.1"),
+            version("17.0.10"),
+            version("22.0.1"),
+        ];
+        versions.sort();
+        let sorted: Vec<String> =
+            versions.iter().map(|item| item.to_string()).collect();
+        assert_eq!(
+            vec![
+                "1.8.0_392",
+                "17.0.9+9",
+                "17.0.10",
+                "21-ea",
+                "21",
+                "22.0.1",
+                "22.0.1.0.1",
+            ],
+            sorted
+        );
+        assert_eq!(version("17.0.9+9-LTS"), version("17.0.9+9"));
+        assert_eq!(version("21.0.0"), version("21"));
+    }
+
+    #[test]
+    fn test_version_serde() {
+        let json = serde_json::to_string(&version("1.8.0_392")).unwrap();
+        assert_eq!("\"1.8.0_392\"", json);
+        let parsed: JavaVersion = serde_json::from_str(&json).unwrap();
+        assert_eq!(8, parsed.feature);
+
+        let unknown: JavaVersion = serde_json::from_str("\"Unknown\"").unwrap();
+        assert!(unknown.is_unknown());
+        assert_eq!("Unknown", unknown.to_string());
+        assert!(unknown < version("1.8.0_392"));
+    }
+}
@@ -1,11 +1,82 @@
+use std::path::{Path, PathBuf};
+
+use super::{
+    arch::Arch,
+    capabilities::JdkCapabilities,
+    distribution::Distribution,
+    integrity::{IntegrityStatus, JdkFingerprint},
+    java_version::JavaVersion,
+    jdk_source::JdkSource,
+    lifecycle::SupportStatus,
+};
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Jdk {
     pub name: String,
     pub path: String,
-    pub version: String,
-    pub arch: String,
+    pub version: JavaVersion,
+    pub arch: Arch,
+    #[serde(default)]
+    pub distribution: Distribution,
+    #[serde(default)]
+    pub capabilities: JdkCapabilities,
+    #[serde(default)]
+    pub source: JdkSource,
     #[serde(default)]
     pub is_current: bool,
     #[serde(default)]
     pub is_valid: bool,
+    /// The jdk does not match the host architecture.
+    #[serde(default)]
+    pub is_foreign_arch: bool,
+    /// The sha256 digest of the package the jdk is installed from, it
+    /// matched the published checksum.
+    #[serde(default)]
+    pub verified_digest: Option<String>,
+    #[serde(default)]
+    pub fingerprint: Option<JdkFingerprint>,
+    #[serde(default)]
+    pub integrity: IntegrityStatus,
+    /// The newest patch release found in the catalogs, `None` if the jdk
+    /// is up to date or it has not been checked.
+    #[serde(default)]
+    pub latest_available: Option<String>,
+    /// Set from the lifecycle table when the jdks are loaded.
+    #[serde(default)]
+    pub support: SupportStatus,
+    /// The last day of any support of the release, `YYYY-MM-DD`.
+    #[serde(default)]
+    pub support_until: Option<String>,
+}
+
+impl Jdk {
+    /// The jdk home dir, `path` is the dir of the java executable.
+    pub fn home_dir(&self) -> PathBuf {
+        let bin_dir = Path::new(&self.path);
+        bin_dir.parent().unwrap_or(bin_dir).to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Arch, Jdk};
+
+    #[test]
+    fn test_deserialize_baseline_jdks() {
+        let json = r#"[
+            {"name": "OpenJDK ", "path": "C:\\jdk\\bin", "version": "Unknown",
+                "arch": "64-Bit", "is_current": false, "is_valid": true},
+            {"name": "OpenJDK 17", "path": "C:\\jdk-17\\bin",
+                "version": "17.0.9", "arch": "32-Bit", "is_current": true,
+                "is_valid": true}
+        ]"#;
+        let jdks: Vec<Jdk> = serde_json::from_str(json).unwrap();
+        assert_eq!(2, jdks.len());
+        assert_eq!(0, jdks[0].version.feature);
+        assert_eq!("Unknown", jdks[0].version.to_string());
+        assert_eq!(17, jdks[1].version.feature);
+        assert_eq!(Arch::Unknown, jdks[0].arch);
+        assert_eq!(Arch::X86, jdks[1].arch);
+        assert!(jdks[1].is_current);
+    }
 }
@@ -0,0 +1,70 @@
+use std::fmt::Display;
+
+/// Where a jdk comes from.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum JdkSource {
+    Sdkman,
+    Jenv,
+    Asdf,
+    Mise,
+    /// Downloaded by IntelliJ IDEA into `~/.jdks`.
+    IntellijIdea,
+    /// Provisioned by Gradle toolchains into `~/.gradle/jdks`.
+    Gradle,
+    /// JetBrains Runtime bundled with JetBrains IDEs.
+    JetBrainsIde,
+    /// JetBrains Runtime bundled with Android Studio.
+    AndroidStudio,
+    /// Installed by the package manager of the OS.
+    System,
+    /// Registered in the Windows registry by an installer.
+    Registry,
+    /// Installed by this app into the managed jdks dir.
+    Installed,
+    /// Added by the user or found on the PATH.
+    #[default]
+    #[serde(other)]
+    Manual,
+}
+
+impl JdkSource {
+    /// Check if the jdk is installed and used by another tool, deleting it
+    /// may break the tool.
+    pub fn is_tool_managed(&self) -> bool {
+        !matches!(self, JdkSource::Manual | JdkSource::Installed)
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            JdkSource::Sdkman => "SDKMAN!",
+            JdkSource::Jenv => "jenv",
+            JdkSource::Asdf => "asdf",
+            JdkSource::Mise => "mise",
+            JdkSource::IntellijIdea => "IntelliJ IDEA",
+            JdkSource::Gradle => "Gradle",
+            JdkSource::JetBrainsIde => "JetBrains IDE",
+            JdkSource::AndroidStudio => "Android Studio",
+            JdkSource::System => "System",
+            JdkSource::Registry => "Windows Registry",
+            JdkSource::Installed => "JDK Switcher",
+            JdkSource::Manual => "Manual",
+        }
+    }
+}
+
+impl Display for JdkSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
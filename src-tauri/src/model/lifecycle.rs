@@ -0,0 +1,57 @@
+/// Support dates of the java releases, per distribution.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LifecycleTable {
+    /// When the table was last updated, `YYYY-MM-DD`.
+    pub updated: String,
+    pub releases: Vec<LifecycleEntry>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LifecycleEntry {
+    /// A distribution id, e.g. `temurin`, or `*` for the OpenJDK release
+    /// itself, it is used if no entry of the distribution is found. Upstream
+    /// updates of a release end with the next feature release, LTS
+    /// releases have no announced end there.
+    pub distribution: String,
+    pub major: u32,
+    /// The release date, `YYYY-MM-DD`.
+    pub ga: String,
+    #[serde(default)]
+    pub lts: bool,
+    /// The end of the premier support, `YYYY-MM-DD`.
+    #[serde(default)]
+    pub premier_support_until: Option<String>,
+    /// The end of the extended support if offered, `YYYY-MM-DD`.
+    #[serde(default)]
+    pub extended_support_until: Option<String>,
+}
+
+impl LifecycleEntry {
+    /// The last day of any support.
+    pub fn support_until(&self) -> Option<&str> {
+        self.extended_support_until
+            .as_deref()
+            .or(self.premier_support_until.as_deref())
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SupportStatus {
+    /// The release is not in the lifecycle table.
+    #[default]
+    Unknown,
+    Supported,
+    /// The support ends in a few months.
+    EndingSoon,
+    EndOfLife,
+}
@@ -1,2 +1,10 @@
+pub mod arch;
+pub mod capabilities;
+pub mod distribution;
+pub mod integrity;
+pub mod java_version;
 pub mod jdk;
+pub mod jdk_source;
+pub mod lifecycle;
 pub mod settings;
+pub mod version_constraint;
@@ -2,4 +2,92 @@
 pub struct SettingsValues {
     pub theme: String,
     pub skip_dir_selection_hint: bool,
+    /// Hide runtimes without a compiler from the list.
+    #[serde(default)]
+    pub hide_jres: bool,
+    /// Do not add runtimes without a compiler when scanning dirs.
+    #[serde(default)]
+    pub reject_jres: bool,
+    /// How many levels of sub dirs to visit when scanning jdks.
+    #[serde(default = "default_scan_max_depth")]
+    pub scan_max_depth: usize,
+    /// Glob patterns of dirs to skip when scanning jdks, matched against
+    /// both the dir name and the full path.
+    #[serde(default = "default_scan_excludes")]
+    pub scan_excludes: Vec<String>,
+    /// Follow symbolic links and junctions when scanning jdks.
+    #[serde(default)]
+    pub scan_follow_links: bool,
+    /// Dirs that have been scanned, they can be rescanned later.
+    #[serde(default)]
+    pub scan_roots: Vec<String>,
+    /// Rescan the scan roots when the app starts.
+    #[serde(default)]
+    pub rescan_on_startup: bool,
+    /// Check the files of the saved jdks when the app starts.
+    #[serde(default)]
+    pub verify_on_startup: bool,
+    /// Look for newer patch releases of the saved jdks when the app starts.
+    #[serde(default = "default_check_updates_on_startup")]
+    pub check_updates_on_startup: bool,
+    /// Jdks found from other tools have been offered to the user.
+    #[serde(default)]
+    pub discovery_offered: bool,
+    /// Keep `~/.m2/toolchains.xml` in sync with the saved jdks.
+    #[serde(default)]
+    pub sync_maven_toolchains: bool,
+    /// Keep the installation paths of Gradle in sync with the saved jdks.
+    #[serde(default)]
+    pub sync_gradle_paths: bool,
+    /// Search and install jdks from the public Disco API.
+    #[serde(default = "default_disco_catalog_enabled")]
+    pub disco_catalog_enabled: bool,
+    /// Urls or paths of catalog indexes on mirrors or shared dirs.
+    #[serde(default)]
+    pub catalog_sources: Vec<String>,
+    /// Minisign public keys trusted to sign jdk packages.
+    #[serde(default)]
+    pub trusted_minisign_keys: Vec<String>,
+    /// Files of GPG public keys trusted to sign jdk packages.
+    #[serde(default)]
+    pub trusted_gpg_keys: Vec<String>,
+    /// Refuse to install packages whose signature cannot be verified.
+    #[serde(default)]
+    pub strict_signatures: bool,
+    /// Project dirs registered by the user, their pinned jdks are not
+    /// uninstalled.
+    #[serde(default)]
+    pub known_projects: Vec<String>,
+    /// Url or path to update the jdk lifecycle table from.
+    #[serde(default)]
+    pub lifecycle_source: String,
+}
+
+pub fn default_disco_catalog_enabled() -> bool {
+    true
+}
+
+pub fn default_check_updates_on_startup() -> bool {
+    true
+}
+
+pub fn default_scan_max_depth() -> usize {
+    16
+}
+
+pub fn default_scan_excludes() -> Vec<String> {
+    [
+        "node_modules",
+        ".git",
+        ".svn",
+        ".hg",
+        ".gradle",
+        ".idea",
+        "build",
+        "target",
+        "out",
+    ]
+    .iter()
+    .map(|item| item.to_string())
+    .collect()
 }
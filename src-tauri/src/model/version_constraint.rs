@@ -0,0 +1,242 @@
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
+
+use crate::errors::AppError;
+
+use super::java_version::{compare_numbers, JavaVersion};
+
+/// A java version requirement.
+///
+/// Syntax, terms are separated by spaces or commas and all of them must match:
+///
+/// - `17`, `17.0.9`: the version starts with these numbers.
+/// - `>=17`, `>17`, `<=21`, `<21`, `=17.0.9`: compare with the version.
+/// - `21+`: same as `>=21`.
+/// - `~17.0.8`: at least `17.0.8`, but within `17.0.x`.
+///
+/// Legacy numbers are accepted too, `1.8` is the same as `8`.
+#[derive(Clone, Debug)]
+pub struct VersionConstraint {
+    terms: Vec<Term>,
+    raw: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    Prefix(Vec<u32>),
+    Compare(Comparator, Vec<u32>),
+    Tilde(Vec<u32>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparator {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl VersionConstraint {
+    /// Check if a version satisfies this constraint.
+    pub fn matches(&self, version: &JavaVersion) -> bool {
+        if version.is_unknown() {
+            return false;
+        }
+        let numbers = version.numbers();
+        self.terms.iter().all(|term| match term {
+            Term::Prefix(prefix) => {
+                prefix.iter().enumerate().all(|(index, n)| {
+                    numbers.get(index).copied().unwrap_or(0) == *n
+                })
+            }
+            Term::Compare(comparator, target) => {
+                let ordering = compare_numbers(&numbers, target);
+                match comparator {
+                    Comparator::Eq => ordering == Ordering::Equal,
+                    Comparator::Gt => ordering == Ordering::Greater,
+                    Comparator::Ge => ordering != Ordering::Less,
+                    Comparator::Lt => ordering == Ordering::Less,
+                    Comparator::Le => ordering != Ordering::Greater,
+                }
+            }
+            Term::Tilde(target) => {
+                let fixed = &target[..target.len().saturating_sub(1).max(1)];
+                let same_prefix = fixed.iter().enumerate().all(|(index, n)| {
+                    numbers.get(index).copied().unwrap_or(0) == *n
+                });
+                same_prefix
+                    && compare_numbers(&numbers, target) != Ordering::Less
+            }
+        })
+    }
+
+    /// Combine with another constraint, both of them must match.
+    pub fn and(&self, other: &VersionConstraint) -> VersionConstraint {
+        let mut terms = self.terms.clone();
+        terms.extend(other.terms.iter().cloned());
+        Self {
+            terms,
+            raw: format!("{} {}", self.raw, other.raw),
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, AppError> {
+        let raw = value.trim();
+        let invalid =
+            || AppError::new(format!("Invalid version constraint '{}'", raw));
+
+        let mut terms = vec![];
+        for token in raw.split(|c: char| c.is_whitespace() || c == ',') {
+            if token.is_empty() {
+                continue;
+            }
+            let (comparator, version) =
+                if let Some(v) = token.strip_prefix(">=") {
+                    (Some(Comparator::Ge), v)
+                } else if let Some(v) = token.strip_prefix("<=") {
+                    (Some(Comparator::Le), v)
+                } else if let Some(v) = token.strip_prefix('>') {
+                    (Some(Comparator::Gt), v)
+                } else if let Some(v) = token.strip_prefix('<') {
+                    (Some(Comparator::Lt), v)
+                } else if let Some(v) = token.strip_prefix('=') {
+                    (Some(Comparator::Eq), v)
+                } else {
+                    (None, token)
+                };
+            let term = if let Some(comparator) = comparator {
+                Term::Compare(
+                    comparator,
+                    parse_numbers(version).ok_or_else(invalid)?,
+                )
+            } else if let Some(v) = version.strip_prefix('~') {
+                Term::Tilde(parse_numbers(v).ok_or_else(invalid)?)
+            } else if let Some(v) = version.strip_suffix('+') {
+                Term::Compare(
+                    Comparator::Ge,
+                    parse_numbers(v).ok_or_else(invalid)?,
+                )
+            } else {
+                Term::Prefix(parse_numbers(version).ok_or_else(invalid)?)
+            };
+            terms.push(term);
+        }
+
+        if terms.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            terms,
+            raw: raw.to_string(),
+        })
+    }
+}
+
+/// Parse version numbers like `17`, `17.0.8` or the legacy `1.8.0_392`.
+fn parse_numbers(value: &str) -> Option<Vec<u32>> {
+    if value.is_empty() {
+        return None;
+    }
+    let mut numbers = value
+        .split(|c| c == '.' || c == '_')
+        .map(|item| item.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    if numbers.len() >= 2 && numbers[0] == 1 {
+        // Legacy version, 1.8.0_392 -> 8.0.392
+        numbers.remove(0);
+    }
+    Some(numbers)
+}
+
+impl FromStr for VersionConstraint {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VersionConstraint::parse(s)
+    }
+}
+
+impl Display for VersionConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl serde::Serialize for VersionConstraint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for VersionConstraint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value
+            .parse()
+            .map_err(|e: AppError| serde::de::Error::custom(e.message))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::VersionConstraint;
+    use crate::model::java_version::JavaVersion;
+
+    fn matches(constraint: &str, version: &str) -> bool {
+        let constraint: VersionConstraint = constraint.parse().unwrap();
+        constraint.matches(&version.parse::<JavaVersion>().unwrap())
+    }
+
+    #[test]
+    fn test_prefix_constraint() {
+        assert!(matches("17", "17.0.9+9"));
+        assert!(!matches("17", "21"));
+        assert!(matches("17.0.9", "17.0.9"));
+        assert!(!matches("17.0.9", "17.0.10"));
+        assert!(matches("1.8", "1.8.0_392"));
+        assert!(matches("8", "1.8.0_392"));
+    }
+
+    #[test]
+    fn test_range_constraint() {
+        assert!(matches(">=17 <21", "17.0.1"));
+        assert!(matches(">=17, <21", "20"));
+        assert!(!matches(">=17 <21", "21.0.1"));
+        assert!(!matches(">=17 <21", "11.0.21"));
+        assert!(matches("21+", "22.0.1"));
+        assert!(!matches("21+", "17"));
+        assert!(matches("=17.0.9", "17.0.9+9"));
+    }
+
+    #[test]
+    fn test_tilde_constraint() {
+        assert!(matches("~17.0.8", "17.0.8"));
+        assert!(matches("~17.0.8", "17.0.10"));
+        assert!(!matches("~17.0.8", "17.0.7"));
+        assert!(!matches("~17.0.8", "17.1"));
+        assert!(!matches("~17.0.8", "18"));
+        assert!(matches("~17", "17.0.1"));
+    }
+
+    #[test]
+    fn test_unknown_version() {
+        let unknown = JavaVersion::unknown("Unknown");
+        for constraint in ["<21", ">=0", "1.8+"] {
+            let constraint: VersionConstraint = constraint.parse().unwrap();
+            assert!(!constraint.matches(&unknown));
+        }
+    }
+
+    #[test]
+    fn test_invalid_constraint() {
+        assert!("".parse::<VersionConstraint>().is_err());
+        assert!(">=".parse::<VersionConstraint>().is_err());
+        assert!("latest".parse::<VersionConstraint>().is_err());
+    }
+}
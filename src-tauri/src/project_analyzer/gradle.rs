@@ -0,0 +1,235 @@
+use crate::model::version_constraint::VersionConstraint;
+
+use super::ProjectRequirement;
+
+pub const BUILD_FILE_NAMES: [&str; 2] = ["build.gradle", "build.gradle.kts"];
+
+/// Keys of the toolchain requirements, Gradle runs the toolchain apart
+/// from the jdk which runs Gradle itself.
+pub const TOOLCHAIN_KEYS: [&str; 2] =
+    ["toolchain.languageVersion", "jvmToolchain"];
+
+pub const SETTINGS_FILE_NAMES: [&str; 2] =
+    ["settings.gradle", "settings.gradle.kts"];
+
+pub const WRAPPER_PROPERTIES_PATH: &str =
+    "gradle/wrapper/gradle-wrapper.properties";
+
+/// The first Gradle version which runs on each java version, from
+/// https://docs.gradle.org/current/userguide/compatibility.html
+const JAVA_COMPATIBILITY: [(u32, [u32; 2]); 18] = [
+    (8, [2, 0]),
+    (9, [4, 3]),
+    (10, [4, 7]),
+    (11, [5, 0]),
+    (12, [5, 4]),
+    (13, [6, 0]),
+    (14, [6, 3]),
+    (15, [6, 7]),
+    (16, [7, 0]),
+    (17, [7, 3]),
+    (18, [7, 5]),
+    (19, [7, 6]),
+    (20, [8, 3]),
+    (21, [8, 5]),
+    (22, [8, 8]),
+    (23, [8, 10]),
+    (24, [8, 14]),
+    (25, [9, 1]),
+];
+
+/// Read the java requirements of a Groovy or Kotlin build script, the
+/// toolchain (`JavaLanguageVersion.of(17)`, `jvmToolchain(17)`) requires
+/// the exact version, `sourceCompatibility` and `targetCompatibility`
+/// require that version or newer.
+///
+/// The script is not evaluated, only literal values are recognized.
+pub fn parse_build_script(
+    file: &str,
+    content: &str,
+) -> Vec<ProjectRequirement> {
+    let mut requirements = vec![];
+    for line in content.lines().map(|line| line.trim()) {
+        if line.starts_with("//") {
+            continue;
+        }
+
+        for (call, key) in [
+            ("JavaLanguageVersion.of(", TOOLCHAIN_KEYS[0]),
+            ("jvmToolchain(", TOOLCHAIN_KEYS[1]),
+        ] {
+            let Some(index) = line.find(call) else {
+                continue;
+            };
+            let args = &line[index + call.len()..];
+            let Some(end) = args.find(')') else {
+                continue;
+            };
+            let version = parse_java_version(&args[..end])
+                .and_then(|version| version.parse().ok());
+            if let Some(version) = version {
+                requirements.push(ProjectRequirement::new(file, key, version));
+            }
+        }
+
+        let property = line.strip_prefix("java.").unwrap_or(line);
+        for key in ["sourceCompatibility", "targetCompatibility"] {
+            let Some(value) = property.strip_prefix(key) else {
+                continue;
+            };
+            let value = value.trim();
+            let value = match value.strip_prefix(".set(") {
+                Some(value) => value.strip_suffix(')').unwrap_or(value),
+                None => value.trim_start_matches('=').trim(),
+            };
+            let version = parse_java_version(value)
+                .and_then(|version| format!("{}+", version).parse().ok());
+            if let Some(version) = version {
+                requirements.push(ProjectRequirement::new(file, key, version));
+            }
+        }
+    }
+    requirements
+}
+
+/// Parse java version expressions of build scripts, e.g. `17`, `'1.8'`,
+/// `JavaVersion.VERSION_1_8` or `JavaVersion.toVersion("11")`.
+fn parse_java_version(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    let expr = expr
+        .strip_prefix("JavaVersion.toVersion(")
+        .and_then(|item| item.strip_suffix(')'))
+        .unwrap_or(expr);
+    let version = match expr.strip_prefix("JavaVersion.VERSION_") {
+        Some(version) => version.replace('_', "."),
+        None => expr.trim_matches(['"', '\'']).to_string(),
+    };
+    let is_valid = !version.is_empty()
+        && version.chars().all(|c| c.is_ascii_digit() || c == '.');
+    is_valid.then_some(version)
+}
+
+/// Read the Gradle version from `distributionUrl` of the wrapper properties
+/// and require a java version which can run it.
+pub fn parse_wrapper_properties(content: &str) -> Vec<ProjectRequirement> {
+    let Some(gradle_version) = content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("distributionUrl"))
+        .find_map(parse_distribution_version)
+    else {
+        return vec![];
+    };
+    java_requirement(&gradle_version)
+        .map(|version| {
+            vec![ProjectRequirement::new(
+                WRAPPER_PROPERTIES_PATH,
+                "distributionUrl",
+                version,
+            )]
+        })
+        .unwrap_or_default()
+}
+
+/// `=https\://services.gradle.org/distributions/gradle-8.5-bin.zip` to
+/// `[8, 5]`.
+fn parse_distribution_version(value: &str) -> Option<Vec<u32>> {
+    let name = value.rsplit('/').next()?;
+    let version = name.strip_prefix("gradle-")?;
+    let version = version.split('-').next().filter(|item| !item.is_empty())?;
+    version
+        .split('.')
+        .map(|item| item.parse::<u32>().ok())
+        .collect()
+}
+
+/// The java versions which can run a Gradle version, newer java versions
+/// are not capped when the Gradle version is newer than the known table.
+fn java_requirement(gradle_version: &[u32]) -> Option<VersionConstraint> {
+    let min_java = if gradle_version[0] >= 9 { 17 } else { 8 };
+    let supported = JAVA_COMPATIBILITY
+        .iter()
+        .rposition(|(_, min_gradle)| gradle_version >= &min_gradle[..])?;
+    let constraint = if supported == JAVA_COMPATIBILITY.len() - 1 {
+        format!(">={}", min_java)
+    } else {
+        format!(">={} <{}", min_java, JAVA_COMPATIBILITY[supported].0 + 1)
+    };
+    constraint.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_build_script, parse_wrapper_properties};
+    use crate::model::java_version::JavaVersion;
+
+    fn version(value: &str) -> JavaVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_build_script() {
+        let script = r#"
+            java {
+                toolchain {
+                    languageVersion = JavaLanguageVersion.of(17)
+                }
+                sourceCompatibility = JavaVersion.VERSION_1_8
+            }
+            // targetCompatibility = '21'
+            kotlin { jvmToolchain(21) }
+            targetCompatibility = '11'
+        "#;
+        let requirements = parse_build_script("build.gradle", script);
+        let keys: Vec<&str> =
+            requirements.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(
+            vec![
+                "toolchain.languageVersion",
+                "sourceCompatibility",
+                "jvmToolchain",
+                "targetCompatibility"
+            ],
+            keys
+        );
+        let toolchain = requirements[0].version.as_ref().unwrap();
+        assert!(toolchain.matches(&version("17.0.9")));
+        assert!(!toolchain.matches(&version("21")));
+        let source = requirements[1].version.as_ref().unwrap();
+        assert!(source.matches(&version("1.8.0_392")));
+        assert!(source.matches(&version("17")));
+    }
+
+    #[test]
+    fn test_parse_kotlin_build_script() {
+        let script =
+            "java.sourceCompatibility.set(JavaVersion.toVersion(\"11\"))";
+        let requirements = parse_build_script("build.gradle.kts", script);
+        assert_eq!(1, requirements.len());
+        assert_eq!("sourceCompatibility", requirements[0].key);
+    }
+
+    #[test]
+    fn test_parse_wrapper_properties() {
+        let matches = |gradle: &str, java: &str| {
+            let content = format!(
+                "distributionBase=GRADLE_USER_HOME\n\
+                 distributionUrl=https\\://services.gradle.org/distributions/\
+                 gradle-{}-bin.zip\n",
+                gradle
+            );
+            let requirements = parse_wrapper_properties(&content);
+            requirements[0]
+                .version
+                .as_ref()
+                .unwrap()
+                .matches(&version(java))
+        };
+        assert!(matches("8.5", "21.0.1"));
+        assert!(!matches("8.5", "22"));
+        assert!(matches("7.6.3", "17"));
+        assert!(!matches("7.2", "17"));
+        assert!(matches("9.2.0", "26"));
+        assert!(!matches("9.2.0", "11"));
+        assert!(matches("8.10-rc-1", "23"));
+    }
+}
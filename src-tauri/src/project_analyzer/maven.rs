@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use crate::{errors::AppError, model::version_constraint::VersionConstraint};
+
+use super::ProjectRequirement;
+
+pub const POM_FILE_NAME: &str = "pom.xml";
+
+const COMPILER_PLUGIN: &str = "maven-compiler-plugin";
+
+/// Read the java requirements of a `pom.xml`, from the compiler properties
+/// (`maven.compiler.release`, `source` and `target`), the compiler plugin
+/// configuration and the `requireJavaVersion` rule of the enforcer plugin.
+///
+/// `release` overrides `source` and `target`, like the compiler plugin does.
+/// Properties like `${java.version}` are resolved from the pom properties.
+pub fn parse_pom(content: &str) -> Result<Vec<ProjectRequirement>, AppError> {
+    let doc = roxmltree::Document::parse(content)
+        .map_err(|e| AppError::new(format!("Invalid pom.xml: {}", e)))?;
+    let project = doc.root_element();
+
+    let properties: HashMap<&str, &str> = project
+        .children()
+        .filter(|node| node.has_tag_name("properties"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.is_element())
+        .map(|node| (node.tag_name().name(), node.text().unwrap_or("").trim()))
+        .collect();
+    let resolve = |value: &str| resolve_property(value, &properties);
+
+    let plugin_config = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("plugin"))
+        .find(|node| {
+            node.children().any(|child| {
+                child.has_tag_name("artifactId")
+                    && child.text().map(|text| text.trim())
+                        == Some(COMPILER_PLUGIN)
+            })
+        })
+        .and_then(|node| {
+            node.children()
+                .find(|child| child.has_tag_name("configuration"))
+        });
+    let compiler_option = |name: &str| {
+        let configured = plugin_config.and_then(|config| {
+            config
+                .children()
+                .find(|child| child.has_tag_name(name))
+                .and_then(|child| child.text())
+        });
+        let key = format!("maven.compiler.{}", name);
+        configured
+            .or_else(|| properties.get(key.as_str()).copied())
+            .and_then(resolve)
+            .map(|value| (key, value))
+    };
+
+    let mut requirements = vec![];
+    let compiler_options = match compiler_option("release") {
+        Some(release) => vec![release],
+        None => ["source", "target"]
+            .iter()
+            .filter_map(|name| compiler_option(name))
+            .collect(),
+    };
+    for (key, value) in compiler_options {
+        // Compiling for a version requires that version or newer
+        if let Ok(version) = format!("{}+", value).parse() {
+            requirements.push(ProjectRequirement::new(
+                POM_FILE_NAME,
+                &key,
+                version,
+            ));
+        }
+    }
+
+    let enforced = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("requireJavaVersion"))
+        .filter_map(|node| {
+            node.children()
+                .find(|child| child.has_tag_name("version"))
+                .and_then(|child| child.text())
+        })
+        .filter_map(resolve)
+        .filter_map(|value| parse_version_range(&value));
+    for version in enforced {
+        requirements.push(ProjectRequirement::new(
+            POM_FILE_NAME,
+            "requireJavaVersion",
+            version,
+        ));
+    }
+
+    Ok(requirements)
+}
+
+/// Check if a `pom.xml` declares modules, i.e. it aggregates the projects
+/// in its sub dirs. Invalid poms are not aggregators.
+pub fn is_aggregator(content: &str) -> bool {
+    let Ok(doc) = roxmltree::Document::parse(content) else {
+        return false;
+    };
+    doc.root_element()
+        .children()
+        .filter(|node| node.has_tag_name("modules"))
+        .flat_map(|node| node.children())
+        .any(|node| node.has_tag_name("module"))
+}
+
+/// Resolve `${name}` references, returns `None` if it cannot be resolved.
+fn resolve_property(
+    value: &str,
+    properties: &HashMap<&str, &str>,
+) -> Option<String> {
+    let mut value = value.trim();
+    // Limit the depth in case of cyclic references
+    for _ in 0..8 {
+        let Some(name) = value
+            .strip_prefix("${")
+            .and_then(|item| item.strip_suffix('}'))
+        else {
+            return (!value.is_empty()).then(|| value.to_string());
+        };
+        value = properties.get(name)?;
+    }
+    None
+}
+
+/// Convert a Maven version range to a constraint, e.g. `[17,21)` to
+/// `>=17 <21`. A plain version like `17` means `17` or newer for the
+/// enforcer. Unions like `(,1.8],[11,)` are not supported.
+pub fn parse_version_range(value: &str) -> Option<VersionConstraint> {
+    let value = value.trim();
+    if !value.starts_with(['[', '(']) {
+        return format!("{}+", value).parse().ok();
+    }
+    if value.matches(',').count() > 1 {
+        return None;
+    }
+    let lower_inclusive = value.starts_with('[');
+    let upper_inclusive = value.ends_with(']');
+    let inner = value.get(1..value.len() - 1)?;
+    let constraint = match inner.split_once(',') {
+        None => format!("={}", inner.trim()),
+        Some((lower, upper)) => {
+            let mut terms = vec![];
+            if !lower.trim().is_empty() {
+                let op = if lower_inclusive { ">=" } else { ">" };
+                terms.push(format!("{}{}", op, lower.trim()));
+            }
+            if !upper.trim().is_empty() {
+                let op = if upper_inclusive { "<=" } else { "<" };
+                terms.push(format!("{}{}", op, upper.trim()));
+            }
+            terms.join(" ")
+        }
+    };
+    constraint.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_pom, parse_version_range};
+    use crate::model::java_version::JavaVersion;
+
+    fn version(value: &str) -> JavaVersion {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_pom() {
+        let pom = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <project xmlns="http://maven.apache.org/POM/4.0.0">
+              <properties>
+                <java.version>17</java.version>
+                <maven.compiler.source>1.8</maven.compiler.source>
+                <maven.compiler.release>${java.version}</maven.compiler.release>
+              </properties>
+              <build>
+                <plugins>
+                  <plugin>
+                    <artifactId>maven-enforcer-plugin</artifactId>
+                    <configuration>
+                      <rules>
+                        <requireJavaVersion>
+                          <version>[17,22)</version>
+                        </requireJavaVersion>
+                      </rules>
+                    </configuration>
+                  </plugin>
+                </plugins>
+              </build>
+            </project>"#;
+        let requirements = parse_pom(pom).unwrap();
+        assert_eq!(2, requirements.len());
+        assert_eq!("maven.compiler.release", requirements[0].key);
+        assert_eq!("requireJavaVersion", requirements[1].key);
+        let release = requirements[0].version.as_ref().unwrap();
+        assert!(release.matches(&version("21.0.1")));
+        assert!(!release.matches(&version("11.0.21")));
+    }
+
+    #[test]
+    fn test_parse_compiler_plugin() {
+        let pom = r#"<project>
+              <build>
+                <plugins>
+                  <plugin>
+                    <artifactId>maven-compiler-plugin</artifactId>
+                    <configuration>
+                      <source>1.8</source>
+                      <target>11</target>
+                    </configuration>
+                  </plugin>
+                </plugins>
+              </build>
+            </project>"#;
+        let requirements = parse_pom(pom).unwrap();
+        let keys: Vec<&str> =
+            requirements.iter().map(|item| item.key.as_str()).collect();
+        assert_eq!(
+            vec!["maven.compiler.source", "maven.compiler.target"],
+            keys
+        );
+    }
+
+    #[test]
+    fn test_parse_version_range() {
+        let matches = |range: &str, value: &str| {
+            parse_version_range(range).unwrap().matches(&version(value))
+        };
+        assert!(matches("[17,22)", "21.0.1"));
+        assert!(!matches("[17,22)", "22"));
+        assert!(matches("(11,]", "17"));
+        assert!(!matches("(11,]", "11"));
+        assert!(matches("1.8", "17"));
+        assert!(matches("[17]", "17"));
+        assert!(parse_version_range("(,1.8],[11,)").is_none());
+    }
+}
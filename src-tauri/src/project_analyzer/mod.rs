@@ -0,0 +1,208 @@
+pub mod gradle;
+pub mod maven;
+pub mod workspace;
+
+use std::path::Path;
+
+use crate::{
+    errors::AppError,
+    jdk_selector::{self, JdkRequirement},
+    model::{
+        distribution::Distribution, jdk::Jdk,
+        version_constraint::VersionConstraint,
+    },
+};
+
+/// A java requirement declared by a project file.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ProjectRequirement {
+    /// The file path, relative to the project dir if it is inside.
+    pub file: String,
+    /// The declaration, e.g. `maven.compiler.release`.
+    pub key: String,
+    pub distribution: Option<Distribution>,
+    pub version: Option<VersionConstraint>,
+}
+
+impl ProjectRequirement {
+    fn new(file: &str, key: &str, version: VersionConstraint) -> Self {
+        Self {
+            file: file.to_string(),
+            key: key.to_string(),
+            distribution: None,
+            version: Some(version),
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ProjectAnalysis {
+    pub requirements: Vec<ProjectRequirement>,
+    /// All requirements combined, `None` if nothing is declared.
+    pub requirement: Option<String>,
+    /// The newest stored jdk which satisfies all requirements.
+    pub jdk: Option<Jdk>,
+}
+
+/// Find the java requirements of a project dir from `pom.xml`,
+/// `build.gradle(.kts)`, the Gradle wrapper and `.java-version`. Only the
+/// files of the dir itself are read, pin files of parent dirs are not.
+///
+/// The Gradle wrapper only requires a jdk which can run Gradle, it is
+/// skipped if a toolchain is declared since Gradle provides the toolchain
+/// apart from that jdk.
+pub fn find_requirements(
+    dir: &Path,
+) -> Result<Vec<ProjectRequirement>, AppError> {
+    let mut requirements = vec![];
+
+    let pom = dir.join(maven::POM_FILE_NAME);
+    if pom.is_file() {
+        requirements.extend(maven::parse_pom(&read_file(&pom)?)?);
+    }
+
+    for name in gradle::BUILD_FILE_NAMES {
+        let path = dir.join(name);
+        if path.is_file() {
+            let content = read_file(&path)?;
+            requirements.extend(gradle::parse_build_script(name, &content));
+        }
+    }
+
+    let has_toolchain = requirements
+        .iter()
+        .any(|item| gradle::TOOLCHAIN_KEYS.contains(&item.key.as_str()));
+    let wrapper = dir.join(gradle::WRAPPER_PROPERTIES_PATH);
+    if wrapper.is_file() && !has_toolchain {
+        requirements
+            .extend(gradle::parse_wrapper_properties(&read_file(&wrapper)?));
+    }
+
+    let pin_file = dir.join(jdk_selector::PIN_FILE_NAME);
+    if pin_file.is_file() {
+        let pin = jdk_selector::read_pin_file(&pin_file)?;
+        requirements.push(ProjectRequirement {
+            file: jdk_selector::PIN_FILE_NAME.to_string(),
+            key: jdk_selector::PIN_FILE_NAME.to_string(),
+            distribution: pin.distribution,
+            version: pin.version,
+        });
+    }
+
+    Ok(requirements)
+}
+
+/// Find the requirements of a project and the best matching jdk.
+pub fn analyze_project(
+    dir: &Path,
+    jdks: &[Jdk],
+) -> Result<ProjectAnalysis, AppError> {
+    let requirements = find_requirements(dir)?;
+    let combined = combine_requirements(&requirements);
+    let jdk = combined
+        .as_ref()
+        .and_then(|requirement| jdk_selector::select_best(jdks, requirement))
+        .cloned();
+    Ok(ProjectAnalysis {
+        requirement: combined.map(|item| item.to_string()),
+        requirements,
+        jdk,
+    })
+}
+
+/// Combine requirements into one, the first declared distribution is used.
+pub fn combine_requirements(
+    requirements: &[ProjectRequirement],
+) -> Option<JdkRequirement> {
+    if requirements.is_empty() {
+        return None;
+    }
+    let distribution = requirements.iter().find_map(|item| item.distribution);
+    let version = requirements
+        .iter()
+        .filter_map(|item| item.version.as_ref())
+        .fold(None, |acc: Option<VersionConstraint>, item| match acc {
+            Some(acc) => Some(acc.and(item)),
+            None => Some(item.clone()),
+        });
+    Some(JdkRequirement {
+        distribution,
+        version,
+    })
+}
+
+fn read_file(path: &Path) -> Result<String, AppError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        AppError::new(format!("Cannot read '{}': {}", path.display(), e))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{combine_requirements, find_requirements, ProjectRequirement};
+    use crate::{
+        model::{distribution::Distribution, jdk::Jdk},
+        test_util::{jdk, temp_dir},
+    };
+
+    #[test]
+    fn test_combine_requirements() {
+        let requirements = [
+            ProjectRequirement::new(
+                "pom.xml",
+                "release",
+                "11+".parse().unwrap(),
+            ),
+            ProjectRequirement::new(
+                "pom.xml",
+                "enforcer",
+                "<21".parse().unwrap(),
+            ),
+            ProjectRequirement {
+                file: ".java-version".to_string(),
+                key: ".java-version".to_string(),
+                distribution: Some(Distribution::Temurin),
+                version: None,
+            },
+        ];
+        let requirement = combine_requirements(&requirements).unwrap();
+        assert_eq!(Some(Distribution::Temurin), requirement.distribution);
+        assert!(requirement.matches(&Jdk {
+            distribution: Distribution::Temurin,
+            ..jdk("/opt/jdk-17.0.9/bin", "17.0.9")
+        }));
+        assert!(!requirement.matches(&Jdk {
+            distribution: Distribution::Temurin,
+            ..jdk("/opt/jdk-21.0.1/bin", "21.0.1")
+        }));
+        assert!(combine_requirements(&[]).is_none());
+    }
+
+    #[test]
+    fn test_find_requirements_with_toolchain() {
+        let dir = temp_dir("project-toolchain");
+        let wrapper = dir.join("gradle").join("wrapper");
+        std::fs::create_dir_all(&wrapper).unwrap();
+        std::fs::write(
+            wrapper.join("gradle-wrapper.properties"),
+            "distributionUrl=https\\://services.gradle.org/distributions/\
+             gradle-7.3-bin.zip\n",
+        )
+        .unwrap();
+        let with_wrapper = find_requirements(&dir);
+        std::fs::write(
+            dir.join("build.gradle"),
+            "kotlin { jvmToolchain(21) }\n",
+        )
+        .unwrap();
+        let with_toolchain = find_requirements(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let keys = |requirements: Vec<ProjectRequirement>| -> Vec<String> {
+            requirements.into_iter().map(|item| item.key).collect()
+        };
+        assert_eq!(vec!["distributionUrl"], keys(with_wrapper.unwrap()));
+        // Gradle 7.3 cannot run on java 21, but it can compile with it
+        assert_eq!(vec!["jvmToolchain"], keys(with_toolchain.unwrap()));
+    }
+}
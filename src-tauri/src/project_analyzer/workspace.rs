@@ -0,0 +1,406 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    errors::AppError,
+    jdk_selector::{self, PIN_FILE_NAME},
+    model::{jdk::Jdk, version_constraint::VersionConstraint},
+};
+
+use super::{combine_requirements, find_requirements, gradle, maven};
+
+/// Projects are not searched deeper than this.
+const MAX_DEPTH: usize = 6;
+
+/// Build outputs and dependency dirs which never contain projects.
+const SKIPPED_DIRS: [&str; 4] = ["node_modules", "target", "build", "out"];
+
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    /// A saved jdk satisfies the requirement.
+    Matched,
+    /// No saved jdk satisfies the requirement.
+    Missing,
+    /// The project does not declare a java version.
+    Unspecified,
+    /// The build files cannot be read.
+    Error,
+}
+
+impl ProjectStatus {
+    fn id(&self) -> &'static str {
+        match self {
+            ProjectStatus::Matched => "matched",
+            ProjectStatus::Missing => "missing",
+            ProjectStatus::Unspecified => "unspecified",
+            ProjectStatus::Error => "error",
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ProjectReport {
+    /// The project dir, relative to the workspace root.
+    pub dir: String,
+    pub status: ProjectStatus,
+    pub requirement: Option<String>,
+    /// The lowest java major which satisfies the requirement.
+    pub required_major: Option<u32>,
+    /// Where the requirements come from, e.g. `pom.xml (requireJavaVersion)`.
+    pub sources: Vec<String>,
+    pub jdk: Option<Jdk>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct WorkspaceReport {
+    pub root: String,
+    pub projects: Vec<ProjectReport>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ReportFormat {
+    /// Find the format by the file extension.
+    pub fn from_path(path: &Path) -> Result<Self, AppError> {
+        let extension = path
+            .extension()
+            .map(|item| item.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("json") => Ok(ReportFormat::Json),
+            Some("csv") => Ok(ReportFormat::Csv),
+            Some("md") | Some("markdown") => Ok(ReportFormat::Markdown),
+            _ => Err(AppError::new(format!(
+                "Unsupported report format '{}', use .json, .csv or .md",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Find projects under the root and resolve their jdks. A dir with a build
+/// file or a pin file is a project, its sub dirs are not reported
+/// separately unless it aggregates modules, e.g. a Maven pom with
+/// `<modules>` or a Gradle multi-project build. A pin file of the root alone
+/// does not make the root a project.
+pub fn scan_workspace(root: &Path, jdks: &[Jdk]) -> WorkspaceReport {
+    let mut dirs = vec![];
+    find_project_dirs(root, 0, &mut dirs);
+    dirs.sort();
+    let projects = dirs
+        .iter()
+        .map(|dir| analyze_project_dir(root, dir, jdks))
+        .collect();
+    WorkspaceReport {
+        root: root.to_string_lossy().to_string(),
+        projects,
+    }
+}
+
+fn find_project_dirs(dir: &Path, depth: usize, dirs: &mut Vec<PathBuf>) {
+    if is_project_dir(dir, depth > 0) {
+        dirs.push(dir.to_path_buf());
+        if !is_aggregator(dir) {
+            return;
+        }
+    }
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|item| item.ok()) {
+        let is_dir = entry.file_type().is_ok_and(|item| item.is_dir());
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !is_dir || name.starts_with('.') || SKIPPED_DIRS.contains(&&*name) {
+            continue;
+        }
+        find_project_dirs(&entry.path(), depth + 1, dirs);
+    }
+}
+
+fn is_project_dir(dir: &Path, check_pin_file: bool) -> bool {
+    let has_build_file = gradle::BUILD_FILE_NAMES
+        .iter()
+        .chain([maven::POM_FILE_NAME].iter())
+        .any(|name| dir.join(name).is_file());
+    has_build_file || (check_pin_file && dir.join(PIN_FILE_NAME).is_file())
+}
+
+fn is_aggregator(dir: &Path) -> bool {
+    let pom = std::fs::read_to_string(dir.join(maven::POM_FILE_NAME));
+    if pom.is_ok_and(|content| maven::is_aggregator(&content)) {
+        return true;
+    }
+    gradle::SETTINGS_FILE_NAMES.iter().any(|name| {
+        std::fs::read_to_string(dir.join(name))
+            .is_ok_and(|content| content.contains("include"))
+    })
+}
+
+fn analyze_project_dir(root: &Path, dir: &Path, jdks: &[Jdk]) -> ProjectReport {
+    let relative = dir.strip_prefix(root).unwrap_or(dir);
+    let relative = if relative.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        relative.to_string_lossy().replace('\\', "/")
+    };
+    let mut report = ProjectReport {
+        dir: relative,
+        status: ProjectStatus::Unspecified,
+        requirement: None,
+        required_major: None,
+        sources: vec![],
+        jdk: None,
+        error: None,
+    };
+
+    let requirements = match find_requirements(dir) {
+        Ok(requirements) => requirements,
+        Err(e) => {
+            report.status = ProjectStatus::Error;
+            report.error = Some(e.message);
+            return report;
+        }
+    };
+    let Some(requirement) = combine_requirements(&requirements) else {
+        return report;
+    };
+    report.sources = requirements
+        .iter()
+        .map(|item| format!("{} ({})", item.file, item.key))
+        .collect();
+    report.requirement = Some(requirement.to_string());
+    report.required_major = requirement.version.as_ref().and_then(lowest_major);
+    report.jdk = jdk_selector::select_best(jdks, &requirement).cloned();
+    report.status = if report.jdk.is_some() {
+        ProjectStatus::Matched
+    } else {
+        ProjectStatus::Missing
+    };
+    report
+}
+
+/// Find the lowest major version which satisfies the constraint.
+fn lowest_major(constraint: &VersionConstraint) -> Option<u32> {
+    (1..=99).find(|major| {
+        major
+            .to_string()
+            .parse()
+            .is_ok_and(|version| constraint.matches(&version))
+    })
+}
+
+/// Render the report as JSON, CSV or a Markdown table with a summary of the
+/// required majors.
+pub fn render_report(
+    report: &WorkspaceReport,
+    format: ReportFormat,
+) -> Result<String, AppError> {
+    match format {
+        ReportFormat::Json => Ok(serde_json::to_string_pretty(report)?),
+        ReportFormat::Csv => Ok(render_csv(report)),
+        ReportFormat::Markdown => Ok(render_markdown(report)),
+    }
+}
+
+const COLUMNS: [&str; 7] = [
+    "project",
+    "status",
+    "required_major",
+    "requirement",
+    "sources",
+    "jdk",
+    "jdk_path",
+];
+
+fn row(project: &ProjectReport) -> [String; 7] {
+    let jdk = project.jdk.as_ref();
+    [
+        project.dir.clone(),
+        project.status.id().to_string(),
+        project
+            .required_major
+            .map(|item| item.to_string())
+            .unwrap_or_default(),
+        project.requirement.clone().unwrap_or_default(),
+        project.sources.join("; "),
+        jdk.map(|jdk| format!("{} {}", jdk.name, jdk.version))
+            .unwrap_or_else(|| project.error.clone().unwrap_or_default()),
+        jdk.map(|jdk| jdk.home_dir().to_string_lossy().to_string())
+            .unwrap_or_default(),
+    ]
+}
+
+fn render_csv(report: &WorkspaceReport) -> String {
+    let escape = |value: &str| {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    };
+    let mut lines = vec![COLUMNS.join(",")];
+    for project in &report.projects {
+        let cells: Vec<String> =
+            row(project).iter().map(|item| escape(item)).collect();
+        lines.push(cells.join(","));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn render_markdown(report: &WorkspaceReport) -> String {
+    let escape = |value: &str| value.replace('|', "\\|");
+    let mut lines = vec![
+        format!("# JDK requirements of `{}`", report.root),
+        String::new(),
+    ];
+
+    // How many projects require each major, and how many of them have no
+    // matching jdk.
+    let mut majors: BTreeMap<u32, (usize, usize)> = BTreeMap::new();
+    for project in &report.projects {
+        if let Some(major) = project.required_major {
+            let entry = majors.entry(major).or_default();
+            entry.0 += 1;
+            if project.status == ProjectStatus::Missing {
+                entry.1 += 1;
+            }
+        }
+    }
+    if !majors.is_empty() {
+        lines.push("| Java | Projects | Missing |".to_string());
+        lines.push("| --- | --- | --- |".to_string());
+        for (major, (count, missing)) in majors {
+            lines.push(format!("| {} | {} | {} |", major, count, missing));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push(format!("| {} |", COLUMNS.join(" | ")));
+    lines.push(format!("|{}", " --- |".repeat(COLUMNS.len())));
+    for project in &report.projects {
+        let cells: Vec<String> =
+            row(project).iter().map(|item| escape(item)).collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::{render_report, scan_workspace, ProjectStatus, ReportFormat};
+    use crate::{
+        model::jdk::Jdk,
+        test_util::{jdk, temp_dir},
+    };
+
+    #[test]
+    fn test_scan_workspace() {
+        let root = temp_dir("workspace-report");
+        let files = [
+            (".java-version", "21\n"),
+            (
+                "billing/pom.xml",
+                "<project><properties>\
+                 <maven.compiler.release>17</maven.compiler.release>\
+                 </properties></project>",
+            ),
+            ("billing/api/pom.xml", "<project />"),
+            ("legacy/.java-version", "1.8\n"),
+            ("tools/cli/build.gradle", "plugins { id 'java' }\n"),
+            ("tools/cli/node_modules/x/pom.xml", "<project />"),
+        ];
+        for (file, content) in files {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let jdks = [Jdk {
+            name: "Temurin".to_string(),
+            ..jdk("/opt/jdk-21/bin", "21.0.1")
+        }];
+
+        let report = scan_workspace(&root, &jdks);
+        let _ = std::fs::remove_dir_all(&root);
+
+        let projects: Vec<(&str, ProjectStatus, Option<u32>)> = report
+            .projects
+            .iter()
+            .map(|item| (item.dir.as_str(), item.status, item.required_major))
+            .collect();
+        // The pin file of the root is not inherited
+        assert_eq!(
+            vec![
+                ("billing", ProjectStatus::Matched, Some(17)),
+                ("legacy", ProjectStatus::Missing, Some(8)),
+                ("tools/cli", ProjectStatus::Unspecified, None),
+            ],
+            projects
+        );
+
+        let csv = render_report(&report, ReportFormat::Csv).unwrap();
+        assert!(csv.starts_with("project,status,required_major,"));
+        assert!(csv.contains("\nbilling,matched,17,17+,pom.xml"));
+        let markdown = render_report(&report, ReportFormat::Markdown).unwrap();
+        assert!(markdown.contains("| 8 | 1 | 1 |"));
+        assert!(render_report(&report, ReportFormat::Json).is_ok());
+    }
+
+    #[test]
+    fn test_scan_aggregator_workspace() {
+        let root = temp_dir("workspace-aggregator");
+        let files = [
+            (
+                "pom.xml",
+                "<project><modules><module>app</module>\
+                 <module>lib</module></modules></project>",
+            ),
+            (
+                "app/pom.xml",
+                "<project><properties>\
+                 <maven.compiler.release>21</maven.compiler.release>\
+                 </properties></project>",
+            ),
+            ("lib/pom.xml", "<project />"),
+            ("lib/src/pom.xml", "<project />"),
+        ];
+        for (file, content) in files {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let report = scan_workspace(&root, &[]);
+        let _ = std::fs::remove_dir_all(&root);
+
+        let projects: Vec<(&str, Option<u32>)> = report
+            .projects
+            .iter()
+            .map(|item| (item.dir.as_str(), item.required_major))
+            .collect();
+        assert_eq!(
+            vec![(".", None), ("app", Some(21)), ("lib", None)],
+            projects
+        );
+    }
+
+    #[test]
+    fn test_report_format_from_path() {
+        let format = |path: &str| ReportFormat::from_path(Path::new(path));
+        assert_eq!(ReportFormat::Csv, format("report.CSV").unwrap());
+        assert_eq!(ReportFormat::Markdown, format("report.md").unwrap());
+        assert!(format("report.txt").is_err());
+    }
+}
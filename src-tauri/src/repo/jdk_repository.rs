@@ -1,10 +1,15 @@
 use std::{
     collections::HashMap,
     fs::{self, File, OpenOptions},
+    sync::Mutex,
 };
 
 use crate::{errors::AppError, model::jdk::Jdk, util::paths};
 
+/// Writes read the saved jdks first, they are serialized so that
+/// concurrent writes do not lose each other's changes.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
 pub struct JdkRepository {}
 
 #[allow(dead_code)]
@@ -21,60 +26,82 @@ impl JdkRepository {
 
     /// Add a jdk.
     pub fn add(&self, jdk: &Jdk) -> Result<(), AppError> {
-        let mut all = self.get_all().unwrap_or_default();
-        if all.iter().any(|item| item.path == jdk.path) {
-            return self.update(jdk);
-        }
-        all.push(jdk.clone());
-        self.save_jdks(&all)
+        self.update_all(|all| {
+            match all.iter().position(|item| item.path == jdk.path) {
+                Some(index) => all[index] = jdk.clone(),
+                None => all.push(jdk.clone()),
+            }
+            Ok(())
+        })
     }
 
     /// Add a list of jdk.
     pub fn add_all(&self, jdks: &Vec<Jdk>) -> Result<(), AppError> {
-        let mut all = self.get_all().unwrap_or_default();
-        let mut map = HashMap::<String, usize>::new();
-        for (index, jdk) in all.iter().enumerate() {
-            map.insert(jdk.path.clone(), index);
-        }
-        for jdk in jdks {
-            if let Some(index) = map.get(&jdk.path) {
-                // Already exists, update
-                all[*index] = jdk.clone();
-            } else {
-                // Add to last
-                all.push(jdk.clone())
+        self.update_all(|all| {
+            let mut map = HashMap::<String, usize>::new();
+            for (index, jdk) in all.iter().enumerate() {
+                map.insert(jdk.path.clone(), index);
             }
-        }
-        self.save_jdks(&all)
+            for jdk in jdks {
+                if let Some(index) = map.get(&jdk.path) {
+                    // Already exists, update
+                    all[*index] = jdk.clone();
+                } else {
+                    // Add to last
+                    all.push(jdk.clone())
+                }
+            }
+            Ok(())
+        })
     }
 
     /// Update a jdk.
     pub fn update(&self, jdk: &Jdk) -> Result<(), AppError> {
-        let mut all = self.get_all().unwrap_or_default();
-        let Some(index) = all.iter().position(|item| item.path == jdk.path) else {
-            return  Err(AppError::new(format!("Jdk '{}' does not exist.", jdk.path)));
-        };
-        all[index] = jdk.clone();
-        self.save_jdks(&all)
+        self.update_all(|all| {
+            let Some(index) = all.iter().position(|item| item.path == jdk.path) else {
+                return  Err(AppError::new(format!("Jdk '{}' does not exist.", jdk.path)));
+            };
+            all[index] = jdk.clone();
+            Ok(())
+        })
     }
 
     /// Remove a jdk by its path.
     pub fn remove_by_path(&self, path: &str) -> Result<(), AppError> {
-        let mut all = self.get_all().unwrap_or_default();
-        let Some(index) = all.iter().position(|item| item.path == path) else {
-            return  Err(AppError::new(format!("Jdk '{}' does not exist.", path)));
-        };
-        all.remove(index);
-        self.save_jdks(&all)
+        self.update_all(|all| {
+            let Some(index) = all.iter().position(|item| item.path == path) else {
+                return  Err(AppError::new(format!("Jdk '{}' does not exist.", path)));
+            };
+            all.remove(index);
+            Ok(())
+        })
+    }
+
+    /// Remove jdks by their paths, unknown paths are ignored.
+    pub fn remove_all_by_paths(
+        &self,
+        paths: &[String],
+    ) -> Result<(), AppError> {
+        self.update_all(|all| {
+            all.retain(|item| !paths.contains(&item.path));
+            Ok(())
+        })
     }
 
     /// Remove a jdk.
     pub fn remove(&self, jdk: &Jdk) -> Result<(), AppError> {
+        self.remove_by_path(&jdk.path)
+    }
+
+    /// Change the saved jdks, they are read again right before the change
+    /// and no other write happens in between. Nothing is saved on errors.
+    pub fn update_all<F>(&self, f: F) -> Result<(), AppError>
+    where
+        F: FnOnce(&mut Vec<Jdk>) -> Result<(), AppError>,
+    {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         let mut all = self.get_all().unwrap_or_default();
-        let Some(index) = all.iter().position(|item| item.path == jdk.path) else {
-            return  Err(AppError::new(format!("Jdk '{}' does not exist.", jdk.path)));
-        };
-        all.remove(index);
+        f(&mut all)?;
         self.save_jdks(&all)
     }
 
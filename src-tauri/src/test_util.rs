@@ -0,0 +1,29 @@
+//! Helpers shared by the unit tests.
+
+use std::path::PathBuf;
+
+use crate::model::jdk::Jdk;
+
+/// An empty dir in the temp dir, unique to the test process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "jdk-switcher-test-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A valid x64 jdk of an unknown distribution, `path` is its bin dir.
+pub fn jdk(path: &str, version: &str) -> Jdk {
+    serde_json::from_value(serde_json::json!({
+        "name": "",
+        "path": path,
+        "version": version,
+        "arch": "x64",
+        "is_valid": true,
+    }))
+    .unwrap()
+}
@@ -8,10 +8,53 @@ pub fn app_data_dir() -> PathBuf {
     AppDirs::new(Some(APP_DATA_DIR), false).unwrap().data_dir
 }
 
+/// The home dir of the current user.
+pub fn user_home_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let home = std::env::var_os("USERPROFILE");
+    #[cfg(not(target_os = "windows"))]
+    let home = std::env::var_os("HOME");
+    home.filter(|item| !item.is_empty()).map(PathBuf::from)
+}
+
+/// The config dir of the current user, e.g. `%APPDATA%` on Windows and
+/// `~/.config` on Linux.
+pub fn user_config_dir() -> Option<PathBuf> {
+    AppDirs::new(None, false).map(|dirs| dirs.config_dir)
+}
+
+/// The Gradle user home, `GRADLE_USER_HOME` or `~/.gradle`.
+pub fn gradle_user_home() -> Option<PathBuf> {
+    std::env::var_os("GRADLE_USER_HOME")
+        .filter(|item| !item.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| user_home_dir().map(|home| home.join(".gradle")))
+}
+
+/// Jdks installed by the app.
+pub fn managed_jdks_dir() -> PathBuf {
+    app_data_dir().join("jdks")
+}
+
+/// Partially downloaded packages, kept to resume later.
+pub fn downloads_dir() -> PathBuf {
+    app_data_dir().join("downloads")
+}
+
+/// Uninstalled jdks are moved here before they are deleted.
+pub fn trash_dir() -> PathBuf {
+    app_data_dir().join("trash")
+}
+
 pub fn jdks_json_path() -> PathBuf {
     app_data_dir().join("data").join("jdks.json")
 }
 
+/// A lifecycle table downloaded to replace the bundled one.
+pub fn lifecycle_json_path() -> PathBuf {
+    app_data_dir().join("data").join("lifecycle.json")
+}
+
 pub fn settings_json_path() -> PathBuf {
     app_data_dir().join("settings.json")
 }
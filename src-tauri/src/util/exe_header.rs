@@ -0,0 +1,171 @@
+use std::{fs::File, io::Read, path::Path};
+
+use crate::{errors::AppError, model::arch::Arch};
+
+// Enough for the headers we read, the PE header offset is usually small.
+const HEADER_READ_LIMIT: u64 = 4096;
+
+/// Read the machine type from the header of an executable, PE (Windows),
+/// ELF (Linux) and Mach-O (macOS) executables are supported.
+pub fn read_exe_arch(path: &Path) -> Result<Arch, AppError> {
+    let mut bytes = vec![];
+    File::open(path)?
+        .take(HEADER_READ_LIMIT)
+        .read_to_end(&mut bytes)?;
+    parse_exe_arch(&bytes).ok_or_else(|| {
+        AppError::new(format!(
+            "Unsupported executable format: '{}'",
+            path.display()
+        ))
+    })
+}
+
+fn parse_exe_arch(bytes: &[u8]) -> Option<Arch> {
+    if bytes.starts_with(b"MZ") {
+        parse_pe_arch(bytes)
+    } else if bytes.starts_with(b"\x7fELF") {
+        parse_elf_arch(bytes)
+    } else {
+        parse_mach_o_arch(bytes)
+    }
+}
+
+fn parse_pe_arch(bytes: &[u8]) -> Option<Arch> {
+    let pe_offset = read_u32(bytes, 0x3c, false)? as usize;
+    if bytes.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+        return None;
+    }
+    let arch = match read_u16(bytes, pe_offset + 4, false)? {
+        0x014c => Arch::X86,
+        0x8664 => Arch::X64,
+        0x01c4 => Arch::Arm,
+        0xaa64 => Arch::Arm64,
+        _ => Arch::Unknown,
+    };
+    Some(arch)
+}
+
+fn parse_elf_arch(bytes: &[u8]) -> Option<Arch> {
+    let big_endian = *bytes.get(5)? == 2;
+    let arch = match read_u16(bytes, 18, big_endian)? {
+        3 => Arch::X86,
+        62 => Arch::X64,
+        40 => Arch::Arm,
+        183 => Arch::Arm64,
+        21 => Arch::Ppc64,
+        22 => Arch::S390x,
+        243 => Arch::RiscV64,
+        _ => Arch::Unknown,
+    };
+    Some(arch)
+}
+
+fn parse_mach_o_arch(bytes: &[u8]) -> Option<Arch> {
+    match read_u32(bytes, 0, true)? {
+        // Universal binary, prefer the host architecture.
+        0xcafebabe => {
+            let count = read_u32(bytes, 4, true)? as usize;
+            let archs: Vec<Arch> = (0..count)
+                .map_while(|index| read_u32(bytes, 8 + index * 20, true))
+                .map(mach_o_cpu_type_to_arch)
+                .collect();
+            let host = Arch::host();
+            archs
+                .iter()
+                .find(|arch| **arch == host)
+                .or(archs.first())
+                .copied()
+        }
+        0xfeedface | 0xfeedfacf => {
+            Some(mach_o_cpu_type_to_arch(read_u32(bytes, 4, true)?))
+        }
+        0xcefaedfe | 0xcffaedfe => {
+            Some(mach_o_cpu_type_to_arch(read_u32(bytes, 4, false)?))
+        }
+        _ => None,
+    }
+}
+
+fn mach_o_cpu_type_to_arch(cpu_type: u32) -> Arch {
+    match cpu_type {
+        7 => Arch::X86,
+        0x0100_0007 => Arch::X64,
+        12 => Arch::Arm,
+        0x0100_000c => Arch::Arm64,
+        0x0100_0012 => Arch::Ppc64,
+        _ => Arch::Unknown,
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let value: [u8; 2] = bytes.get(offset..offset + 2)?.try_into().ok()?;
+    if big_endian {
+        Some(u16::from_be_bytes(value))
+    } else {
+        Some(u16::from_le_bytes(value))
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let value: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    if big_endian {
+        Some(u32::from_be_bytes(value))
+    } else {
+        Some(u32::from_le_bytes(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_exe_arch;
+    use crate::model::arch::Arch;
+
+    fn pe_header(machine: u16) -> Vec<u8> {
+        let mut bytes = vec![0u8; 0x90];
+        bytes[0..2].copy_from_slice(b"MZ");
+        bytes[0x3c..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        bytes[0x80..0x84].copy_from_slice(b"PE\0\0");
+        bytes[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_parse_pe_arch() {
+        assert_eq!(Some(Arch::X64), parse_exe_arch(&pe_header(0x8664)));
+        assert_eq!(Some(Arch::X86), parse_exe_arch(&pe_header(0x014c)));
+        assert_eq!(Some(Arch::Arm64), parse_exe_arch(&pe_header(0xaa64)));
+    }
+
+    #[test]
+    fn test_parse_elf_arch() {
+        let mut bytes = vec![0u8; 64];
+        bytes[0..4].copy_from_slice(b"\x7fELF");
+        bytes[4] = 2; // 64-bit
+        bytes[5] = 1; // Little endian
+        bytes[18..20].copy_from_slice(&183u16.to_le_bytes());
+        assert_eq!(Some(Arch::Arm64), parse_exe_arch(&bytes));
+    }
+
+    #[test]
+    fn test_parse_mach_o_arch() {
+        let mut bytes = vec![0u8; 32];
+        bytes[0..4].copy_from_slice(&0xfeedfacfu32.to_le_bytes());
+        bytes[4..8].copy_from_slice(&0x0100_0007u32.to_le_bytes());
+        assert_eq!(Some(Arch::X64), parse_exe_arch(&bytes));
+
+        // Universal binary with x64 and arm64 slices.
+        let mut bytes = vec![0u8; 48];
+        bytes[0..4].copy_from_slice(&0xcafebabeu32.to_be_bytes());
+        bytes[4..8].copy_from_slice(&2u32.to_be_bytes());
+        bytes[8..12].copy_from_slice(&0x0100_0007u32.to_be_bytes());
+        bytes[28..32].copy_from_slice(&0x0100_000cu32.to_be_bytes());
+        let arch = parse_exe_arch(&bytes).unwrap();
+        assert!(arch == Arch::X64 || arch == Arch::Arm64);
+    }
+
+    #[test]
+    fn test_parse_unknown_format() {
+        assert_eq!(None, parse_exe_arch(b"#!/bin/sh\n"));
+        assert_eq!(None, parse_exe_arch(b""));
+    }
+}
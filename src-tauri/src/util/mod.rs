@@ -1,5 +1,9 @@
+pub mod cancellation_token;
 pub mod cargo_manifest;
+#[cfg(windows)]
 pub mod env;
+pub mod exe_header;
+pub mod release_file;
 
 mod app_paths;
 mod command_exe_path_finder;
@@ -0,0 +1,36 @@
+use std::{collections::HashMap, path::Path};
+
+/// Read the `release` file of a jdk home, which contains lines like
+/// `JAVA_VERSION="17.0.9"`.
+pub fn read_release_file(jdk_home: &Path) -> Option<HashMap<String, String>> {
+    let content = std::fs::read_to_string(jdk_home.join("release")).ok()?;
+    Some(parse_release_file(&content))
+}
+
+pub fn parse_release_file(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim().trim_matches('"');
+            (key.trim().to_string(), value.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_release_file;
+
+    #[test]
+    fn test_parse_release_file() {
+        let release = parse_release_file(
+            "IMPLEMENTOR=\"Eclipse Adoptium\"\n\
+             JAVA_VERSION=\"17.0.9\"\n\
+             MODULES=\"java.base java.compiler\"\n",
+        );
+        assert_eq!("Eclipse Adoptium", release["IMPLEMENTOR"]);
+        assert_eq!("17.0.9", release["JAVA_VERSION"]);
+        assert_eq!("java.base java.compiler", release["MODULES"]);
+    }
+}
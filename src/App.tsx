@@ -1,27 +1,81 @@
 import {
+  ArchiveBoxArrowDownIcon,
+  ArchiveBoxXMarkIcon,
+  ArrowUpCircleIcon,
+  ArrowDownTrayIcon,
+  ArrowPathIcon,
+  ArrowUpTrayIcon,
+  BellAlertIcon,
+  Cog6ToothIcon,
+  CommandLineIcon,
   ComputerDesktopIcon,
+  CloudArrowDownIcon,
   CubeTransparentIcon,
   CursorArrowRippleIcon,
+  DocumentMagnifyingGlassIcon,
   ExclamationTriangleIcon,
+  EyeIcon,
+  EyeSlashIcon,
   FolderOpenIcon,
   InformationCircleIcon,
+  MagnifyingGlassIcon,
   MoonIcon,
   PlusIcon,
+  ShieldCheckIcon,
   SunIcon,
+  TableCellsIcon,
   TrashIcon,
 } from "@heroicons/react/24/outline";
 import { invoke } from "@tauri-apps/api/tauri";
-import { open } from "@tauri-apps/plugin-dialog";
-import { useEffect, useMemo, useState } from "react";
+import { confirm, open, save } from "@tauri-apps/plugin-dialog";
+import { getCurrent } from "@tauri-apps/plugin-window";
+import { useCallback, useEffect, useMemo, useState } from "react";
 import { Toaster, toast } from "sonner";
 import "./App.css";
+import ExportIdeJdksDialog, { ExportPreview } from "./ExportIdeJdksDialog";
+import ImportIdeJdksDialog, { IdeKind } from "./ImportIdeJdksDialog";
+import InstallJdkDialog, { formatSize } from "./InstallJdkDialog";
 import JdkDirSelectorDialog from "./JdkDirSelectorDialog";
+import SettingsDialog from "./SettingsDialog";
 import CircularLoader from "./component/CircularLoader";
 import useTauriEvents from "./hook/useTauriEvents";
 import { AppTheme, AppUiState } from "./model/AppUiState";
+import {
+  CatalogPackage,
+  InstallResult,
+  UninstallReport,
+  UpdateReport,
+  UpgradeResult,
+} from "./model/Catalog";
+import { JarInspection } from "./model/JarInspection";
 import { Jdk } from "./model/Jdk";
+import { ProjectAnalysis } from "./model/ProjectAnalysis";
+import { ScanProgress } from "./model/ScanProgress";
 import applyAppTheme from "./hook/applyAppTheme";
 
+type ImportReport = {
+  added: string[];
+  updated: string[];
+  invalid: string[];
+};
+
+type IntegrityReport = {
+  checked: number;
+  modified: string[];
+  corrupted: string[];
+};
+
+type JarExit = {
+  path: string;
+  code: number | null;
+};
+
+type RescanSummary = {
+  found: number;
+  removed: number;
+  skipped: string[];
+};
+
 enum ToastDuration {
   Short = 1500,
   Normal = 2500,
@@ -32,24 +86,71 @@ enum ToastDuration {
 function App() {
   const uiState = useTauriEvents<AppUiState>("ui-state-stream", {
     jdks: [],
-    settings: { theme: AppTheme.Unknown, skip_dir_selection_hint: false },
+    discovered_jdks: [],
+    settings: {
+      theme: AppTheme.Unknown,
+      skip_dir_selection_hint: false,
+      hide_jres: false,
+      reject_jres: false,
+      scan_max_depth: 16,
+      scan_excludes: [],
+      scan_follow_links: false,
+      scan_roots: [],
+      rescan_on_startup: false,
+      verify_on_startup: false,
+      check_updates_on_startup: true,
+      discovery_offered: false,
+      sync_maven_toolchains: false,
+      sync_gradle_paths: false,
+      disco_catalog_enabled: true,
+      catalog_sources: [],
+      trusted_minisign_keys: [],
+      trusted_gpg_keys: [],
+      strict_signatures: false,
+      known_projects: [],
+      lifecycle_source: "",
+    },
   });
 
+  const scanProgress = useTauriEvents<ScanProgress | null>(
+    "scan-progress-stream",
+    null
+  );
+
   const theme = uiState.settings.theme;
 
   const [isLoadingJdks, setLoadingJdks] = useState(true);
 
   const [operatingMessage, setOperatingMessage] = useState<string | null>(null);
 
+  const [isScanning, setScanning] = useState(false);
+
   const [isShowJdkDirSelectorDialog, setShowJdkDirSelectorDialog] =
     useState(false);
 
   const [skipDirSelectionHint, setSkipDirSelectionHint] = useState(false);
 
+  const [distributionFilter, setDistributionFilter] = useState<string | null>(
+    null
+  );
+
+  const distributions = useMemo(() => {
+    return [...new Set(uiState.jdks.map((item) => item.distribution))];
+  }, [uiState.jdks]);
+
   const [currentJdk, jdks] = useMemo(() => {
     const curr = uiState.jdks.find((item) => item.is_current);
-    return [curr, uiState.jdks.filter((item) => !item.is_current)];
-  }, [uiState.jdks]);
+    return [
+      curr,
+      uiState.jdks.filter(
+        (item) =>
+          !item.is_current &&
+          (distributionFilter == null ||
+            item.distribution === distributionFilter) &&
+          !(uiState.settings.hide_jres && item.capabilities.javac !== true)
+      ),
+    ];
+  }, [uiState.jdks, distributionFilter, uiState.settings.hide_jres]);
 
   const toggleTheme = async () => {
     let nextTheme: AppTheme;
@@ -70,10 +171,22 @@ function App() {
     await invoke("update_app_theme", { theme: nextTheme });
   };
 
-  const removeJdk = (jdk: Jdk) => {
+  const removeJdk = async (jdk: Jdk) => {
     const name = `${jdk.name} ${jdk.version}`;
+    const isToolManaged =
+      jdk.source !== "manual" && jdk.source !== "installed";
+    if (isToolManaged) {
+      const confirmed = await confirm(
+        `JDK '${name}' is managed by ${sourceName(jdk)}, removing it from ` +
+          "the list may confuse the tool. Remove anyway?",
+        { title: "Remove JDK", type: "warning" }
+      );
+      if (!confirmed) {
+        return;
+      }
+    }
     setOperatingMessage(`Removing JDK '${name}'`);
-    invoke("remove_jdk_by_path", { path: jdk.path })
+    invoke("remove_jdk_by_path", { path: jdk.path, force: isToolManaged })
       .then(() => {
         toast.success(`Removed JDK '${name}'`, {
           duration: ToastDuration.Short,
@@ -87,7 +200,106 @@ function App() {
       .finally(() => setOperatingMessage(null));
   };
 
-  const switchToJdk = (jdk: Jdk) => {
+  const uninstallJdk = async (jdk: Jdk) => {
+    const name = `${jdk.name} ${jdk.version}`;
+    const confirmed = await confirm(
+      `Delete the files of JDK '${name}' from disk? This cannot be undone.`,
+      { title: "Delete JDK", type: "warning" }
+    );
+    if (!confirmed) {
+      return;
+    }
+    setOperatingMessage(`Deleting JDK '${name}'`);
+    invoke<UninstallReport>("uninstall_jdk", { path: jdk.path })
+      .then((report) => {
+        toast.success(
+          `Deleted JDK '${report.removed.join("', '")}', ` +
+            `${formatSize(report.freed_bytes)} freed`,
+          { duration: ToastDuration.Normal }
+        );
+      })
+      .catch((e) => {
+        toast.error(`Failed to delete JDK '${name}', error: ${e.message}`, {
+          duration: ToastDuration.Infinity,
+        });
+      })
+      .finally(() => setOperatingMessage(null));
+  };
+
+  const upgradeJdk = async (jdk: Jdk) => {
+    const name = `${jdk.name} ${jdk.version}`;
+    setOperatingMessage(`Looking for updates of JDK '${name}'`);
+    let upgrade: CatalogPackage | null;
+    try {
+      upgrade = await invoke<CatalogPackage | null>("find_jdk_upgrade", {
+        path: jdk.path,
+      });
+    } catch (e) {
+      toast.error(`Failed to look for updates: ${(e as Error).message}`, {
+        duration: ToastDuration.Infinity,
+      });
+      return;
+    } finally {
+      setOperatingMessage(null);
+    }
+    if (upgrade == null) {
+      toast.info(`JDK '${name}' is up to date.`, {
+        duration: ToastDuration.Normal,
+      });
+      return;
+    }
+    const confirmed = await confirm(
+      `Install ${upgrade.java_version} next to JDK '${name}'? Pins of ` +
+        "known projects are moved to the new JDK.",
+      { title: "Upgrade JDK" }
+    );
+    if (!confirmed) {
+      return;
+    }
+
+    setOperatingMessage(`Installing ${upgrade.filename}`);
+    let result: UpgradeResult;
+    try {
+      result = await invoke<UpgradeResult>("upgrade_jdk", { path: jdk.path });
+    } catch (e) {
+      toast.error(`Failed to upgrade JDK '${name}': ${(e as Error).message}`, {
+        duration: ToastDuration.Infinity,
+      });
+      return;
+    } finally {
+      setOperatingMessage(null);
+    }
+    showInstallResult("Upgraded to", {
+      jdks: [result.new],
+      signature: result.signature,
+    });
+    if (result.migrated_pins.length > 0) {
+      toast.info(`Updated pins: ${result.migrated_pins.join(", ")}`, {
+        duration: ToastDuration.Long,
+      });
+    }
+    if (result.old.source === "installed") {
+      await uninstallJdk(result.old);
+    } else if (
+      await confirm(`Remove the old JDK '${name}' from the list?`, {
+        title: "Upgrade JDK",
+      })
+    ) {
+      await removeJdk(result.old);
+    }
+  };
+
+  const switchToJdk = async (jdk: Jdk) => {
+    if (jdk.support === "end_of_life") {
+      const confirmed = await confirm(
+        `Support of JDK '${jdk.name} ${jdk.version}' ended on ` +
+          `${jdk.support_until}, it gets no security updates. Switch anyway?`,
+        { title: "Switch JDK", type: "warning" }
+      );
+      if (!confirmed) {
+        return;
+      }
+    }
     setOperatingMessage(`Switching to '${jdk.name} ${jdk.version}'`);
     invoke("switch_to_jdk", { jdk: jdk })
       .then(() => {
@@ -105,6 +317,129 @@ function App() {
       });
   };
 
+  const analyzeProject = async () => {
+    const dir = await open({ directory: true });
+    if (dir == null) {
+      return;
+    }
+    invoke<ProjectAnalysis>("analyze_project", { dir: dir })
+      .then((analysis) => {
+        if (analysis.requirement == null) {
+          toast.info("No JDK requirement found in the project.", {
+            duration: ToastDuration.Normal,
+          });
+          return;
+        }
+        const sources = analysis.requirements
+          .map((item) => `${item.file} (${item.key})`)
+          .join(", ");
+        const jdk = analysis.jdk;
+        if (jdk == null) {
+          toast.warning(
+            `No saved JDK matches '${analysis.requirement}', from ${sources}`,
+            { duration: ToastDuration.Long }
+          );
+          return;
+        }
+        toast.success(
+          `Use '${jdk.name} ${jdk.version}' for '${analysis.requirement}', from ${sources}`,
+          {
+            duration: ToastDuration.Long,
+            action: jdk.is_current
+              ? undefined
+              : { label: "Switch", onClick: () => switchToJdk(jdk) },
+          }
+        );
+      })
+      .catch((e) => {
+        const message = "Failed to analyze the project: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      });
+  };
+
+  const exportWorkspaceReport = async () => {
+    const root = await open({ directory: true });
+    if (root == null) {
+      return;
+    }
+    const path = await save({
+      defaultPath: "jdk-report.md",
+      filters: [
+        { name: "Markdown", extensions: ["md"] },
+        { name: "CSV", extensions: ["csv"] },
+        { name: "JSON", extensions: ["json"] },
+      ],
+    });
+    if (path == null) {
+      return;
+    }
+    setOperatingMessage("Scanning projects...");
+    invoke<number>("export_workspace_report", { root: root, path: path })
+      .then((count) => {
+        const message = `Exported the report of ${count} project(s).`;
+        toast.success(message, { duration: ToastDuration.Normal });
+      })
+      .catch((e) => {
+        const message = "Failed to export the report: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => setOperatingMessage(null));
+  };
+
+  const runJar = (path: string) => {
+    invoke<Jdk>("run_jar", { path: path })
+      .then((jdk) => {
+        const message = `Started with '${jdk.name} ${jdk.version}'`;
+        toast.success(message, { duration: ToastDuration.Normal });
+      })
+      .catch((e) => {
+        const message = "Failed to run the jar: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      });
+  };
+
+  const inspectJar = async () => {
+    const file = await open({
+      directory: false,
+      filters: [{ name: "Java", extensions: ["jar", "class"] }],
+    });
+    if (file == null || Array.isArray(file)) {
+      return;
+    }
+    const path = file;
+    invoke<JarInspection>("inspect_jar", { path: path })
+      .then(({ info, jdk }) => {
+        if (info.min_java == null) {
+          toast.info("No class file found.", {
+            duration: ToastDuration.Normal,
+          });
+          return;
+        }
+        const required = `Requires Java ${info.min_java}+ (class version ${info.class_major})`;
+        if (jdk == null) {
+          toast.warning(`${required}, no saved JDK matches.`, {
+            duration: ToastDuration.Long,
+          });
+          return;
+        }
+        const canRun = path.toLowerCase().endsWith(".jar");
+        toast.success(`${required}, use '${jdk.name} ${jdk.version}'`, {
+          duration: ToastDuration.Long,
+          action: canRun
+            ? { label: "Run", onClick: () => runJar(path) }
+            : undefined,
+        });
+      })
+      .catch((e) => {
+        const message = "Failed to inspect the file: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      });
+  };
+
   const selectJdkDir = async () => {
     await invoke("update_skip_dir_selection_hint", {
       value: skipDirSelectionHint,
@@ -114,6 +449,7 @@ function App() {
     });
     if (dir != null) {
       setOperatingMessage("Looking for JDK(s)...");
+      setScanning(true);
       invoke<number>("add_jdks_from_dir", { dir: dir })
         .then((count) => {
           const message = `Added ${count} JDK(s).`;
@@ -124,8 +460,228 @@ function App() {
           console.error(message);
           toast.error(message, { duration: ToastDuration.Infinity });
         })
-        .finally(() => setOperatingMessage(null));
+        .finally(() => {
+          setOperatingMessage(null);
+          setScanning(false);
+        });
+    }
+  };
+
+  const rescanScanRoots = () => {
+    setOperatingMessage("Rescanning JDK folders...");
+    setScanning(true);
+    invoke<RescanSummary>("rescan_scan_roots")
+      .then((summary) => {
+        const message = `Found ${summary.found} new JDK(s), removed ${summary.removed} missing JDK(s).`;
+        if (summary.skipped.length > 0) {
+          toast.warning(
+            `${message} Skipped unreachable folder(s) '${summary.skipped.join(
+              "', '"
+            )}'.`,
+            { duration: ToastDuration.Long }
+          );
+          return;
+        }
+        toast.success(message, { duration: ToastDuration.Normal });
+      })
+      .catch((e) => {
+        const message = "Failed to rescan folders: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => {
+        setOperatingMessage(null);
+        setScanning(false);
+      });
+  };
+
+  const showInstallResult = (action: string, result: InstallResult) => {
+    const names = result.jdks.map((item) => `${item.name} ${item.version}`);
+    const message = `${action} JDK '${names.join("', '")}'`;
+    if (result.signature.status === "unverified") {
+      toast.warning(
+        `${message}, its signature is not verified: ${result.signature.reason}`,
+        { duration: ToastDuration.Infinity }
+      );
+      return;
+    }
+    toast.success(message, { duration: ToastDuration.Normal });
+  };
+
+  const importJdkArchive = (path: string) => {
+    setOperatingMessage("Importing JDK archive...");
+    setScanning(true);
+    invoke<InstallResult>("import_jdk_archive", { path: path })
+      .then((result) => showInstallResult("Imported", result))
+      .catch((e) => {
+        const message = "Failed to import the archive: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => {
+        setOperatingMessage(null);
+        setScanning(false);
+      });
+  };
+
+  const selectJdkArchive = async () => {
+    const file = await open({
+      directory: false,
+      filters: [
+        { name: "JDK archive", extensions: ["zip", "gz", "tgz", "xz", "txz"] },
+      ],
+    });
+    if (file == null || Array.isArray(file)) {
+      return;
     }
+    importJdkArchive(file);
+  };
+
+  const verifyJdks = () => {
+    setOperatingMessage("Checking JDK files...");
+    setScanning(true);
+    invoke<IntegrityReport>("verify_jdks")
+      .then((report) => {
+        const changed = [...report.modified, ...report.corrupted];
+        if (changed.length === 0) {
+          toast.success(`Checked ${report.checked} JDK(s), no change found.`, {
+            duration: ToastDuration.Normal,
+          });
+          return;
+        }
+        toast.warning(
+          `Files of JDK '${changed.join("', '")}' were changed or removed.`,
+          { duration: ToastDuration.Infinity }
+        );
+      })
+      .catch((e) => {
+        const message = "Failed to check jdks: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => {
+        setOperatingMessage(null);
+        setScanning(false);
+      });
+  };
+
+  const checkJdkUpdates = () => {
+    setOperatingMessage("Looking for JDK updates...");
+    setScanning(true);
+    invoke<UpdateReport>("check_jdk_updates")
+      .then((report) => {
+        if (report.outdated.length === 0) {
+          toast.success(`Checked ${report.checked} JDK(s), all up to date.`, {
+            duration: ToastDuration.Normal,
+          });
+          return;
+        }
+        toast.info(`Updates available: ${report.outdated.join(", ")}`, {
+          duration: ToastDuration.Long,
+        });
+      })
+      .catch((e) => {
+        const message = "Failed to check updates: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => {
+        setOperatingMessage(null);
+        setScanning(false);
+      });
+  };
+
+  const [isShowSettingsDialog, setShowSettingsDialog] = useState(false);
+
+  const [isShowInstallJdkDialog, setShowInstallJdkDialog] = useState(false);
+  const onJdkInstalled = (result: InstallResult) => {
+    setShowInstallJdkDialog(false);
+    showInstallResult("Installed", result);
+  };
+  const onInstallError = useCallback((message: string) => {
+    toast.error("Failed to install: " + message, {
+      duration: ToastDuration.Infinity,
+    });
+  }, []);
+
+  const [isShowExportIdeJdksDialog, setShowExportIdeJdksDialog] =
+    useState(false);
+  const onExportedToIde = (previews: ExportPreview[]) => {
+    setShowExportIdeJdksDialog(false);
+    const count = previews.reduce((sum, item) => sum + item.added.length, 0);
+    toast.success(`Exported ${count} JDK(s).`, {
+      duration: ToastDuration.Normal,
+    });
+  };
+  const onExportError = useCallback((message: string) => {
+    toast.error("Failed to export jdks: " + message, {
+      duration: ToastDuration.Infinity,
+    });
+  }, []);
+
+  const [isShowImportIdeJdksDialog, setShowImportIdeJdksDialog] =
+    useState(false);
+  const importIdeJdks = async (kind: IdeKind, chooseFile: boolean) => {
+    let path: string | null = null;
+    if (chooseFile) {
+      const file = await open({ directory: false });
+      if (file == null || Array.isArray(file)) {
+        return;
+      }
+      path = file;
+    }
+    setShowImportIdeJdksDialog(false);
+    setOperatingMessage("Importing JDK(s)...");
+    invoke<ImportReport>("import_ide_jdks", { kind: kind, path: path })
+      .then((report) => {
+        const message =
+          `Added ${report.added.length}, updated ${report.updated.length} ` +
+          `JDK(s).`;
+        toast.success(message, { duration: ToastDuration.Normal });
+        if (report.invalid.length > 0) {
+          toast.warning(
+            `Skipped ${report.invalid.length} invalid JDK(s): ` +
+              report.invalid.join(", "),
+            { duration: ToastDuration.Long }
+          );
+        }
+      })
+      .catch((e) => {
+        const message = "Failed to import jdks: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => setOperatingMessage(null));
+  };
+
+  const discoverJdks = () => {
+    setOperatingMessage("Looking for JDK(s) installed by other tools...");
+    invoke<number>("discover_jdks")
+      .then((count) => {
+        if (count === 0) {
+          toast.info("No new JDK found.", { duration: ToastDuration.Normal });
+        }
+      })
+      .catch((e) => {
+        const message = "Failed to discover jdks: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      })
+      .finally(() => setOperatingMessage(null));
+  };
+
+  const addDiscoveredJdks = () => {
+    const paths = uiState.discovered_jdks.map((item) => item.path);
+    invoke<number>("add_discovered_jdks", { paths: paths })
+      .then((count) => {
+        const message = `Added ${count} JDK(s).`;
+        toast.success(message, { duration: ToastDuration.Normal });
+      })
+      .catch((e) => {
+        const message = "Failed to add jdks: " + e.message;
+        console.error(message);
+        toast.error(message, { duration: ToastDuration.Infinity });
+      });
   };
 
   useEffect(() => {
@@ -141,7 +697,9 @@ function App() {
       cancelLoadingTid = setTimeout(() => {
         setLoadingJdks(false);
       }, 100);
-      // Blocking call
+      // Blocking calls
+      invoke("listen_scan_progress_stream");
+      invoke("listen_install_progress_stream");
       await invoke("listen_ui_state_stream");
     };
     setup();
@@ -151,6 +709,42 @@ function App() {
     };
   }, []);
 
+  useEffect(() => {
+    const unlistenPromise = getCurrent().listen<JarExit>(
+      "jar-exit",
+      (event) => {
+        const { path, code } = event.payload;
+        const name = path.split(/[\\/]/).pop();
+        if (code === 0) {
+          toast.success(`'${name}' exited`, { duration: ToastDuration.Normal });
+          return;
+        }
+        const reason = code == null ? "was terminated" : `exited with code ${code}`;
+        toast.error(`'${name}' ${reason}`, {
+          duration: ToastDuration.Infinity,
+        });
+      }
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  useEffect(() => {
+    // Archives dropped on the window are imported
+    const unlistenPromise = getCurrent().listen<string[]>(
+      "tauri://file-drop",
+      (event) => {
+        event.payload
+          .filter((path) => /\.(zip|tar\.gz|tgz|tar\.xz|txz)$/i.test(path))
+          .forEach(importJdkArchive);
+      }
+    );
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   applyAppTheme(theme);
 
   return (
@@ -169,6 +763,120 @@ function App() {
               <InformationCircleIcon />
             </div>
 
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title={
+                uiState.settings.hide_jres
+                  ? "Show JREs"
+                  : "Hide JREs (runtimes without javac)"
+              }
+              onClick={() =>
+                invoke("update_hide_jres", {
+                  value: !uiState.settings.hide_jres,
+                })
+              }
+            >
+              {uiState.settings.hide_jres ? <EyeSlashIcon /> : <EyeIcon />}
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Install a JDK"
+              onClick={() => setShowInstallJdkDialog(true)}
+            >
+              <CloudArrowDownIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Add a JDK from an archive file, or drop it on the window"
+              onClick={selectJdkArchive}
+            >
+              <ArchiveBoxArrowDownIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Check files of saved JDKs for changes"
+              onClick={verifyJdks}
+            >
+              <ShieldCheckIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Look for newer patch releases of saved JDKs"
+              onClick={checkJdkUpdates}
+            >
+              <BellAlertIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Find JDKs installed by other tools"
+              onClick={discoverJdks}
+            >
+              <MagnifyingGlassIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Find the JDK for a project"
+              onClick={analyzeProject}
+            >
+              <DocumentMagnifyingGlassIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Export JDK requirements of a workspace"
+              onClick={exportWorkspaceReport}
+            >
+              <TableCellsIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Find the JDK for a JAR"
+              onClick={inspectJar}
+            >
+              <CommandLineIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Import JDKs from IDEs"
+              onClick={() => setShowImportIdeJdksDialog(true)}
+            >
+              <ArrowDownTrayIcon />
+            </div>
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Export JDKs to IDEs"
+              onClick={() => setShowExportIdeJdksDialog(true)}
+            >
+              <ArrowUpTrayIcon />
+            </div>
+
+            {uiState.settings.scan_roots.length > 0 && (
+              <div
+                className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+                title="Rescan added folders"
+                onClick={rescanScanRoots}
+              >
+                <ArrowPathIcon />
+              </div>
+            )}
+
+            <div
+              className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
+              title="Settings"
+              onClick={() => setShowSettingsDialog(true)}
+            >
+              <Cog6ToothIcon />
+            </div>
+
             <div
               className="w-8 h-8 p-[0.3rem] rounded-full hover:bg-gray-100 dark:hover:bg-gray-600 cursor-pointer"
               onClick={toggleTheme}
@@ -211,6 +919,37 @@ function App() {
             </div>
           )}
 
+          {uiState.discovered_jdks.length > 0 && (
+            <div className="mb-4 p-2 rounded-lg bg-violet-500/10">
+              <p className="mb-2">
+                Found {uiState.discovered_jdks.length} JDK(s) installed by
+                other tools:
+              </p>
+              <ul className="mb-2 text-sm">
+                {uiState.discovered_jdks.map((item) => (
+                  <li key={item.path} className="truncate">
+                    {item.name} {item.version}{" "}
+                    <span className="opacity-70">({sourceName(item)})</span>
+                  </li>
+                ))}
+              </ul>
+              <div className="flex justify-end gap-2">
+                <button
+                  className="px-4 py-1 rounded hover:bg-gray-500/20"
+                  onClick={() => invoke("dismiss_discovered_jdks")}
+                >
+                  Dismiss
+                </button>
+                <button
+                  className="px-4 py-1 rounded bg-violet-500 hover:bg-violet-600 text-white"
+                  onClick={addDiscoveredJdks}
+                >
+                  Add all
+                </button>
+              </div>
+            </div>
+          )}
+
           {currentJdk != null && (
             <>
               <p className="mb-2">Current</p>
@@ -219,17 +958,41 @@ function App() {
                 className="mb-4"
                 onSwitchToJdkClick={() => {}}
                 onRemoveJdkClick={() => {}}
+                onUninstallJdkClick={() => {}}
+                onUpgradeJdkClick={upgradeJdk}
               />
             </>
           )}
 
-          {jdks.length > 0 && (
+          {(jdks.length > 0 || distributionFilter != null) && (
             <>
-              <p className="mb-2">Saved</p>
+              <div className="mb-2 flex items-center justify-between">
+                <p>Saved</p>
+                {distributions.length > 1 && (
+                  <select
+                    className="px-2 text-sm rounded-full bg-gray-100 dark:bg-gray-800"
+                    value={distributionFilter ?? ""}
+                    onChange={(e) =>
+                      setDistributionFilter(
+                        e.target.value === "" ? null : e.target.value
+                      )
+                    }
+                  >
+                    <option value="">All distributions</option>
+                    {distributions.map((item) => (
+                      <option key={item} value={item}>
+                        {item}
+                      </option>
+                    ))}
+                  </select>
+                )}
+              </div>
               <JdkList
                 list={jdks}
                 onSwitchToJdkClick={switchToJdk}
                 onRemoveJdkClick={removeJdk}
+                onUninstallJdkClick={uninstallJdk}
+                onUpgradeJdkClick={upgradeJdk}
               />
             </>
           )}
@@ -244,23 +1007,78 @@ function App() {
             e.preventDefault();
           }}
         >
-          <div className="p-4 bg-white dark:bg-gray-700 rounded-lg overflow-hidden flex items-center">
-            <CircularLoader className="mr-4" />
-            <p>{operatingMessage}</p>
+          <div className="max-w-[80%] p-4 bg-white dark:bg-gray-700 rounded-lg overflow-hidden">
+            <div className="flex items-center">
+              <CircularLoader className="mr-4 shrink-0" />
+              <p>{operatingMessage}</p>
+            </div>
+            {isScanning && scanProgress != null && !scanProgress.is_finished && (
+              <>
+                <p className="mt-2 text-sm">
+                  Visited {scanProgress.dirs_visited} dir(s), found{" "}
+                  {scanProgress.jdks_found} JDK(s)
+                </p>
+                <p className="text-sm opacity-70 truncate">
+                  {scanProgress.current_path}
+                </p>
+              </>
+            )}
+            {isScanning && (
+              <button
+                className="mt-2 px-4 py-1 rounded hover:bg-rose-500/30"
+                onClick={() => invoke("cancel_scan")}
+              >
+                Cancel
+              </button>
+            )}
           </div>
         </div>
       )}
 
       <JdkDirSelectorDialog
         open={isShowJdkDirSelectorDialog}
+        rejectJres={uiState.settings.reject_jres}
         doNotShowAgain={skipDirSelectionHint}
         onClose={() => setShowJdkDirSelectorDialog(false)}
         onSelectClick={() => {
           selectJdkDir();
           setShowJdkDirSelectorDialog(false);
         }}
+        onUpdateRejectJres={(value) =>
+          invoke("update_reject_jres", { value: value })
+        }
         onUpdateDoNotShowAgain={setSkipDirSelectionHint}
       />
+
+      <InstallJdkDialog
+        open={isShowInstallJdkDialog}
+        onClose={() => setShowInstallJdkDialog(false)}
+        onInstalled={onJdkInstalled}
+        onError={onInstallError}
+      />
+
+      <ExportIdeJdksDialog
+        open={isShowExportIdeJdksDialog}
+        onClose={() => setShowExportIdeJdksDialog(false)}
+        onExported={onExportedToIde}
+        onError={onExportError}
+      />
+
+      <SettingsDialog
+        open={isShowSettingsDialog}
+        settings={uiState.settings}
+        onClose={() => setShowSettingsDialog(false)}
+        onError={(message) =>
+          toast.error(message, { duration: ToastDuration.Infinity })
+        }
+      />
+
+      <ImportIdeJdksDialog
+        open={isShowImportIdeJdksDialog}
+        onClose={() => setShowImportIdeJdksDialog(false)}
+        onImportClick={(kind) => importIdeJdks(kind, false)}
+        onChooseFileClick={(kind) => importIdeJdks(kind, true)}
+      />
     </div>
   );
 }
@@ -270,11 +1088,15 @@ function JdkList({
   className,
   onSwitchToJdkClick,
   onRemoveJdkClick,
+  onUninstallJdkClick,
+  onUpgradeJdkClick,
 }: {
   list: Jdk[];
   className?: string;
   onSwitchToJdkClick: (jdk: Jdk) => void;
   onRemoveJdkClick: (jdk: Jdk) => void;
+  onUninstallJdkClick: (jdk: Jdk) => void;
+  onUpgradeJdkClick: (jdk: Jdk) => void;
 }) {
   const [expandedJdkPath, setExpandedJdkPath] = useState<string | null>(null);
 
@@ -284,6 +1106,7 @@ function JdkList({
         const isCurrent = item.is_current;
         const isValid = item.is_valid;
         const isExpanded = expandedJdkPath === item.path;
+        const isInstalled = item.source === "installed";
 
         return (
           <li
@@ -321,6 +1144,88 @@ function JdkList({
               >
                 {item.arch}
               </span>
+              <span
+                className={
+                  "ml-2 px-2 text-sm rounded-full " +
+                  (isCurrent ? "bg-white/20" : "bg-gray-500/20")
+                }
+                title={
+                  item.capabilities.javac
+                    ? "Java Development Kit"
+                    : "Java Runtime Environment, no compiler available"
+                }
+              >
+                {item.capabilities.javac ? "JDK" : "JRE"}
+              </span>
+              {item.source !== "manual" && (
+                <span
+                  className={
+                    "ml-2 px-2 text-sm rounded-full " +
+                    (isCurrent ? "bg-white/20" : "bg-gray-500/20")
+                  }
+                  title={
+                    item.source === "installed"
+                      ? "Installed by JDK Switcher"
+                      : "Installed and managed by another tool"
+                  }
+                >
+                  {sourceName(item)}
+                </span>
+              )}
+              {item.is_foreign_arch && (
+                <span
+                  className="ml-2 px-2 text-sm rounded-full bg-orange-500/30"
+                  title="This JDK does not match the architecture of this machine"
+                >
+                  Emulated
+                </span>
+              )}
+              {(item.integrity === "modified" ||
+                item.integrity === "corrupted") && (
+                <span
+                  className="ml-2 px-2 text-sm rounded-full bg-red-500/30"
+                  title={
+                    item.integrity === "modified"
+                      ? "Files of this JDK were changed after it was added"
+                      : "Files of this JDK are missing or unreadable"
+                  }
+                >
+                  {item.integrity === "modified" ? "Modified" : "Corrupted"}
+                </span>
+              )}
+              {(item.support === "end_of_life" ||
+                item.support === "ending_soon") && (
+                <span
+                  className={
+                    "ml-2 px-2 text-sm rounded-full " +
+                    (item.support === "end_of_life"
+                      ? "bg-red-500/30"
+                      : "bg-amber-500/30")
+                  }
+                  title={
+                    item.support === "end_of_life"
+                      ? `Support ended on ${item.support_until}`
+                      : `Support ends on ${item.support_until}`
+                  }
+                >
+                  {item.support === "end_of_life" ? "EOL" : "Support ending"}
+                </span>
+              )}
+              {item.latest_available != null && (
+                <span
+                  className={
+                    "ml-2 px-2 text-sm rounded-full hover:brightness-110 " +
+                    (isCurrent ? "bg-white/20" : "bg-emerald-500/30")
+                  }
+                  title="Click to upgrade to the newest patch release"
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    onUpgradeJdkClick(item);
+                  }}
+                >
+                  {item.latest_available} available
+                </span>
+              )}
             </div>
 
             <div className="flex items-center">
@@ -343,6 +1248,23 @@ function JdkList({
                   <FolderOpenIcon />
                 </div>
               )}
+              {isValid && item.distribution !== "unknown" && (
+                <div
+                  className={
+                    "w-6 h-6 p-1 rounded-full cursor-pointer " +
+                    (isCurrent
+                      ? "hover:bg-violet-400"
+                      : "hover:bg-gray-300 dark:hover:bg-gray-500")
+                  }
+                  title="Upgrade to the newest patch release"
+                  onClick={(e) => {
+                    e.stopPropagation();
+                    onUpgradeJdkClick(item);
+                  }}
+                >
+                  <ArrowUpCircleIcon />
+                </div>
+              )}
               {!isValid && (
                 <div className="w-6 h-6 p-1">
                   <ExclamationTriangleIcon className="stroke-orange-500" />
@@ -354,7 +1276,8 @@ function JdkList({
               className={
                 "border-t border-gray-300 dark:border-gray-500 transition-[height,opacity] " +
                 (isExpanded && !isCurrent
-                  ? "mt-2 pt-2 h-[5.6rem] opacity-100"
+                  ? "mt-2 pt-2 opacity-100 " +
+                    (isInstalled ? "h-[8.1rem]" : "h-[5.6rem]")
                   : "h-0 opacity-0")
               }
             >
@@ -386,6 +1309,21 @@ function JdkList({
                 <TrashIcon className="w-5 h-5 mr-2" />
                 <p>Remove from list</p>
               </div>
+
+              {isInstalled && (
+                <div
+                  className="w-fit h-10 px-2 flex items-center rounded-full hover:bg-rose-500/30"
+                  onClick={(e) => {
+                    if (isExpanded) {
+                      e.stopPropagation();
+                      onUninstallJdkClick(item);
+                    }
+                  }}
+                >
+                  <ArchiveBoxXMarkIcon className="w-5 h-5 mr-2" />
+                  <p>Delete from disk</p>
+                </div>
+              )}
             </div>
           </li>
         );
@@ -394,6 +1332,35 @@ function JdkList({
   );
 }
 
+function sourceName(jdk: Jdk): string {
+  switch (jdk.source) {
+    case "sdkman":
+      return "SDKMAN!";
+    case "jenv":
+      return "jenv";
+    case "asdf":
+      return "asdf";
+    case "mise":
+      return "mise";
+    case "intellij_idea":
+      return "IntelliJ IDEA";
+    case "gradle":
+      return "Gradle";
+    case "jet_brains_ide":
+      return "JetBrains IDE";
+    case "android_studio":
+      return "Android Studio";
+    case "system":
+      return "System";
+    case "registry":
+      return "Windows Registry";
+    case "installed":
+      return "Installed";
+    default:
+      return "Manual";
+  }
+}
+
 async function openAboutDialog() {
   await invoke("open_about_dialog");
 }
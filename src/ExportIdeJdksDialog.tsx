@@ -0,0 +1,165 @@
+import { XMarkIcon } from "@heroicons/react/24/outline";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useRef, useState } from "react";
+import { IdeKind } from "./ImportIdeJdksDialog";
+
+export type ExportPreview = {
+  path: string;
+  content: string;
+  added: string[];
+  skipped: string[];
+};
+
+const ides: { kind: IdeKind; name: string }[] = [
+  { kind: "intellij", name: "IntelliJ IDEA" },
+  { kind: "vs_code", name: "VS Code" },
+];
+
+export default function ExportIdeJdksDialog({
+  open,
+  onClose,
+  onExported,
+  onError,
+}: {
+  open: boolean;
+  onClose: () => void;
+  onExported: (previews: ExportPreview[]) => void;
+  onError: (message: string) => void;
+}) {
+  const ref = useRef<HTMLDialogElement>(null);
+
+  const [visible, setVisible] = useState(false);
+
+  const [kind, setKind] = useState<IdeKind>("intellij");
+
+  const [previews, setPreviews] = useState<ExportPreview[] | null>(null);
+
+  useEffect(() => {
+    const dialog = ref.current;
+    if (dialog == null) {
+      return;
+    }
+    if (open) {
+      dialog.showModal();
+      setVisible(true);
+      const clickListener = (ev: MouseEvent) => {
+        const bounds = dialog.getBoundingClientRect();
+        const x = ev.x;
+        const y = ev.y;
+        if (
+          x < bounds.left ||
+          x > bounds.right ||
+          y < bounds.top ||
+          y > bounds.bottom
+        ) {
+          // Backdrop clicked
+          onClose();
+        }
+      };
+      dialog.addEventListener("click", clickListener);
+      return () => {
+        dialog.removeEventListener("click", clickListener);
+      };
+    } else {
+      dialog.close();
+      setVisible(false);
+    }
+  }, [open, onClose]);
+
+  useEffect(() => {
+    if (!open) {
+      return;
+    }
+    setPreviews(null);
+    invoke<ExportPreview[]>("preview_ide_export", { kind: kind })
+      .then(setPreviews)
+      .catch((e) => {
+        setPreviews([]);
+        onError(e.message);
+      });
+  }, [open, kind, onError]);
+
+  const addedCount = (previews ?? []).reduce(
+    (sum, item) => sum + item.added.length,
+    0
+  );
+
+  const exportJdks = () => {
+    invoke<ExportPreview[]>("export_jdks_to_ide", { kind: kind })
+      .then(onExported)
+      .catch((e) => onError(e.message));
+  };
+
+  return (
+    <dialog
+      ref={ref}
+      className={
+        "w-full max-w-[500px] relative p-4 rounded-xl backdrop:bg-black/30 bg-white dark:bg-gray-700 text-gray-900 dark:text-white transition-[transform,opacity] duration-300 ease-bouncy " +
+        (visible
+          ? "translate-y-0 scale-100 opacity-100"
+          : "translate-y-48 scale-75 opacity-0")
+      }
+    >
+      <XMarkIcon
+        className="stroke-gray-400 hover:stroke-red-500 w-6 h-6 absolute right-2 top-2"
+        onClick={onClose}
+      />
+
+      <p className="mt-2 text-xl font-bold">Export JDKs to IDEs</p>
+
+      <div className="mt-4 flex gap-2">
+        {ides.map((ide) => (
+          <button
+            key={ide.kind}
+            className={
+              "px-4 py-1 rounded " +
+              (ide.kind === kind
+                ? "bg-violet-500 text-white"
+                : "hover:bg-gray-500/20")
+            }
+            onClick={() => setKind(ide.kind)}
+          >
+            {ide.name}
+          </button>
+        ))}
+      </div>
+
+      {kind === "intellij" && (
+        <p className="mt-2 text-sm opacity-70">
+          Close the IDE before exporting, it overwrites the file on exit.
+        </p>
+      )}
+
+      <ul className="mt-4 max-h-64 overflow-auto">
+        {previews == null && <li>Loading...</li>}
+        {(previews ?? []).map((preview) => (
+          <li key={preview.path} className="mb-2">
+            <p className="text-sm font-mono break-all">{preview.path}</p>
+            {preview.added.length > 0 ? (
+              <p>Add: {preview.added.join(", ")}</p>
+            ) : (
+              <p className="opacity-70">Nothing to add</p>
+            )}
+            {preview.skipped.length > 0 && (
+              <p className="text-sm opacity-70">
+                Keep {preview.skipped.length} JDK(s) defined already
+              </p>
+            )}
+          </li>
+        ))}
+      </ul>
+
+      <div className="mt-4 flex justify-end">
+        <button
+          className={
+            "shrink-0 bg-violet-500 hover:bg-violet-600 text-white px-4 py-1 rounded " +
+            (addedCount === 0 ? "opacity-50 pointer-events-none" : "")
+          }
+          onClick={exportJdks}
+        >
+          Export
+        </button>
+      </div>
+    </dialog>
+  );
+}
@@ -0,0 +1,108 @@
+import { XMarkIcon } from "@heroicons/react/24/outline";
+import { useEffect, useRef, useState } from "react";
+
+export type IdeKind = "intellij" | "eclipse" | "vs_code";
+
+const ides: { kind: IdeKind; name: string; file: string }[] = [
+  { kind: "intellij", name: "IntelliJ IDEA", file: "jdk.table.xml" },
+  {
+    kind: "eclipse",
+    name: "Eclipse",
+    file: "org.eclipse.jdt.launching.prefs",
+  },
+  { kind: "vs_code", name: "VS Code", file: "settings.json" },
+];
+
+export default function ImportIdeJdksDialog({
+  open,
+  onClose,
+  onImportClick,
+  onChooseFileClick,
+}: {
+  open: boolean;
+  onClose: () => void;
+  onImportClick: (kind: IdeKind) => void;
+  onChooseFileClick: (kind: IdeKind) => void;
+}) {
+  const ref = useRef<HTMLDialogElement>(null);
+
+  const [visible, setVisible] = useState(false);
+
+  useEffect(() => {
+    const dialog = ref.current;
+    if (dialog == null) {
+      return;
+    }
+    if (open) {
+      dialog.showModal();
+      setVisible(true);
+      const clickListener = (ev: MouseEvent) => {
+        const bounds = dialog.getBoundingClientRect();
+        const x = ev.x;
+        const y = ev.y;
+        if (
+          x < bounds.left ||
+          x > bounds.right ||
+          y < bounds.top ||
+          y > bounds.bottom
+        ) {
+          // Backdrop clicked
+          onClose();
+        }
+      };
+      dialog.addEventListener("click", clickListener);
+      return () => {
+        dialog.removeEventListener("click", clickListener);
+      };
+    } else {
+      dialog.close();
+      setVisible(false);
+    }
+  }, [open, onClose]);
+
+  return (
+    <dialog
+      ref={ref}
+      className={
+        "w-full max-w-[500px] relative p-4 rounded-xl backdrop:bg-black/30 bg-white dark:bg-gray-700 text-gray-900 dark:text-white transition-[transform,opacity] duration-300 ease-bouncy " +
+        (visible
+          ? "translate-y-0 scale-100 opacity-100"
+          : "translate-y-48 scale-75 opacity-0")
+      }
+    >
+      <XMarkIcon
+        className="stroke-gray-400 hover:stroke-red-500 w-6 h-6 absolute right-2 top-2"
+        onClick={onClose}
+      />
+
+      <p className="mt-2 text-xl font-bold">Import JDKs from IDEs</p>
+
+      <p className="mt-2">
+        JDKs defined in the IDE settings are validated before they are added.
+      </p>
+
+      <ul className="mt-4">
+        {ides.map((ide) => (
+          <li key={ide.kind} className="mb-2 flex items-center">
+            <div className="grow">
+              <p>{ide.name}</p>
+              <p className="text-sm font-mono opacity-70">{ide.file}</p>
+            </div>
+            <button
+              className="shrink-0 px-4 py-1 rounded hover:bg-gray-500/20"
+              onClick={() => onChooseFileClick(ide.kind)}
+            >
+              Choose file
+            </button>
+            <button
+              className="shrink-0 ml-2 bg-violet-500 hover:bg-violet-600 text-white px-4 py-1 rounded"
+              onClick={() => onImportClick(ide.kind)}
+            >
+              Import
+            </button>
+          </li>
+        ))}
+      </ul>
+    </dialog>
+  );
+}
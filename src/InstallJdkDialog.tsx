@@ -0,0 +1,258 @@
+import { XMarkIcon } from "@heroicons/react/24/outline";
+import { invoke } from "@tauri-apps/api/tauri";
+import { useEffect, useRef, useState } from "react";
+import useTauriEvents from "./hook/useTauriEvents";
+import {
+  CatalogDistribution,
+  CatalogMajorVersion,
+  CatalogPackage,
+  InstallProgress,
+  InstallResult,
+} from "./model/Catalog";
+
+export default function InstallJdkDialog({
+  open,
+  onClose,
+  onInstalled,
+  onError,
+}: {
+  open: boolean;
+  onClose: () => void;
+  onInstalled: (result: InstallResult) => void;
+  onError: (message: string) => void;
+}) {
+  const ref = useRef<HTMLDialogElement>(null);
+
+  const [visible, setVisible] = useState(false);
+
+  const [distributions, setDistributions] = useState<CatalogDistribution[]>(
+    []
+  );
+
+  const [majorVersions, setMajorVersions] = useState<CatalogMajorVersion[]>(
+    []
+  );
+
+  const [distribution, setDistribution] = useState("temurin");
+
+  const [majorVersion, setMajorVersion] = useState<number | null>(null);
+
+  const [packages, setPackages] = useState<CatalogPackage[] | null>(null);
+
+  const [installing, setInstalling] = useState<string | null>(null);
+
+  const progress = useTauriEvents<InstallProgress | null>(
+    "install-progress-stream",
+    null
+  );
+
+  useEffect(() => {
+    const dialog = ref.current;
+    if (dialog == null) {
+      return;
+    }
+    if (open) {
+      dialog.showModal();
+      setVisible(true);
+      const clickListener = (ev: MouseEvent) => {
+        const bounds = dialog.getBoundingClientRect();
+        const x = ev.x;
+        const y = ev.y;
+        if (
+          x < bounds.left ||
+          x > bounds.right ||
+          y < bounds.top ||
+          y > bounds.bottom
+        ) {
+          // Backdrop clicked
+          onClose();
+        }
+      };
+      dialog.addEventListener("click", clickListener);
+      return () => {
+        dialog.removeEventListener("click", clickListener);
+      };
+    } else {
+      dialog.close();
+      setVisible(false);
+    }
+  }, [open, onClose]);
+
+  useEffect(() => {
+    // Reload every time, the catalogs may have been changed in settings
+    if (!open) {
+      return;
+    }
+    invoke<CatalogDistribution[]>("catalog_distributions")
+      .then((items) => {
+        setDistributions(items);
+        setDistribution((current) =>
+          items.some((item) => item.api_parameter === current) ||
+          items.length === 0
+            ? current
+            : items[0].api_parameter
+        );
+      })
+      .catch((e) => onError(e.message));
+    invoke<CatalogMajorVersion[]>("catalog_major_versions")
+      .then((versions) => {
+        const maintained = versions.filter((item) => item.maintained);
+        setMajorVersions(maintained);
+        const lts = maintained.find(
+          (item) => item.term_of_support.toLowerCase() === "lts"
+        );
+        setMajorVersion(
+          lts?.major_version ?? maintained[0]?.major_version ?? null
+        );
+      })
+      .catch((e) => onError(e.message));
+  }, [open, onError]);
+
+  useEffect(() => {
+    if (!open || majorVersion == null) {
+      return;
+    }
+    setPackages(null);
+    const query = {
+      distribution: distribution,
+      version: majorVersion.toString(),
+      latest: true,
+    };
+    invoke<CatalogPackage[]>("catalog_packages", { query: query })
+      .then(setPackages)
+      .catch((e) => {
+        setPackages([]);
+        onError(e.message);
+      });
+  }, [open, distribution, majorVersion, onError]);
+
+  const install = (item: CatalogPackage) => {
+    setInstalling(item.filename);
+    invoke<InstallResult>("install_jdk", { package: item })
+      .then(onInstalled)
+      .catch((e) => onError(e.message))
+      .finally(() => setInstalling(null));
+  };
+
+  const progressText = () => {
+    if (progress == null || progress.package !== installing) {
+      return "Preparing...";
+    }
+    switch (progress.stage) {
+      case "downloading":
+        return progress.total != null
+          ? `Downloading ${Math.floor(
+              (progress.downloaded / progress.total) * 100
+            )}%`
+          : `Downloading ${formatSize(progress.downloaded)}`;
+      case "verifying":
+        return "Verifying...";
+      case "extracting":
+        return "Extracting...";
+      case "finished":
+        return "Finished";
+    }
+  };
+
+  return (
+    <dialog
+      ref={ref}
+      className={
+        "w-full max-w-[600px] relative p-4 rounded-xl backdrop:bg-black/30 bg-white dark:bg-gray-700 text-gray-900 dark:text-white transition-[transform,opacity] duration-300 ease-bouncy " +
+        (visible
+          ? "translate-y-0 scale-100 opacity-100"
+          : "translate-y-48 scale-75 opacity-0")
+      }
+    >
+      <XMarkIcon
+        className="stroke-gray-400 hover:stroke-red-500 w-6 h-6 absolute right-2 top-2"
+        onClick={onClose}
+      />
+
+      <p className="mt-2 text-xl font-bold">Install a JDK</p>
+
+      <div className="mt-4 flex gap-2">
+        <select
+          className="flex-1 px-2 py-1 rounded bg-gray-500/10"
+          value={distribution}
+          onChange={(e) => setDistribution(e.target.value)}
+        >
+          {distributions.map((item) => (
+            <option key={item.api_parameter} value={item.api_parameter}>
+              {item.name}
+            </option>
+          ))}
+        </select>
+        <select
+          className="px-2 py-1 rounded bg-gray-500/10"
+          value={majorVersion ?? ""}
+          onChange={(e) => setMajorVersion(parseInt(e.target.value))}
+        >
+          {majorVersions.map((item) => (
+            <option key={item.major_version} value={item.major_version}>
+              {item.major_version}
+              {item.term_of_support !== "" &&
+                ` (${item.term_of_support.toUpperCase()})`}
+            </option>
+          ))}
+        </select>
+      </div>
+
+      <ul className="mt-4 max-h-64 overflow-auto">
+        {packages == null && <li>Loading...</li>}
+        {packages != null && packages.length === 0 && (
+          <li className="opacity-70">No package found.</li>
+        )}
+        {(packages ?? []).map((item) => (
+          <li
+            key={`${item.catalog} ${item.id}`}
+            className="py-2 flex items-center border-b border-gray-500/20"
+          >
+            <div className="flex-1 min-w-0">
+              <p>
+                {item.java_version}{" "}
+                <span className="text-sm opacity-70">
+                  {item.architecture} {item.archive_type}
+                </span>
+              </p>
+              <p className="text-sm opacity-70 truncate" title={item.catalog}>
+                {item.filename}
+              </p>
+            </div>
+            {item.size > 0 && (
+              <span className="mx-2 text-sm opacity-70 shrink-0">
+                {formatSize(item.size)}
+              </span>
+            )}
+            {installing === item.filename ? (
+              <button
+                className="shrink-0 px-4 py-1 rounded hover:bg-rose-500/30"
+                onClick={() => invoke("cancel_install")}
+                title="Cancel, the download resumes on the next try"
+              >
+                {progressText()}
+              </button>
+            ) : (
+              <button
+                className={
+                  "shrink-0 bg-violet-500 hover:bg-violet-600 text-white px-4 py-1 rounded " +
+                  (installing != null ? "opacity-50 pointer-events-none" : "")
+                }
+                onClick={() => install(item)}
+              >
+                Install
+              </button>
+            )}
+          </li>
+        ))}
+      </ul>
+    </dialog>
+  );
+}
+
+export function formatSize(bytes: number): string {
+  if (bytes >= 1024 * 1024) {
+    return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  }
+  return `${Math.ceil(bytes / 1024)} KB`;
+}
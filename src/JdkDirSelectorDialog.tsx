@@ -3,15 +3,19 @@ import { useEffect, useRef, useState } from "react";
 
 export default function JdkDirSelectorDialog({
   open,
+  rejectJres,
   doNotShowAgain,
   onClose,
   onSelectClick,
+  onUpdateRejectJres,
   onUpdateDoNotShowAgain,
 }: {
   open: boolean;
+  rejectJres: boolean;
   doNotShowAgain: boolean;
   onClose: () => void;
   onSelectClick: () => void;
+  onUpdateRejectJres: (value: boolean) => void;
   onUpdateDoNotShowAgain: (value: boolean) => void;
 }) {
   const ref = useRef<HTMLDialogElement>(null);
@@ -85,6 +89,19 @@ export default function JdkDirSelectorDialog({
         /java.exe
       </p>
 
+      <div
+        className="mt-4 flex items-center cursor-pointer"
+        onClick={() => onUpdateRejectJres(!rejectJres)}
+      >
+        <input
+          type="checkbox"
+          className="mr-2 accent-violet-500"
+          checked={rejectJres}
+          onChange={(e) => onUpdateRejectJres(e.target.checked)}
+        />
+        <p>Skip JREs (runtimes without javac)</p>
+      </div>
+
       <div className="mt-4 flex justify-between">
         <div
           className="flex items-center cursor-pointer"
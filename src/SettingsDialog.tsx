@@ -0,0 +1,423 @@
+import {
+  ArrowPathIcon,
+  PlusIcon,
+  XMarkIcon,
+} from "@heroicons/react/24/outline";
+import { invoke } from "@tauri-apps/api/tauri";
+import { open as openFile } from "@tauri-apps/plugin-dialog";
+import { useEffect, useRef, useState } from "react";
+import { AppSettings } from "./model/AppUiState";
+
+export default function SettingsDialog({
+  open,
+  settings,
+  onClose,
+  onError,
+}: {
+  open: boolean;
+  settings: AppSettings;
+  onClose: () => void;
+  onError: (message: string) => void;
+}) {
+  const ref = useRef<HTMLDialogElement>(null);
+
+  const [visible, setVisible] = useState(false);
+
+  useEffect(() => {
+    const dialog = ref.current;
+    if (dialog == null) {
+      return;
+    }
+    if (open) {
+      dialog.showModal();
+      setVisible(true);
+      const clickListener = (ev: MouseEvent) => {
+        const bounds = dialog.getBoundingClientRect();
+        const x = ev.x;
+        const y = ev.y;
+        if (
+          x < bounds.left ||
+          x > bounds.right ||
+          y < bounds.top ||
+          y > bounds.bottom
+        ) {
+          // Backdrop clicked
+          onClose();
+        }
+      };
+      dialog.addEventListener("click", clickListener);
+      return () => {
+        dialog.removeEventListener("click", clickListener);
+      };
+    } else {
+      dialog.close();
+      setVisible(false);
+    }
+  }, [open, onClose]);
+
+  const [catalogSource, setCatalogSource] = useState("");
+
+  const update = (command: string, value: boolean) => {
+    invoke(command, { value: value }).catch((e) => onError(e.message));
+  };
+
+  const [minisignKeys, setMinisignKeys] = useState("");
+
+  useEffect(() => {
+    setMinisignKeys(settings.trusted_minisign_keys.join("\n"));
+  }, [settings.trusted_minisign_keys]);
+
+  const updateTrustedKeys = (minisignKeys: string[], gpgKeys: string[]) => {
+    invoke("update_trusted_keys", {
+      minisignKeys: minisignKeys,
+      gpgKeys: gpgKeys,
+    }).catch((e) => onError(e.message));
+  };
+
+  const addGpgKey = async () => {
+    const file = await openFile({ directory: false });
+    if (file == null || Array.isArray(file)) {
+      return;
+    }
+    updateTrustedKeys(settings.trusted_minisign_keys, [
+      ...settings.trusted_gpg_keys,
+      file,
+    ]);
+  };
+
+  const [lifecycleSource, setLifecycleSource] = useState("");
+
+  const [lifecycleUpdated, setLifecycleUpdated] = useState<string | null>(
+    null
+  );
+
+  useEffect(() => {
+    setLifecycleSource(settings.lifecycle_source);
+  }, [settings.lifecycle_source]);
+
+  const updateLifecycleTable = () => {
+    if (lifecycleSource.trim() === "") {
+      return;
+    }
+    setLifecycleUpdated(null);
+    invoke<string>("update_lifecycle_table", { source: lifecycleSource })
+      .then(setLifecycleUpdated)
+      .catch((e) => onError(e.message));
+  };
+
+  const [scanMaxDepth, setScanMaxDepth] = useState("");
+
+  const [scanExcludes, setScanExcludes] = useState("");
+
+  useEffect(() => {
+    setScanMaxDepth(settings.scan_max_depth.toString());
+    setScanExcludes(settings.scan_excludes.join("\n"));
+  }, [settings.scan_max_depth, settings.scan_excludes]);
+
+  const updateScanOptions = (
+    maxDepth: string,
+    excludes: string,
+    followLinks: boolean
+  ) => {
+    const depth = parseInt(maxDepth);
+    invoke("update_scan_options", {
+      maxDepth: isNaN(depth) || depth < 0 ? settings.scan_max_depth : depth,
+      excludes: excludes.split("\n"),
+      followLinks: followLinks,
+    }).catch((e) => onError(e.message));
+  };
+
+  const addKnownProject = async () => {
+    const dir = await openFile({ directory: true });
+    if (dir == null || Array.isArray(dir)) {
+      return;
+    }
+    invoke("add_known_project", { dir: dir }).catch((e) => onError(e.message));
+  };
+
+  const addCatalogSource = () => {
+    if (catalogSource.trim() === "") {
+      return;
+    }
+    invoke("add_catalog_source", { source: catalogSource })
+      .then(() => setCatalogSource(""))
+      .catch((e) => onError(e.message));
+  };
+
+  return (
+    <dialog
+      ref={ref}
+      className={
+        "w-full max-w-[500px] relative p-4 rounded-xl backdrop:bg-black/30 bg-white dark:bg-gray-700 text-gray-900 dark:text-white transition-[transform,opacity] duration-300 ease-bouncy " +
+        (visible
+          ? "translate-y-0 scale-100 opacity-100"
+          : "translate-y-48 scale-75 opacity-0")
+      }
+    >
+      <XMarkIcon
+        className="stroke-gray-400 hover:stroke-red-500 w-6 h-6 absolute right-2 top-2"
+        onClick={onClose}
+      />
+
+      <p className="mt-2 text-xl font-bold">Settings</p>
+
+      <p className="mt-4 font-bold">Build tools</p>
+
+      <SettingsCheckbox
+        label="Sync JDKs to Maven toolchains (~/.m2/toolchains.xml)"
+        checked={settings.sync_maven_toolchains}
+        onChange={(value) => update("update_sync_maven_toolchains", value)}
+      />
+
+      <SettingsCheckbox
+        label="Sync JDKs to Gradle installation paths (~/.gradle/gradle.properties)"
+        checked={settings.sync_gradle_paths}
+        onChange={(value) => update("update_sync_gradle_paths", value)}
+      />
+
+      <p className="mt-4 font-bold">Scanning</p>
+
+      <SettingsCheckbox
+        label="Rescan added folders on startup"
+        checked={settings.rescan_on_startup}
+        onChange={(value) => update("update_rescan_on_startup", value)}
+      />
+
+      <SettingsCheckbox
+        label="Check files of saved JDKs for changes on startup"
+        checked={settings.verify_on_startup}
+        onChange={(value) => update("update_verify_on_startup", value)}
+      />
+
+      <SettingsCheckbox
+        label="Follow symbolic links and junctions"
+        checked={settings.scan_follow_links}
+        onChange={(value) =>
+          updateScanOptions(scanMaxDepth, scanExcludes, value)
+        }
+      />
+
+      <div className="mt-2 flex items-center">
+        <p className="flex-1 text-sm">Max depth of sub folders</p>
+        <input
+          className="w-16 px-2 py-1 rounded bg-gray-500/10 text-sm"
+          type="number"
+          min={0}
+          value={scanMaxDepth}
+          onChange={(e) => setScanMaxDepth(e.target.value)}
+          onBlur={() =>
+            updateScanOptions(
+              scanMaxDepth,
+              scanExcludes,
+              settings.scan_follow_links
+            )
+          }
+        />
+      </div>
+
+      <p className="mt-2 text-sm">Skipped folders, glob patterns one per line</p>
+      <textarea
+        className="mt-1 w-full px-2 py-1 rounded bg-gray-500/10 text-sm font-mono"
+        rows={3}
+        value={scanExcludes}
+        onChange={(e) => setScanExcludes(e.target.value)}
+        onBlur={() =>
+          updateScanOptions(
+            scanMaxDepth,
+            scanExcludes,
+            settings.scan_follow_links
+          )
+        }
+      />
+
+      <p className="mt-2 text-sm">Added folders</p>
+      <ul>
+        {settings.scan_roots.map((root) => (
+          <li key={root} className="py-1 flex items-center">
+            <p className="flex-1 text-sm truncate" title={root}>
+              {root}
+            </p>
+            <XMarkIcon
+              className="shrink-0 w-5 h-5 stroke-gray-400 hover:stroke-red-500 cursor-pointer"
+              onClick={() =>
+                invoke("remove_scan_root", { root: root }).catch((e) =>
+                  onError(e.message)
+                )
+              }
+            />
+          </li>
+        ))}
+      </ul>
+
+      <div className="mt-4 flex items-center">
+        <p className="flex-1 font-bold">Projects</p>
+        <PlusIcon
+          className="shrink-0 w-6 h-6 p-0.5 rounded-full hover:bg-gray-500/20 cursor-pointer"
+          onClick={addKnownProject}
+        />
+      </div>
+      <p className="mt-1 text-sm opacity-70">
+        JDKs pinned by these projects are not uninstalled, their pins follow
+        upgrades.
+      </p>
+      <ul>
+        {settings.known_projects.map((dir) => (
+          <li key={dir} className="py-1 flex items-center">
+            <p className="flex-1 text-sm truncate" title={dir}>
+              {dir}
+            </p>
+            <XMarkIcon
+              className="shrink-0 w-5 h-5 stroke-gray-400 hover:stroke-red-500 cursor-pointer"
+              onClick={() =>
+                invoke("remove_known_project", { dir: dir }).catch((e) =>
+                  onError(e.message)
+                )
+              }
+            />
+          </li>
+        ))}
+      </ul>
+
+      <p className="mt-4 font-bold">Catalogs</p>
+
+      <SettingsCheckbox
+        label="Install JDKs from the public foojay catalog"
+        checked={settings.disco_catalog_enabled}
+        onChange={(value) => update("update_disco_catalog_enabled", value)}
+      />
+
+      <SettingsCheckbox
+        label="Look for newer patch releases of saved JDKs on startup"
+        checked={settings.check_updates_on_startup}
+        onChange={(value) => update("update_check_updates_on_startup", value)}
+      />
+
+      <ul className="mt-2">
+        {settings.catalog_sources.map((source) => (
+          <li key={source} className="py-1 flex items-center">
+            <p className="flex-1 text-sm truncate" title={source}>
+              {source}
+            </p>
+            <XMarkIcon
+              className="shrink-0 w-5 h-5 stroke-gray-400 hover:stroke-red-500 cursor-pointer"
+              onClick={() =>
+                invoke("remove_catalog_source", { source: source }).catch(
+                  (e) => onError(e.message)
+                )
+              }
+            />
+          </li>
+        ))}
+      </ul>
+
+      <div className="mt-2 flex items-center gap-2">
+        <input
+          className="flex-1 px-2 py-1 rounded bg-gray-500/10 text-sm"
+          placeholder="Index URL or path, e.g. https://mirror/jdks/index.json"
+          value={catalogSource}
+          onChange={(e) => setCatalogSource(e.target.value)}
+          onKeyDown={(e) => e.key === "Enter" && addCatalogSource()}
+        />
+        <PlusIcon
+          className="shrink-0 w-6 h-6 p-0.5 rounded-full hover:bg-gray-500/20 cursor-pointer"
+          onClick={addCatalogSource}
+        />
+      </div>
+
+      <p className="mt-4 font-bold">Support lifecycle</p>
+
+      <div className="mt-2 flex items-center gap-2">
+        <input
+          className="flex-1 px-2 py-1 rounded bg-gray-500/10 text-sm"
+          placeholder="Lifecycle table URL or path, the bundled one is used by default"
+          value={lifecycleSource}
+          onChange={(e) => setLifecycleSource(e.target.value)}
+          onKeyDown={(e) => e.key === "Enter" && updateLifecycleTable()}
+        />
+        <ArrowPathIcon
+          className="shrink-0 w-6 h-6 p-1 rounded-full hover:bg-gray-500/20 cursor-pointer"
+          onClick={updateLifecycleTable}
+        />
+      </div>
+      {lifecycleUpdated != null && (
+        <p className="mt-1 text-sm opacity-70">
+          Updated, the table is from {lifecycleUpdated}.
+        </p>
+      )}
+
+      <p className="mt-4 font-bold">Verification</p>
+
+      <SettingsCheckbox
+        label="Refuse JDK packages whose signature cannot be verified"
+        checked={settings.strict_signatures}
+        onChange={(value) => update("update_strict_signatures", value)}
+      />
+
+      <p className="mt-2 text-sm">Trusted minisign public keys, one per line</p>
+      <textarea
+        className="mt-1 w-full px-2 py-1 rounded bg-gray-500/10 text-sm font-mono"
+        rows={2}
+        value={minisignKeys}
+        onChange={(e) => setMinisignKeys(e.target.value)}
+        onBlur={() =>
+          updateTrustedKeys(
+            minisignKeys.split("\n"),
+            settings.trusted_gpg_keys
+          )
+        }
+      />
+
+      <div className="mt-2 flex items-center">
+        <p className="flex-1 text-sm">Trusted GPG public key files</p>
+        <PlusIcon
+          className="shrink-0 w-6 h-6 p-0.5 rounded-full hover:bg-gray-500/20 cursor-pointer"
+          onClick={addGpgKey}
+        />
+      </div>
+      <ul>
+        {settings.trusted_gpg_keys.map((file) => (
+          <li key={file} className="py-1 flex items-center">
+            <p className="flex-1 text-sm truncate" title={file}>
+              {file}
+            </p>
+            <XMarkIcon
+              className="shrink-0 w-5 h-5 stroke-gray-400 hover:stroke-red-500 cursor-pointer"
+              onClick={() =>
+                updateTrustedKeys(
+                  settings.trusted_minisign_keys,
+                  settings.trusted_gpg_keys.filter((item) => item !== file)
+                )
+              }
+            />
+          </li>
+        ))}
+      </ul>
+    </dialog>
+  );
+}
+
+function SettingsCheckbox({
+  label,
+  checked,
+  onChange,
+}: {
+  label: string;
+  checked: boolean;
+  onChange: (value: boolean) => void;
+}) {
+  return (
+    <div
+      className="mt-2 flex items-center cursor-pointer"
+      onClick={() => onChange(!checked)}
+    >
+      <input
+        type="checkbox"
+        className="mr-2 accent-violet-500"
+        checked={checked}
+        onChange={(e) => onChange(e.target.checked)}
+        onClick={(e) => e.stopPropagation()}
+      />
+      <p>{label}</p>
+    </div>
+  );
+}
@@ -10,9 +10,29 @@ export enum AppTheme {
 export type AppSettings = {
   theme: AppTheme;
   skip_dir_selection_hint: boolean;
+  hide_jres: boolean;
+  reject_jres: boolean;
+  scan_max_depth: number;
+  scan_excludes: string[];
+  scan_follow_links: boolean;
+  scan_roots: string[];
+  rescan_on_startup: boolean;
+  verify_on_startup: boolean;
+  check_updates_on_startup: boolean;
+  discovery_offered: boolean;
+  sync_maven_toolchains: boolean;
+  sync_gradle_paths: boolean;
+  disco_catalog_enabled: boolean;
+  catalog_sources: string[];
+  trusted_minisign_keys: string[];
+  trusted_gpg_keys: string[];
+  strict_signatures: boolean;
+  known_projects: string[];
+  lifecycle_source: string;
 };
 
 export type AppUiState = {
   settings: AppSettings;
   jdks: Jdk[];
+  discovered_jdks: Jdk[];
 };
@@ -0,0 +1,63 @@
+import { Jdk } from "./Jdk";
+
+export type CatalogDistribution = {
+  name: string;
+  api_parameter: string;
+};
+
+export type CatalogMajorVersion = {
+  major_version: number;
+  term_of_support: string;
+  maintained: boolean;
+};
+
+export type CatalogPackage = {
+  id: string;
+  archive_type: string;
+  distribution: string;
+  major_version: number;
+  java_version: string;
+  distribution_version: string;
+  operating_system: string;
+  architecture: string;
+  package_type: string;
+  filename: string;
+  size: number;
+  term_of_support: string;
+  catalog: string;
+};
+
+export type SignatureStatus =
+  | { status: "verified" }
+  | { status: "unsigned" }
+  | { status: "unverified"; reason: string };
+
+export type InstallResult = {
+  jdks: Jdk[];
+  signature: SignatureStatus;
+};
+
+export type UpdateReport = {
+  checked: number;
+  outdated: string[];
+};
+
+export type UpgradeResult = {
+  old: Jdk;
+  new: Jdk;
+  signature: SignatureStatus;
+  migrated_pins: string[];
+  switched: boolean;
+};
+
+export type UninstallReport = {
+  removed: string[];
+  freed_bytes: number;
+};
+
+export type InstallProgress = {
+  package: string;
+  stage: "downloading" | "verifying" | "extracting" | "finished";
+  downloaded: number;
+  total: number | null;
+};
@@ -0,0 +1,16 @@
+import { Jdk } from "./Jdk";
+
+export type JarInfo = {
+  class_major: number | null;
+  min_java: number | null;
+  multi_release: boolean;
+  release_versions: number[];
+  created_by: string | null;
+  build_jdk_spec: string | null;
+  main_class: string | null;
+};
+
+export type JarInspection = {
+  info: JarInfo;
+  jdk: Jdk | null;
+};
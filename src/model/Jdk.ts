@@ -1,8 +1,43 @@
+export type JdkCapabilities = {
+  javac: boolean;
+  jlink: boolean;
+  jpackage: boolean;
+  jshell: boolean;
+  native_image: boolean;
+  jfr: boolean;
+  src_zip: boolean;
+  jmods: boolean;
+  jvm: "hotspot" | "openj9" | "unknown";
+};
+
+export type JdkSource =
+  | "sdkman"
+  | "jenv"
+  | "asdf"
+  | "mise"
+  | "intellij_idea"
+  | "gradle"
+  | "jet_brains_ide"
+  | "android_studio"
+  | "system"
+  | "registry"
+  | "installed"
+  | "manual";
+
 export type Jdk = {
   name: string;
   path: string;
   version: string;
   arch: string;
+  distribution: string;
+  capabilities: JdkCapabilities;
+  source: JdkSource;
   is_valid: boolean;
   is_current: boolean;
+  is_foreign_arch: boolean;
+  verified_digest: string | null;
+  integrity: "unknown" | "intact" | "modified" | "corrupted";
+  latest_available: string | null;
+  support: "unknown" | "supported" | "ending_soon" | "end_of_life";
+  support_until: string | null;
 };
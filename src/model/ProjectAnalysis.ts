@@ -0,0 +1,14 @@
+import { Jdk } from "./Jdk";
+
+export type ProjectRequirement = {
+  file: string;
+  key: string;
+  distribution: string | null;
+  version: string | null;
+};
+
+export type ProjectAnalysis = {
+  requirements: ProjectRequirement[];
+  requirement: string | null;
+  jdk: Jdk | null;
+};
@@ -0,0 +1,6 @@
+export type ScanProgress = {
+  dirs_visited: number;
+  jdks_found: number;
+  current_path: string;
+  is_finished: boolean;
+};